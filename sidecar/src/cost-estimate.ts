@@ -0,0 +1,39 @@
+/** Token counts as reported on an assistant message */
+interface TokenUsage {
+  readonly input_tokens: number;
+  readonly output_tokens: number;
+  readonly cache_creation_input_tokens?: number | null;
+  readonly cache_read_input_tokens?: number | null;
+}
+
+/**
+ * List prices in USD per million tokens, most specific model match first.
+ * Only used for the running estimate between results; each `result` message
+ * replaces it with the SDK's own total.
+ */
+const PRICES: ReadonlyArray<{ match: string; input: number; output: number }> = [
+  { match: "opus-4-5", input: 5, output: 25 },
+  { match: "opus", input: 15, output: 75 },
+  { match: "sonnet", input: 3, output: 15 },
+  { match: "haiku-4", input: 1, output: 5 },
+  { match: "haiku", input: 0.8, output: 4 },
+];
+
+/** Cache writes cost more than plain input, cache reads much less */
+const CACHE_WRITE_FACTOR = 1.25;
+const CACHE_READ_FACTOR = 0.1;
+
+/** Estimated cost of one assistant message, or null for a model we have no price for */
+function estimateCostUsd(model: string | undefined, usage: TokenUsage): number | null {
+  const price = model ? PRICES.find((p) => model.includes(p.match)) : undefined;
+  if (!price) return null;
+
+  const inputTokens =
+    usage.input_tokens +
+    (usage.cache_creation_input_tokens ?? 0) * CACHE_WRITE_FACTOR +
+    (usage.cache_read_input_tokens ?? 0) * CACHE_READ_FACTOR;
+  return (inputTokens * price.input + usage.output_tokens * price.output) / 1_000_000;
+}
+
+export { estimateCostUsd };
+export type { TokenUsage };
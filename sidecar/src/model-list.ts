@@ -0,0 +1,34 @@
+import { query, type SDKUserMessage } from "@anthropic-ai/claude-agent-sdk";
+import type { SidecarEvent } from "./types.js";
+
+/** Prompt stream that never yields — keeps the query open for control requests */
+async function* idlePrompt(signal: AbortSignal): AsyncGenerator<SDKUserMessage> {
+  await new Promise<void>((resolve) => signal.addEventListener("abort", () => resolve()));
+}
+
+/** Ask the SDK which models this account can use and emit them as `models_list` */
+async function listModels(
+  emit: (event: SidecarEvent) => void,
+  log: (msg: string) => void,
+): Promise<void> {
+  const abortController = new AbortController();
+  const q = query({
+    prompt: idlePrompt(abortController.signal),
+    options: { abortController, stderr: (data: string) => log(`SDK: ${data.trimEnd()}`) },
+  });
+
+  try {
+    const models = await q.supportedModels();
+    emit({
+      type: "models_list",
+      models: models.map((m) => ({ id: m.value, displayName: m.displayName, description: m.description })),
+    });
+    log(`Reported ${models.length} models`);
+  } catch (e: unknown) {
+    emit({ type: "error", message: `Failed to list models: ${e instanceof Error ? e.message : String(e)}` });
+  } finally {
+    abortController.abort();
+  }
+}
+
+export { listModels };
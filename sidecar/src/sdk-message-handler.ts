@@ -1,12 +1,27 @@
 import type { SDKMessage } from "@anthropic-ai/claude-agent-sdk";
 import type { SidecarEvent, ToolCallInfo } from "./types.js";
+import { estimateCostUsd } from "./cost-estimate.js";
 
 let sdkSessionId = "";
+let sessionModel: string | undefined;
+
+/** Cumulative cost from the last `result` message */
+let costAtLastResult = 0;
+/** Estimated cost of each assistant message since then, by message id (streamed parts repeat the id) */
+const estimatedSinceResult = new Map<string, number>();
+
+/** Tools the model has called whose results haven't come back: tool_use id -> name */
+const inFlightTools = new Map<string, string>();
 
 function getSdkSessionId(): string {
   return sdkSessionId;
 }
 
+/** Whether a call to `toolName` is running right now */
+function isToolInFlight(toolName: string): boolean {
+  return [...inFlightTools.values()].includes(toolName);
+}
+
 function processSDKMessage(
   sessionId: string,
   msg: SDKMessage,
@@ -36,6 +51,7 @@ function handleSystem(
 ): void {
   if ("subtype" in msg && msg.subtype === "init") {
     sdkSessionId = msg.session_id;
+    sessionModel = msg.model;
     emit({ type: "session_started", sessionId, sdkSessionId });
     log(`Session init: model=${msg.model}, tools=${msg.tools.length}, sdk_sid=${sdkSessionId}`);
   } else if ("subtype" in msg) {
@@ -61,6 +77,7 @@ function handleAssistant(
     } else if (block.type === "thinking" && "thinking" in block) {
       thinking = block.thinking as string;
     } else if (block.type === "tool_use") {
+      inFlightTools.set(block.id, block.name);
       toolCalls.push({ name: block.name, input: block.input as Record<string, unknown> });
     }
   }
@@ -69,6 +86,8 @@ function handleAssistant(
     log(`Assistant error: ${assistantError} content="${textContent.slice(0, 100)}"`);
   }
 
+  emitCostEstimate(sessionId, msg, emit);
+
   if (textContent || thinking || msg.message.usage) {
     emit({
       type: "message",
@@ -84,6 +103,30 @@ function handleAssistant(
   }
 }
 
+/** Spend so far including this turn's assistant messages, so the meter climbs before the result */
+function emitCostEstimate(
+  sessionId: string,
+  msg: Extract<SDKMessage, { type: "assistant" }>,
+  emit: (event: SidecarEvent) => void,
+): void {
+  const usage = msg.message.usage;
+  const estimate = usage ? estimateCostUsd(msg.message.model ?? sessionModel, usage) : null;
+  if (estimate === null) return;
+
+  estimatedSinceResult.set(msg.message.id, estimate);
+  let costUsd = costAtLastResult;
+  for (const cost of estimatedSinceResult.values()) costUsd += cost;
+  emit({ type: "cost_update", sessionId, costUsd });
+}
+
+/** Tool results come back to the model as user messages */
+function handleUser(_sessionId: string, msg: SDKMessage): void {
+  if (msg.type !== "user" || typeof msg.message.content === "string") return;
+  for (const block of msg.message.content) {
+    if (block.type === "tool_result") inFlightTools.delete(block.tool_use_id);
+  }
+}
+
 function handleToolProgress(
   sessionId: string,
   msg: SDKMessage,
@@ -105,9 +148,15 @@ function handleResult(
   log: (msg: string) => void,
 ): void {
   if (msg.type !== "result") return;
+  inFlightTools.clear();
+
+  // Cumulative across turns; replaces the running estimate with the SDK's figure
+  costAtLastResult = msg.total_cost_usd;
+  estimatedSinceResult.clear();
+  emit({ type: "cost_update", sessionId, costUsd: msg.total_cost_usd });
 
   if (msg.subtype === "success") {
-    emit({ type: "session_completed", sessionId, sdkSessionId, totalCostUsd: msg.total_cost_usd, durationMs: msg.duration_ms });
+    emit({ type: "session_completed", sessionId, sdkSessionId, totalCostUsd: msg.total_cost_usd, durationMs: msg.duration_ms, model: sessionModel });
     log(`Session completed: ${msg.duration_ms}ms, cost=$${msg.total_cost_usd}, turns=${msg.num_turns}`);
   } else {
     const reason = msg.subtype === "error_max_budget_usd"
@@ -134,15 +183,34 @@ function handleAuthStatus(
   log(`Auth status: authenticating=${authMsg.isAuthenticating} error=${authMsg.error ?? "none"}`);
 }
 
+/** Indices of thinking content blocks that are still streaming */
+const openThinkingBlocks = new Set<number>();
+
 function handleStreamEvent(
   sessionId: string,
   msg: SDKMessage,
   emit: (event: SidecarEvent) => void,
 ): void {
   const event = (msg as Record<string, unknown>).event as Record<string, unknown> | undefined;
-  if (!event || event.type !== "content_block_delta") return;
+  if (!event) return;
+
+  if (event.type === "content_block_start") {
+    const block = event.content_block as Record<string, unknown> | undefined;
+    if (block?.type === "thinking") openThinkingBlocks.add(event.index as number);
+  } else if (event.type === "content_block_stop") {
+    if (openThinkingBlocks.delete(event.index as number)) {
+      emit({ type: "thinking", sessionId, text: "", done: true });
+    }
+  } else if (event.type === "content_block_delta") {
+    handleContentBlockDelta(sessionId, event.delta as Record<string, unknown> | undefined, emit);
+  }
+}
 
-  const delta = event.delta as Record<string, unknown> | undefined;
+function handleContentBlockDelta(
+  sessionId: string,
+  delta: Record<string, unknown> | undefined,
+  emit: (event: SidecarEvent) => void,
+): void {
   if (!delta) return;
 
   if (delta.type === "text_delta" && typeof delta.text === "string") {
@@ -177,6 +245,7 @@ function handleRateLimitEvent(
 const messageHandlers: Record<string, Handler> = {
   system: handleSystem,
   assistant: handleAssistant,
+  user: handleUser,
   tool_progress: handleToolProgress,
   result: handleResult,
   auth_status: handleAuthStatus,
@@ -184,4 +253,4 @@ const messageHandlers: Record<string, Handler> = {
   rate_limit_event: handleRateLimitEvent,
 };
 
-export { processSDKMessage, getSdkSessionId };
+export { processSDKMessage, getSdkSessionId, isToolInFlight };
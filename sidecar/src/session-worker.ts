@@ -1,19 +1,22 @@
 /**
  * Per-session sidecar worker. One Node.js process per agent session.
  *
- * Protocol (stdin JSON-lines): start_session, send_message, abort_session,
- * end_session, tool_approval_response
+ * Protocol (stdin JSON-lines): start_session, prewarm_session, send_message, abort_session,
+ * end_session, cancel_tool, reprompt, set_model, set_permission_mode,
+ * tool_approval_response, list_models (one-shot)
  *
  * Protocol (stdout JSON-lines): session_started, message, tool_use, tool_result,
- * tool_approval_request, tool_progress, session_completed, session_failed
+ * tool_approval_request, tool_progress, turn_interrupted, session_completed, session_failed,
+ * reprompt_status, model_changed, permission_mode_changed, models_list
  */
 
 import * as readline from "node:readline";
-import { query, type SDKUserMessage } from "@anthropic-ai/claude-agent-sdk";
-import type { SidecarEvent, WorkerCommand } from "./types.js";
+import { query, type PermissionMode, type Query, type SDKUserMessage } from "@anthropic-ai/claude-agent-sdk";
+import type { PermissionModeName, SidecarEvent, WorkerCommand } from "./types.js";
 import { createAsyncQueue } from "./async-queue.js";
-import { requestToolApproval, resolveApproval } from "./tool-approval.js";
-import { processSDKMessage } from "./sdk-message-handler.js";
+import { cancelPendingApprovals, requestToolApproval, resolveApproval } from "./tool-approval.js";
+import { isToolInFlight, processSDKMessage } from "./sdk-message-handler.js";
+import { listModels } from "./model-list.js";
 
 function log(msg: string): void {
   process.stderr.write(`[SESSION-WORKER] ${msg}\n`);
@@ -61,17 +64,19 @@ function buildQueryOptions(
 async function runSession(
   sessionId: string,
   projectPath: string,
-  prompt: string,
+  prompt: string | undefined,
   model: string | undefined,
   maxBudgetUsd: number | undefined,
   resumeSessionId: string | undefined,
   followUps: ReturnType<typeof createAsyncQueue<string>>,
   abortController: AbortController,
+  onQuery: (q: Query) => void,
 ): Promise<void> {
   log(`Starting SDK query: sid=${sessionId}, cwd=${projectPath}, model=${model ?? "default"}, resume=${resumeSessionId ?? "none"}`);
 
+  // A prewarmed session has no prompt yet: its first follow-up opens the conversation
   async function* promptGenerator(): AsyncGenerator<SDKUserMessage> {
-    yield makeUserMessage(sessionId, prompt);
+    if (prompt !== undefined) yield makeUserMessage(sessionId, prompt);
     const iter = followUps.iterator();
     while (true) {
       const { value, done } = await iter.next();
@@ -82,10 +87,14 @@ async function runSession(
 
   const options = buildQueryOptions(sessionId, projectPath, model, maxBudgetUsd, resumeSessionId, abortController);
   const q = query({ prompt: promptGenerator(), options });
+  onQuery(q);
 
   try {
     for await (const msg of q) {
-      if (abortController.signal.aborted) break;
+      if (abortController.signal.aborted) {
+        emit({ type: "session_failed", sessionId, error: "Aborted by user" });
+        break;
+      }
       processSDKMessage(sessionId, msg, emit, log);
     }
   } catch (e: unknown) {
@@ -102,26 +111,115 @@ async function runSession(
 interface WorkerContext {
   sessionId: string;
   started: boolean;
+  query?: Query;
   readonly followUpQueue: ReturnType<typeof createAsyncQueue<string>>;
   readonly abortController: AbortController;
 }
 
+/**
+ * Stop a tool call. One still awaiting approval is denied and reported as a
+ * cancelled tool_result. The SDK can't stop a running tool on its own, so a
+ * tool already executing interrupts the whole turn and is reported as
+ * turn_interrupted instead. Anything else is an error: there's nothing to cancel.
+ */
+function cancelTool(ctx: WorkerContext, toolName: string): void {
+  const sessionId = ctx.sessionId;
+  const denied = cancelPendingApprovals(toolName);
+  if (denied > 0) {
+    log(`Cancel tool ${toolName}: denied ${denied} pending approval(s)`);
+    emit({ type: "tool_result", sessionId, toolName, output: "Cancelled by user", cancelled: true });
+  } else if (ctx.query && isToolInFlight(toolName)) {
+    log(`Cancel tool ${toolName}: running, interrupting current turn`);
+    ctx.query.interrupt().catch((e: unknown) => log(`Interrupt failed: ${String(e)}`));
+    emit({ type: "turn_interrupted", sessionId, toolName });
+  } else {
+    emit({ type: "error", message: `Cannot cancel ${toolName}: it is not pending or running` });
+  }
+}
+
+/**
+ * Interrupt the running turn and queue `message` once it has settled. Before
+ * the session has started there is nothing to interrupt, so only `sent` is reported.
+ */
+async function reprompt(ctx: WorkerContext, message: string): Promise<void> {
+  const sessionId = ctx.sessionId;
+  if (ctx.query) {
+    emit({ type: "reprompt_status", sessionId, stage: "interrupting" });
+    await ctx.query.interrupt().catch((e: unknown) => log(`Interrupt failed: ${String(e)}`));
+    emit({ type: "reprompt_status", sessionId, stage: "interrupted" });
+  }
+  ctx.followUpQueue.push(message);
+  emit({ type: "reprompt_status", sessionId, stage: "sent" });
+}
+
+/** Switch model mid-session; failures are reported rather than ignored */
+async function setModel(ctx: WorkerContext, model: string): Promise<void> {
+  if (!ctx.query) {
+    emit({ type: "error", message: `Cannot change model: session ${ctx.sessionId} has not started` });
+    return;
+  }
+  try {
+    await ctx.query.setModel(model);
+    emit({ type: "model_changed", sessionId: ctx.sessionId, model });
+  } catch (e: unknown) {
+    emit({ type: "error", message: `Cannot change model to ${model}: ${String(e)}` });
+  }
+}
+
+const SDK_PERMISSION_MODES: Record<PermissionModeName, PermissionMode> = {
+  ask: "default",
+  auto_edit: "acceptEdits",
+  plan: "plan",
+};
+
+/** Change how later tool calls are approved; confirmed with permission_mode_changed */
+async function setPermissionMode(ctx: WorkerContext, mode: PermissionModeName): Promise<void> {
+  if (!ctx.query) {
+    emit({ type: "error", message: `Cannot change permission mode: session ${ctx.sessionId} has not started` });
+    return;
+  }
+  try {
+    await ctx.query.setPermissionMode(SDK_PERMISSION_MODES[mode]);
+    emit({ type: "permission_mode_changed", sessionId: ctx.sessionId, mode });
+  } catch (e: unknown) {
+    emit({ type: "error", message: `Cannot change permission mode to ${mode}: ${String(e)}` });
+  }
+}
+
+/** start_session fields, with the prompt optional for prewarmed sessions */
+type LaunchOptions = Omit<Extract<WorkerCommand, { type: "start_session" }>, "type" | "prompt"> & {
+  prompt?: string;
+};
+
+/** Run the session to completion, then exit the process */
+function launchSession(ctx: WorkerContext, cmd: LaunchOptions): void {
+  ctx.started = true;
+  ctx.sessionId = cmd.sessionId;
+  runSession(ctx.sessionId, cmd.projectPath, cmd.prompt, cmd.model, cmd.maxBudgetUsd, cmd.resumeSessionId, ctx.followUpQueue, ctx.abortController, (q) => { ctx.query = q; })
+    .catch((e: unknown) => {
+      const errMsg = e instanceof Error ? e.message : String(e);
+      if (!errMsg.includes("abort")) {
+        log(`Session error: ${errMsg}`);
+        emit({ type: "session_failed", sessionId: ctx.sessionId, error: errMsg });
+      }
+    })
+    .finally(() => {
+      log("Session ended, exiting process");
+      setTimeout(() => process.exit(0), 100);
+    });
+}
+
 function handleCommand(ctx: WorkerContext, cmd: WorkerCommand): void {
   if (cmd.type === "start_session" && !ctx.started) {
-    ctx.started = true;
-    ctx.sessionId = cmd.sessionId;
-    runSession(ctx.sessionId, cmd.projectPath, cmd.prompt, cmd.model, cmd.maxBudgetUsd, cmd.resumeSessionId, ctx.followUpQueue, ctx.abortController)
-      .catch((e: unknown) => {
-        const errMsg = e instanceof Error ? e.message : String(e);
-        if (!errMsg.includes("abort")) {
-          log(`Session error: ${errMsg}`);
-          emit({ type: "session_failed", sessionId: ctx.sessionId, error: errMsg });
-        }
-      })
-      .finally(() => {
-        log("Session ended, exiting process");
-        setTimeout(() => process.exit(0), 100);
-      });
+    launchSession(ctx, cmd);
+  } else if (cmd.type === "prewarm_session" && !ctx.started) {
+    log("Prewarming: SDK starting, waiting for the first message");
+    launchSession(ctx, {
+      sessionId: cmd.sessionId,
+      projectPath: cmd.projectPath,
+      model: cmd.model,
+      resumeSessionId: cmd.resumeSessionId,
+    });
   } else if (cmd.type === "send_message") {
     ctx.followUpQueue.push(cmd.message);
   } else if (cmd.type === "end_session") {
@@ -131,6 +229,16 @@ function handleCommand(ctx: WorkerContext, cmd: WorkerCommand): void {
     log("Abort requested");
     ctx.abortController.abort();
     ctx.followUpQueue.close();
+  } else if (cmd.type === "cancel_tool") {
+    cancelTool(ctx, cmd.toolName);
+  } else if (cmd.type === "set_model") {
+    setModel(ctx, cmd.model).catch((e: unknown) => log(`Set model failed: ${String(e)}`));
+  } else if (cmd.type === "set_permission_mode") {
+    setPermissionMode(ctx, cmd.mode).catch((e: unknown) => log(`Set permission mode failed: ${String(e)}`));
+  } else if (cmd.type === "reprompt") {
+    reprompt(ctx, cmd.message).catch((e: unknown) => log(`Reprompt failed: ${String(e)}`));
+  } else if (cmd.type === "list_models") {
+    listModels(emit, log).finally(() => process.exit(0));
   } else if (cmd.type === "tool_approval_response") {
     resolveApproval(cmd.requestId, cmd.allowed, cmd.updatedPermissions);
   }
@@ -156,6 +264,14 @@ async function main(): Promise<void> {
     }
   });
 
+  // Sent by interrupt_agent_session: cancel the in-flight request and let
+  // runSession report it instead of dying mid-turn
+  process.on("SIGINT", () => {
+    log("SIGINT received, aborting");
+    ctx.abortController.abort();
+    ctx.followUpQueue.close();
+  });
+
   rl.on("close", () => {
     log("stdin closed, aborting");
     ctx.abortController.abort();
@@ -5,6 +5,7 @@ import type {
 import type { SidecarEvent, PermissionUpdateInfo } from "./types.js";
 
 interface PendingApproval {
+  readonly toolName: string;
   readonly input: Record<string, unknown>;
   readonly resolve: (result: PermissionResult) => void;
 }
@@ -63,6 +64,7 @@ function requestToolApproval(
     signal.addEventListener("abort", onAbort, { once: true });
 
     pendingApprovals.set(requestId, {
+      toolName,
       input,
       resolve: (result) => {
         signal.removeEventListener("abort", onAbort);
@@ -92,4 +94,16 @@ function resolveApproval(
   }
 }
 
-export { requestToolApproval, resolveApproval };
+/** Deny every pending approval for `toolName`. Returns how many were cancelled. */
+function cancelPendingApprovals(toolName: string): number {
+  let cancelled = 0;
+  for (const [requestId, pending] of pendingApprovals) {
+    if (pending.toolName !== toolName) continue;
+    pendingApprovals.delete(requestId);
+    pending.resolve({ behavior: "deny", message: "Cancelled by user" });
+    cancelled++;
+  }
+  return cancelled;
+}
+
+export { requestToolApproval, resolveApproval, cancelPendingApprovals };
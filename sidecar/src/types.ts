@@ -10,9 +10,18 @@ type WorkerCommand =
       /** SDK session ID from a prior run — enables conversation resume */
       resumeSessionId?: string;
     }
+  /** Start the SDK now; the first send_message becomes the opening prompt */
+  | { type: "prewarm_session"; sessionId: string; projectPath: string; model?: string; resumeSessionId?: string }
   | { type: "send_message"; sessionId: string; message: string }
   | { type: "abort_session"; sessionId: string }
   | { type: "end_session"; sessionId: string }
+  | { type: "cancel_tool"; sessionId: string; toolName: string }
+  | { type: "set_model"; sessionId: string; model: string }
+  | { type: "set_permission_mode"; sessionId: string; mode: PermissionModeName }
+  /** Interrupt the running turn, then send `message` as the next prompt */
+  | { type: "reprompt"; sessionId: string; message: string }
+  /** One-shot: report available models, then exit */
+  | { type: "list_models" }
   | {
       type: "tool_approval_response";
       requestId: string;
@@ -21,6 +30,16 @@ type WorkerCommand =
       updatedPermissions?: PermissionUpdateInfo[];
     };
 
+/** Approval modes exposed to the app; the worker maps them onto SDK modes */
+type PermissionModeName = "ask" | "auto_edit" | "plan";
+
+/** A model the SDK reports as usable by this account */
+interface ModelListEntry {
+  id: string;
+  displayName: string;
+  description?: string;
+}
+
 /** Mirrors SDK's PermissionUpdate for serialization over stdin */
 interface PermissionUpdateInfo {
   type: "addRules" | "replaceRules" | "removeRules" | "setMode" | "addDirectories" | "removeDirectories";
@@ -44,7 +63,7 @@ type SidecarEvent =
       usage?: UsageInfo;
     }
   | { type: "tool_use"; sessionId: string; toolName: string; input: Record<string, unknown> }
-  | { type: "tool_result"; sessionId: string; toolName: string; output: string }
+  | { type: "tool_result"; sessionId: string; toolName: string; output: string; cancelled?: boolean }
   | {
       type: "tool_approval_request";
       sessionId: string;
@@ -56,16 +75,27 @@ type SidecarEvent =
     }
   | { type: "content_delta"; sessionId: string; delta: string }
   | { type: "thinking_delta"; sessionId: string; delta: string }
+  /** End of a streamed thinking block; the chunks themselves arrive as thinking_delta */
+  | { type: "thinking"; sessionId: string; text: string; done: boolean }
   | { type: "tool_progress"; sessionId: string; toolName: string; elapsedSeconds: number }
+  /** cancel_tool hit a running tool: the whole turn was interrupted, not just that tool */
+  | { type: "turn_interrupted"; sessionId: string; toolName: string }
   | {
       type: "session_completed";
       sessionId: string;
       sdkSessionId: string;
       totalCostUsd?: number;
       durationMs?: number;
+      /** Model the SDK actually ran with (may differ from the requested one on fallback) */
+      model?: string;
     }
+  | { type: "cost_update"; sessionId: string; costUsd: number }
+  | { type: "model_changed"; sessionId: string; model: string }
+  | { type: "permission_mode_changed"; sessionId: string; mode: PermissionModeName }
+  | { type: "reprompt_status"; sessionId: string; stage: "interrupting" | "interrupted" | "sent" }
   | { type: "session_failed"; sessionId: string; error: string }
   | { type: "error"; message: string }
+  | { type: "models_list"; models: ModelListEntry[] }
   | {
       type: "rate_limit_status";
       sessionId: string;
@@ -90,4 +120,5 @@ export type {
   ToolCallInfo,
   UsageInfo,
   PermissionUpdateInfo,
+  PermissionModeName,
 };
@@ -1,3 +1,17 @@
+#[path = "src/content_hash.rs"]
+mod content_hash;
+
+/// Bake the worker script's hash into the binary so `verify_worker` can spot
+/// a corrupted install. Skipped when the script isn't next to the sources.
+fn bake_worker_hash() {
+    let worker = std::path::Path::new("../sidecar/src/session-worker.ts");
+    println!("cargo:rerun-if-changed={}", worker.display());
+    if let Ok(bytes) = std::fs::read(worker) {
+        println!("cargo:rustc-env=CENTRAL_WORKER_HASH={}", content_hash::content_hash(&bytes));
+    }
+}
+
 fn main() {
+    bake_worker_hash();
     tauri_build::build()
 }
@@ -0,0 +1,7 @@
+/// Milliseconds since the Unix epoch, for timestamps we serialize to the frontend
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
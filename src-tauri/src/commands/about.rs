@@ -0,0 +1,89 @@
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::debug_log;
+use crate::shell_env::find_executable;
+
+static NODE_VERSION: OnceLock<Option<String>> = OnceLock::new();
+static CLAUDE_VERSION: OnceLock<Option<String>> = OnceLock::new();
+
+/// Versions to paste into a bug report. Tools that aren't installed are None.
+#[derive(Debug, Serialize)]
+pub struct AboutInfo {
+    pub app_version: String,
+    pub tauri_version: String,
+    pub node_version: Option<String>,
+    pub claude_version: Option<String>,
+    pub git2_version: String,
+    pub libgit2_version: String,
+}
+
+/// App and toolchain versions for the about/diagnostics screen. `node` and
+/// `claude` are run once per launch; later calls reuse the cached answer.
+#[tauri::command]
+pub async fn about_info(app: AppHandle) -> Result<AboutInfo, String> {
+    let (node_version, claude_version) = tokio::task::spawn_blocking(|| {
+        (
+            NODE_VERSION.get_or_init(|| tool_version("node")).clone(),
+            CLAUDE_VERSION
+                .get_or_init(|| tool_version("claude"))
+                .clone(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to query tool versions: {e}"))?;
+
+    let git = git2::Version::get();
+    let (major, minor, patch) = git.libgit2_version();
+    Ok(AboutInfo {
+        app_version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        node_version,
+        claude_version,
+        git2_version: git.crate_version().to_string(),
+        libgit2_version: format!("{major}.{minor}.{patch}"),
+    })
+}
+
+/// `<tool> --version`, resolved on PATH the way workers find it
+fn tool_version(tool: &str) -> Option<String> {
+    let path = find_executable(tool)?;
+    let output = Command::new(&path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| debug_log::log("ABOUT", &format!("WARN: {tool} --version failed: {e}")))
+        .ok()?;
+    parse_version_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First non-empty line, e.g. "v20.11.1" or "1.0.43 (Claude Code)"
+fn parse_version_output(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_takes_first_line() {
+        assert_eq!(
+            parse_version_output("v20.11.1\n"),
+            Some("v20.11.1".to_string())
+        );
+        assert_eq!(
+            parse_version_output("\n 1.0.43 (Claude Code)\nextra\n"),
+            Some("1.0.43 (Claude Code)".to_string())
+        );
+        assert_eq!(parse_version_output("  \n"), None);
+    }
+}
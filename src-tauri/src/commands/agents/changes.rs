@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use tauri::State;
+
+use crate::commands::files::diff::diff_paths;
+use crate::commands::files::types::FileDiff;
+use crate::sidecar::SidecarHandle;
+
+/// Diff of just the files this session's tools wrote, isolating the agent's
+/// work from unrelated local edits elsewhere in the tree
+#[tauri::command]
+pub async fn get_session_diff(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Vec<FileDiff>, String> {
+    let meta = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .session_metadata(&session_id)
+        .ok_or_else(|| format!("Unknown session: {session_id}"))?;
+
+    let project = Path::new(&meta.project_path);
+    let paths: Vec<String> = meta
+        .files_written
+        .iter()
+        .filter_map(|file| relative_to_project(project, file))
+        .collect();
+
+    diff_paths(&meta.project_path, &paths)
+}
+
+/// Tools usually report absolute paths; files outside the project are dropped
+fn relative_to_project(project: &Path, file: &str) -> Option<String> {
+    let path = Path::new(file);
+    if path.is_relative() {
+        return Some(file.to_string());
+    }
+
+    let canonical_project = project.canonicalize().ok();
+    let canonical_file = path.canonicalize().ok();
+    let rel = path.strip_prefix(project).ok().or_else(|| {
+        canonical_file
+            .as_deref()?
+            .strip_prefix(canonical_project.as_deref()?)
+            .ok()
+    })?;
+    Some(rel.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_to_project_strips_project_prefix() {
+        let project = Path::new("/work/app");
+        assert_eq!(
+            relative_to_project(project, "/work/app/src/a.rs").as_deref(),
+            Some("src/a.rs")
+        );
+        assert_eq!(
+            relative_to_project(project, "src/b.rs").as_deref(),
+            Some("src/b.rs")
+        );
+        assert_eq!(relative_to_project(project, "/etc/passwd"), None);
+    }
+}
@@ -0,0 +1,218 @@
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use super::snapshot;
+use crate::debug_log;
+use crate::project_path::{path_string, resolve_project_path};
+use crate::sidecar::{drain_all, DrainReport, SidecarCommand, SidecarError, SidecarHandle};
+
+/// Modes accepted by `set_session_permission_mode`
+const PERMISSION_MODES: &[&str] = &["ask", "auto_edit", "plan"];
+
+/// How long an interrupted worker gets to report the cancellation
+const DEFAULT_INTERRUPT_GRACE_MS: u64 = 3000;
+
+const INTERRUPT_POLL: Duration = Duration::from_millis(50);
+
+/// How long `drain_all_sessions` waits for workers by default
+const DEFAULT_DRAIN_TIMEOUT_MS: u64 = 5000;
+
+/// Spawn a session's worker and initialize the SDK while the user is still
+/// typing. Send the opening prompt with `send_agent_message`; a worker that
+/// never gets one is ended after `keep_alive::IDLE_TIMEOUT`.
+#[tauri::command]
+pub async fn prewarm_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    project_path: String,
+    model: Option<String>,
+    window_label: Option<String>,
+) -> Result<String, SidecarError> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("prewarm_session: sid={session_id}, path={project_path}"),
+    );
+
+    let project_path = resolve_project_path(&project_path).map_err(String::from)?;
+    let command = SidecarCommand::PrewarmSession {
+        session_id: session_id.clone(),
+        project_path: path_string(&project_path),
+        model,
+        resume_session_id: None,
+    };
+
+    sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .prewarm_session(&command, window_label)?;
+    Ok(session_id)
+}
+
+/// Bring back a session whose worker crashed (or is wedged): the old
+/// worker is killed and a new one resumes the SDK conversation. It waits
+/// for the next message unless `resume_prompt` asks it to carry on.
+/// Sessions stopped for going over budget can't be restarted.
+#[tauri::command]
+pub async fn restart_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    resume_prompt: Option<bool>,
+) -> Result<(), SidecarError> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("restart_agent_session: sid={session_id}"),
+    );
+
+    sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .restart_session(&session_id, resume_prompt.unwrap_or(false))
+}
+
+/// Drop the backend state of a deleted session: its worker if one is still
+/// running, metadata, event buffer, tool history, retired start params and
+/// its snapshot ref. `project_path` locates the ref for sessions the backend
+/// no longer knows, e.g. ones pruned after a restart.
+#[tauri::command]
+pub async fn forget_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("forget_agent_session: sid={session_id}"),
+    );
+
+    let meta = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .forget_session(&session_id);
+    let snapshot_project = match meta {
+        Some(meta) => meta.snapshot_id.map(|_| meta.project_path),
+        None => project_path,
+    };
+    if let Some(project) = snapshot_project {
+        snapshot::discard_snapshot(&project, &session_id);
+    }
+    Ok(())
+}
+
+/// Switch a running session to another model without losing its context.
+/// The worker confirms with `model_changed` (which updates the session
+/// metadata) or replies with an `error` event if it can't switch.
+#[tauri::command]
+pub async fn set_agent_model(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    model: String,
+) -> Result<(), String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("set_agent_model: sid={session_id}, model={model}"),
+    );
+
+    let command = SidecarCommand::SetModel { session_id, model };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_command(&command)
+}
+
+/// Set how a session's tool calls are approved from now on, instead of
+/// answering each request: "ask" prompts for everything, "auto_edit" approves
+/// file edits, "plan" lets the agent plan without running tools. The worker
+/// confirms with `permission_mode_changed`, which is recorded in the metadata.
+#[tauri::command]
+pub async fn set_session_permission_mode(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    mode: String,
+) -> Result<(), String> {
+    if !PERMISSION_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "Unknown permission mode: {mode} (expected {})",
+            PERMISSION_MODES.join(", ")
+        ));
+    }
+    debug_log::log(
+        "RUST-CMD",
+        &format!("set_session_permission_mode: sid={session_id}, mode={mode}"),
+    );
+
+    let command = SidecarCommand::SetPermissionMode { session_id, mode };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_command(&command)
+}
+
+/// Softer `abort_agent_session`: SIGINT the worker so the SDK can cancel the
+/// in-flight request and report final cost, wait up to `grace_ms` for
+/// `session_completed`/`session_failed`, then remove the worker (killing it
+/// if it's still running). Returns whether the worker ended the session itself.
+#[tauri::command]
+pub async fn interrupt_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    grace_ms: Option<u64>,
+) -> Result<bool, String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("interrupt_agent_session: sid={session_id}"),
+    );
+    let lock_err = |e: std::sync::PoisonError<_>| format!("Failed to lock sidecar: {e}");
+
+    let since = {
+        let mut manager = sidecar.lock().map_err(lock_err)?;
+        let since = manager.last_event_seq(&session_id);
+        manager.interrupt_session(&session_id)?;
+        since
+    };
+
+    let deadline =
+        Instant::now() + Duration::from_millis(grace_ms.unwrap_or(DEFAULT_INTERRUPT_GRACE_MS));
+    let mut graceful = false;
+    while !graceful && Instant::now() < deadline {
+        tokio::time::sleep(INTERRUPT_POLL).await;
+        graceful = sidecar
+            .lock()
+            .map_err(lock_err)?
+            .session_ended_since(&session_id, since);
+    }
+
+    if !graceful {
+        debug_log::log(
+            "RUST-CMD",
+            &format!("interrupt_agent_session: sid={session_id} did not stop in time, killing"),
+        );
+    }
+    sidecar
+        .lock()
+        .map_err(lock_err)?
+        .remove_session(&session_id);
+    Ok(graceful)
+}
+
+/// End every session gracefully before quitting: send `end_session`, wait up
+/// to `timeout_ms` for each to complete or exit, then kill the stragglers
+#[tauri::command]
+pub async fn drain_all_sessions(
+    sidecar: State<'_, SidecarHandle>,
+    timeout_ms: Option<u64>,
+) -> Result<DrainReport, String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("drain_all_sessions: timeout_ms={timeout_ms:?}"),
+    );
+    let handle = sidecar.inner().clone();
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_DRAIN_TIMEOUT_MS));
+    tokio::task::spawn_blocking(move || drain_all(&handle, timeout))
+        .await
+        .map_err(|e| format!("Drain task failed: {e}"))
+}
@@ -0,0 +1,66 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::debug_log;
+use crate::sidecar::session_meta::SessionMetadata;
+use crate::sidecar::tool_history::ToolHistoryEntry;
+use crate::sidecar::types::AgentEventPayload;
+use crate::sidecar::{worker_command_line, worker_env_report, SidecarHandle, WorkerEnvReport};
+
+/// Everything we know about one session, bundled for a bug report
+#[derive(Debug, Serialize)]
+pub struct SessionDiagnostics {
+    pub metadata: SessionMetadata,
+    /// Buffered events, oldest first
+    pub events: Vec<AgentEventPayload>,
+    pub tool_history: Vec<ToolHistoryEntry>,
+    /// Recent worker stderr, oldest first
+    pub stderr_tail: Vec<String>,
+    /// The command line a worker is spawned with (empty if the script can't be found)
+    pub spawn_command: Vec<String>,
+    /// Worker environment, with proxy credentials redacted
+    pub env: WorkerEnvReport,
+    pub running: bool,
+    /// How the worker exited, once it has
+    pub exit_status: Option<String>,
+}
+
+/// Collect a session's metadata, events, tool calls, stderr, and exit info so
+/// a failed session can be attached to an issue. Works after the worker is gone.
+#[tauri::command]
+pub async fn export_session_diagnostics(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<SessionDiagnostics, String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("export_session_diagnostics: sid={session_id}"),
+    );
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+    let metadata = manager
+        .session_metadata(&session_id)
+        .ok_or_else(|| format!("Unknown session: {session_id}"))?;
+    let exit_status = manager.worker_exit(&session_id);
+    let running = exit_status.is_none() && manager.worker_pid(&session_id).is_some();
+
+    let env = worker_env_report();
+    let spawn_command = env
+        .worker_path
+        .as_deref()
+        .map(worker_command_line)
+        .unwrap_or_default();
+
+    Ok(SessionDiagnostics {
+        metadata,
+        events: manager.replay_events(&session_id, 0),
+        tool_history: manager.tool_history(&session_id),
+        stderr_tail: manager.stderr_tail(&session_id),
+        spawn_command,
+        env,
+        running,
+        exit_status,
+    })
+}
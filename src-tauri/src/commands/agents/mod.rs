@@ -0,0 +1,275 @@
+pub mod changes;
+pub mod control;
+pub mod diagnostics;
+pub mod session_store;
+pub mod snapshot;
+pub mod start;
+
+use std::collections::BTreeMap;
+
+use tauri::State;
+
+use crate::debug_log;
+use crate::sidecar::models::{cached_or_fetch, ModelCacheHandle};
+use crate::sidecar::session_meta::{ModelCost, SessionBudget, SessionMetadata, SessionUsage};
+use crate::sidecar::tool_history::ToolHistoryEntry;
+use crate::sidecar::types::{AgentEventPayload, ModelInfo};
+use crate::sidecar::{SidecarCommand, SidecarHandle};
+
+/// Send a follow-up message to an existing session
+#[tauri::command]
+pub async fn send_agent_message(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    message: String,
+) -> Result<(), String> {
+    let command = SidecarCommand::SendMessage {
+        session_id,
+        message,
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_command(&command)
+}
+
+/// Abort a running agent session
+#[tauri::command]
+pub async fn abort_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<(), String> {
+    let command = SidecarCommand::AbortSession {
+        session_id: session_id.clone(),
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    let _ = manager.send_command(&command);
+    manager.remove_session(&session_id);
+
+    Ok(())
+}
+
+/// Gracefully end a session (close follow-up queue, worker exits).
+/// Keep-alive sessions are parked instead, so a follow-up can reuse the worker.
+#[tauri::command]
+pub async fn end_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<(), String> {
+    let command = SidecarCommand::EndSession {
+        session_id: session_id.clone(),
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    if manager.park_session(&session_id) {
+        return Ok(());
+    }
+
+    let _ = manager.send_command(&command);
+    // Worker will exit on its own after queue closes
+    manager.remove_session(&session_id);
+
+    Ok(())
+}
+
+/// Cancel a tool call. One awaiting approval is denied on its own; a running
+/// tool can't be stopped individually, so the worker interrupts the whole
+/// turn and reports `turn_interrupted`. Anything else gets an `error` event.
+#[tauri::command]
+pub async fn cancel_agent_tool(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    tool_name: String,
+) -> Result<(), String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("cancel_agent_tool: sid={session_id}, tool={tool_name}"),
+    );
+
+    let command = SidecarCommand::CancelTool {
+        session_id,
+        tool_name,
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_command(&command)
+}
+
+/// "Stop what you're doing and do this instead": the worker interrupts the
+/// current turn, waits for it to settle, then sends `message` as a follow-up,
+/// reporting each step as a `reprompt_status` event
+#[tauri::command]
+pub async fn reprompt_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    message: String,
+) -> Result<(), String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("reprompt_agent_session: sid={session_id}"),
+    );
+
+    let command = SidecarCommand::Reprompt {
+        session_id,
+        message,
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_command(&command)
+}
+
+/// Respond to a tool approval request from a session worker
+#[tauri::command]
+pub async fn respond_tool_approval(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    request_id: String,
+    allowed: bool,
+    updated_permissions: Option<serde_json::Value>,
+) -> Result<(), String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("respond_tool_approval: sid={session_id}, req={request_id}, allowed={allowed}"),
+    );
+
+    let command = SidecarCommand::ToolApprovalResponse {
+        request_id,
+        allowed,
+        updated_permissions,
+    };
+
+    let mut manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    manager.send_to_session(&session_id, &command)
+}
+
+/// List currently active sessions tracked by the manager
+#[tauri::command]
+pub async fn list_agent_sessions(sidecar: State<'_, SidecarHandle>) -> Result<Vec<String>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.active_session_ids())
+}
+
+/// Model, cost, and SDK session ID recorded for a session (live or completed)
+#[tauri::command]
+pub async fn get_session_metadata(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Option<SessionMetadata>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.session_metadata(&session_id))
+}
+
+/// Live cost (and budget fraction) for a session, for a running cost meter
+#[tauri::command]
+pub async fn get_session_usage(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Option<SessionUsage>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.session_usage(&session_id))
+}
+
+/// Spent, configured and remaining budget for a session, for
+/// "$0.42 of $1.00 used". `max_budget_usd` is None when no budget was set.
+#[tauri::command]
+pub async fn get_session_budget(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Option<SessionBudget>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.session_budget(&session_id))
+}
+
+/// Completed-session spend broken down by the model that actually ran
+#[tauri::command]
+pub async fn get_cost_by_model(
+    sidecar: State<'_, SidecarHandle>,
+) -> Result<Vec<ModelCost>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.cost_by_model())
+}
+
+/// Ordered tool activity for a session, so a reloaded UI can rebuild its timeline
+#[tauri::command]
+pub async fn get_session_tool_history(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Vec<ToolHistoryEntry>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.tool_history(&session_id))
+}
+
+/// Tool calls by tool name ("14 Read, 3 Edit, 2 Bash") for a session header.
+/// Lighter than the full history, and not capped like it is.
+#[tauri::command]
+pub async fn get_session_tool_counts(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<BTreeMap<String, u64>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.tool_counts(&session_id))
+}
+
+/// Events emitted after `since_seq` (0 = everything still buffered), for
+/// catching up after the frontend reloads mid-session
+#[tauri::command]
+pub async fn replay_agent_events(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    since_seq: u64,
+) -> Result<Vec<AgentEventPayload>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    Ok(manager.replay_events(&session_id, since_seq))
+}
+
+/// Models the SDK/account can use, asked of a short-lived worker and cached.
+/// Pass `refresh` to bypass the cache.
+#[tauri::command]
+pub async fn list_available_models(
+    cache: State<'_, ModelCacheHandle>,
+    refresh: Option<bool>,
+) -> Result<Vec<ModelInfo>, String> {
+    cached_or_fetch(&cache, refresh.unwrap_or(false))
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::sidecar::SidecarHandle;
+
+/// One persisted message, as the frontend reads it from the `messages` table
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ConversationMessage {
+    pub role: String,
+    pub content: Option<String>,
+    pub thinking: Option<String>,
+    pub timestamp: String,
+}
+
+/// Enough context to recognise a past session at a glance
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSummary {
+    pub first_prompt: Option<String>,
+    pub last_assistant_message: Option<String>,
+    pub message_count: usize,
+    /// Only known for sessions that ran since the app last started
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionConversation {
+    pub session_id: String,
+    pub messages: Vec<ConversationMessage>,
+    pub summary: ConversationSummary,
+}
+
+/// A stored session's messages in order plus a summary, for previewing it
+/// before resume. Messages live in the frontend's SQL plugin database, so
+/// the caller passes the rows it loaded; cost comes from the session
+/// metadata while the backend still has it.
+#[tauri::command]
+pub async fn get_session_conversation(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    messages: Vec<ConversationMessage>,
+) -> Result<SessionConversation, String> {
+    let cost_usd = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .session_metadata(&session_id)
+        .and_then(|meta| meta.total_cost_usd);
+
+    Ok(conversation(session_id, messages, cost_usd))
+}
+
+fn conversation(
+    session_id: String,
+    mut messages: Vec<ConversationMessage>,
+    cost_usd: Option<f64>,
+) -> SessionConversation {
+    // ISO timestamps sort chronologically; the sort is stable for equal ones
+    messages.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    let text_of = |role: &str, m: &ConversationMessage| {
+        m.content
+            .as_ref()
+            .filter(|content| m.role == role && !content.is_empty())
+            .cloned()
+    };
+
+    let summary = ConversationSummary {
+        first_prompt: messages.iter().find_map(|m| text_of("user", m)),
+        last_assistant_message: messages.iter().rev().find_map(|m| text_of("assistant", m)),
+        message_count: messages.len(),
+        cost_usd,
+    };
+    SessionConversation {
+        session_id,
+        messages,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(second: u32, role: &str, content: Option<&str>) -> ConversationMessage {
+        ConversationMessage {
+            role: role.to_string(),
+            content: content.map(str::to_string),
+            thinking: None,
+            timestamp: format!("2026-01-01T00:00:0{second}Z"),
+        }
+    }
+
+    #[test]
+    fn orders_messages_and_summarizes_them() {
+        let messages = vec![
+            message(3, "user", Some("Also add a test")),
+            message(1, "user", Some("Fix the login bug")),
+            message(4, "assistant", Some("Done, tests pass")),
+            message(2, "assistant", Some("Looking at auth.ts")),
+            message(5, "assistant", None),
+        ];
+
+        let result = conversation("s1".to_string(), messages, Some(0.42));
+
+        let order: Vec<&str> = result
+            .messages
+            .iter()
+            .map(|m| &m.timestamp[17..19])
+            .collect();
+        assert_eq!(order, ["01", "02", "03", "04", "05"]);
+        assert_eq!(
+            result.summary,
+            ConversationSummary {
+                first_prompt: Some("Fix the login bug".to_string()),
+                last_assistant_message: Some("Done, tests pass".to_string()),
+                message_count: 5,
+                cost_usd: Some(0.42),
+            }
+        );
+    }
+
+    #[test]
+    fn empty_conversation_has_an_empty_summary() {
+        let result = conversation("s1".to_string(), Vec::new(), None);
+
+        assert_eq!(result.summary.first_prompt, None);
+        assert_eq!(result.summary.last_assistant_message, None);
+        assert_eq!(result.summary.message_count, 0);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["sessionId"], "s1");
+        assert!(json["summary"]["costUsd"].is_null());
+    }
+}
@@ -0,0 +1,196 @@
+use git2::{IndexAddOption, Signature};
+use tauri::State;
+
+use crate::commands::files::git_helpers::{head_commit, open_repo};
+use crate::debug_log;
+use crate::sidecar::manager::SidecarManager;
+use crate::sidecar::session_meta::SessionMetadata;
+use crate::sidecar::SidecarHandle;
+
+mod restore;
+
+pub use restore::restore_snapshot;
+
+/// Snapshots live under a hidden ref so `git gc` keeps them reachable
+const SNAPSHOT_REF_PREFIX: &str = "refs/central/snapshots";
+
+/// Record the working tree (tracked edits plus untracked, non-ignored files)
+/// as a commit on a hidden ref, without touching the index or HEAD.
+/// Returns None when the project isn't a git repo.
+pub fn create_snapshot(project_path: &str, session_id: &str) -> Result<Option<String>, String> {
+    let Ok(repo) = open_repo(project_path) else {
+        debug_log::log(
+            "SNAPSHOT",
+            &format!("WARN: {project_path} is not a git repo, skipping snapshot"),
+        );
+        return Ok(None);
+    };
+
+    // In-memory only: the index is never written back to disk
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("Failed to read index: {e}"))?;
+    index
+        .update_all(["*"], None)
+        .and_then(|_| index.add_all(["*"], IndexAddOption::DEFAULT, None))
+        .map_err(|e| format!("Failed to stage working tree: {e}"))?;
+    let tree_id = index
+        .write_tree()
+        .map_err(|e| format!("Failed to write snapshot tree: {e}"))?;
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("Failed to find snapshot tree: {e}"))?;
+
+    let sig = repo
+        .signature()
+        .or_else(|_| Signature::now("Central", "central@localhost"))
+        .map_err(|e| format!("Failed to create signature: {e}"))?;
+    let parent = head_commit(&repo)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let ref_name = format!("{SNAPSHOT_REF_PREFIX}/{session_id}");
+    let message = format!("Central snapshot before session {session_id}");
+
+    let oid = repo
+        .commit(Some(&ref_name), &sig, &sig, &message, &tree, &parents)
+        .map_err(|e| format!("Failed to create snapshot commit: {e}"))?;
+    Ok(Some(oid.to_string()))
+}
+
+/// Drop a session's snapshot ref, e.g. when its worker never started.
+/// A missing ref is not an error.
+pub fn delete_snapshot(project_path: &str, session_id: &str) -> Result<(), String> {
+    let repo = open_repo(project_path)?;
+    let ref_name = format!("{SNAPSHOT_REF_PREFIX}/{session_id}");
+    let deleted = match repo.find_reference(&ref_name) {
+        Ok(mut reference) => reference
+            .delete()
+            .map_err(|e| format!("Failed to delete {ref_name}: {e}")),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to find {ref_name}: {e}")),
+    };
+    deleted
+}
+
+/// A session that never started, or was deleted, has nothing to revert.
+/// Failures (e.g. the project is not a repo) are only logged.
+pub fn discard_snapshot(project_path: &str, session_id: &str) {
+    if let Err(e) = delete_snapshot(project_path, session_id) {
+        debug_log::log("SNAPSHOT", &format!("WARN: {e}"));
+    }
+}
+
+/// Undo what a session wrote to the working tree by restoring those files
+/// from the snapshot taken when it started. Refused while its worker is
+/// still running; the snapshot is dropped once it has been applied.
+#[tauri::command]
+pub async fn revert_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+) -> Result<Vec<String>, String> {
+    let meta = {
+        let mut manager = sidecar
+            .lock()
+            .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+        revertible(&mut manager, &session_id)?
+    };
+    let snapshot_id = meta
+        .snapshot_id
+        .ok_or_else(|| format!("Session {session_id} has no snapshot to revert to"))?;
+
+    debug_log::log(
+        "RUST-CMD",
+        &format!("revert_session: sid={session_id}, snapshot={snapshot_id}"),
+    );
+    let restored = restore_snapshot(&meta.project_path, &snapshot_id, &meta.files_written)?;
+
+    discard_snapshot(&meta.project_path, &session_id);
+    sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .clear_snapshot(&session_id);
+    Ok(restored)
+}
+
+/// The session's metadata, unless its worker could still be writing files
+fn revertible(manager: &mut SidecarManager, session_id: &str) -> Result<SessionMetadata, String> {
+    if manager.worker_pid(session_id).is_some() && manager.worker_exit(session_id).is_none() {
+        return Err(format!(
+            "Session {session_id} is still running; stop it before reverting"
+        ));
+    }
+    manager
+        .session_metadata(session_id)
+        .ok_or_else(|| format!("Unknown session: {session_id}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use std::path::Path;
+
+    pub(super) fn init_repo_with_file(name: &str, content: &str) -> std::path::PathBuf {
+        let temp = std::env::temp_dir().join(format!("central_snapshot_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+
+        std::fs::write(temp.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        temp
+    }
+
+    #[test]
+    fn delete_snapshot_removes_the_ref() {
+        let temp = init_repo_with_file("a.txt", "committed\n");
+        let path = temp.to_string_lossy().to_string();
+        create_snapshot(&path, "s1").unwrap().unwrap();
+
+        delete_snapshot(&path, "s1").unwrap();
+
+        let repo = Repository::open(&temp).unwrap();
+        assert!(repo
+            .find_reference(&format!("{SNAPSHOT_REF_PREFIX}/s1"))
+            .is_err());
+        assert!(delete_snapshot(&path, "s1").is_ok());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn revert_is_refused_while_the_worker_runs() {
+        use crate::sidecar::manager::stubs::{spawn_sleeper, start, stub_manager};
+
+        let mut manager = stub_manager(2, spawn_sleeper);
+        manager.start_session(&start("s1"), None).unwrap();
+        manager.record_snapshot("s1", "abc".to_string());
+
+        let err = revertible(&mut manager, "s1").unwrap_err();
+        assert!(err.contains("still running"), "{err}");
+        assert!(revertible(&mut manager, "missing")
+            .unwrap_err()
+            .contains("Unknown session"));
+
+        manager.remove_session("s1");
+    }
+
+    #[test]
+    fn snapshot_is_skipped_outside_a_repo() {
+        let temp = std::env::temp_dir().join(format!("central_snapshot_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+
+        assert_eq!(
+            create_snapshot(&temp.to_string_lossy(), "s1").unwrap(),
+            None
+        );
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
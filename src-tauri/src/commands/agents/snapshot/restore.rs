@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use git2::{Delta, DiffOptions, Oid};
+
+use crate::commands::files::fs_helpers::{canonical_project, resolve_inside_project};
+use crate::commands::files::git_helpers::open_repo;
+use crate::debug_log;
+
+/// Put the files a session wrote back to the snapshot: rewrite ones that
+/// changed, recreate deleted ones, and remove ones created since. Everything
+/// else (other sessions' edits, files outside the project, the index, HEAD)
+/// is left alone. Returns the restored paths, relative to the repo root.
+pub fn restore_snapshot(
+    project_path: &str,
+    snapshot_id: &str,
+    files: &BTreeSet<String>,
+) -> Result<Vec<String>, String> {
+    let repo = open_repo(project_path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or("Repository has no working directory")?
+        .canonicalize()
+        .map_err(|e| format!("Invalid working directory: {e}"))?;
+    let pathspecs = repo_pathspecs(&workdir, project_path, files)?;
+    // An empty pathspec would match the whole tree
+    if pathspecs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let oid = Oid::from_str(snapshot_id).map_err(|e| format!("Invalid snapshot id: {e}"))?;
+    let tree = repo
+        .find_commit(oid)
+        .and_then(|c| c.tree())
+        .map_err(|e| format!("Snapshot {snapshot_id} not found: {e}"))?;
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .disable_pathspec_match(true);
+    for pathspec in &pathspecs {
+        opts.pathspec(pathspec);
+    }
+    let diff = repo
+        .diff_tree_to_workdir(Some(&tree), Some(&mut opts))
+        .map_err(|e| format!("Failed to diff against snapshot: {e}"))?;
+
+    let mut restored = Vec::new();
+    for delta in diff.deltas() {
+        let Some(rel) = delta.old_file().path().or(delta.new_file().path()) else {
+            continue;
+        };
+        let target = workdir.join(rel);
+        match delta.status() {
+            Delta::Added | Delta::Untracked => remove_if_exists(&target)?,
+            _ => {
+                let blob = repo
+                    .find_blob(delta.old_file().id())
+                    .map_err(|e| format!("Failed to read {}: {e}", rel.display()))?;
+                write_restored(&target, blob.content())?;
+            }
+        }
+        restored.push(rel.to_string_lossy().to_string());
+    }
+    Ok(restored)
+}
+
+/// Repo-relative paths for the written files that are inside the project;
+/// the rest are skipped with a warning
+fn repo_pathspecs(
+    workdir: &Path,
+    project_path: &str,
+    files: &BTreeSet<String>,
+) -> Result<Vec<String>, String> {
+    let project = canonical_project(project_path)?;
+    let mut pathspecs = Vec::new();
+    for file in files {
+        let relative = resolve_inside_project(&project, file).and_then(|full| {
+            full.strip_prefix(workdir)
+                .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+                .map_err(|_| format!("Path is outside the repository: {file}"))
+        });
+        match relative {
+            Ok(rel) => pathspecs.push(rel),
+            Err(e) => debug_log::log("SNAPSHOT", &format!("WARN: not restoring {e}")),
+        }
+    }
+    Ok(pathspecs)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), String> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("Failed to remove {}: {e}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn write_restored(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::create_snapshot;
+    use super::super::tests::init_repo_with_file;
+    use super::*;
+    use git2::Repository;
+
+    fn written(paths: &[&str]) -> BTreeSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn restore_undoes_edits_deletions_and_new_files() {
+        let temp = init_repo_with_file("a.txt", "committed\n");
+        let path = temp.to_string_lossy().to_string();
+        std::fs::write(temp.join("a.txt"), "local edit\n").unwrap();
+        std::fs::write(temp.join("notes.txt"), "untracked\n").unwrap();
+
+        let snapshot = create_snapshot(&path, "s1").unwrap().unwrap();
+
+        std::fs::write(temp.join("a.txt"), "agent edit\n").unwrap();
+        std::fs::remove_file(temp.join("notes.txt")).unwrap();
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        std::fs::write(temp.join("src/new.rs"), "fn main() {}\n").unwrap();
+
+        let files = written(&["a.txt", "notes.txt", "src/new.rs"]);
+        restore_snapshot(&path, &snapshot, &files).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(temp.join("a.txt")).unwrap(),
+            "local edit\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.join("notes.txt")).unwrap(),
+            "untracked\n"
+        );
+        assert!(!temp.join("src/new.rs").exists());
+        // The snapshot must not have staged anything
+        let repo = Repository::open(&temp).unwrap();
+        assert!(repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("notes.txt"), 0)
+            .is_none());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn restore_only_touches_the_project_directory() {
+        let temp = init_repo_with_file("README.md", "root\n");
+        std::fs::create_dir_all(temp.join("app")).unwrap();
+        let project = temp.join("app").to_string_lossy().to_string();
+
+        let snapshot = create_snapshot(&project, "s1").unwrap().unwrap();
+
+        std::fs::write(temp.join("app/new.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(temp.join("README.md"), "edited elsewhere\n").unwrap();
+
+        let files = written(&["new.rs", "../README.md"]);
+        let restored = restore_snapshot(&project, &snapshot, &files).unwrap();
+
+        assert_eq!(restored, ["app/new.rs"]);
+        assert!(!temp.join("app/new.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(temp.join("README.md")).unwrap(),
+            "edited elsewhere\n"
+        );
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn restore_leaves_files_the_session_did_not_write() {
+        let temp = init_repo_with_file("a.txt", "committed\n");
+        let path = temp.to_string_lossy().to_string();
+        std::fs::write(temp.join("b.txt"), "before\n").unwrap();
+
+        let snapshot = create_snapshot(&path, "s1").unwrap().unwrap();
+
+        std::fs::write(temp.join("a.txt"), "agent edit\n").unwrap();
+        // Another session (or the user) edits and creates files meanwhile
+        std::fs::write(temp.join("b.txt"), "other session\n").unwrap();
+        std::fs::write(temp.join("c.txt"), "other session\n").unwrap();
+
+        let absolute = temp.join("a.txt").to_string_lossy().to_string();
+        let restored = restore_snapshot(&path, &snapshot, &written(&[&absolute])).unwrap();
+
+        assert_eq!(restored, ["a.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(temp.join("a.txt")).unwrap(),
+            "committed\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.join("b.txt")).unwrap(),
+            "other session\n"
+        );
+        assert!(temp.join("c.txt").exists());
+        assert!(restore_snapshot(&path, &snapshot, &BTreeSet::new())
+            .unwrap()
+            .is_empty());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
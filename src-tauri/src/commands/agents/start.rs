@@ -0,0 +1,133 @@
+use serde::Deserialize;
+use tauri::State;
+
+use super::snapshot;
+use crate::debug_log;
+use crate::project_path::{path_string, resolve_project_path};
+use crate::sidecar::{SidecarCommand, SidecarError, SidecarHandle};
+
+/// Optional behaviour for `start_agent_session`; everything is off by default
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StartSessionOptions {
+    /// Send the session's events only to this window instead of broadcasting
+    pub window_label: Option<String>,
+    /// Record the working tree first so `revert_session` can undo the run
+    /// (skipped with a warning outside a git repo)
+    pub snapshot: bool,
+    /// Park the worker on end for fast follow-ups until it has been idle
+    /// for `keep_alive::IDLE_TIMEOUT`
+    pub keep_alive: bool,
+    /// Once reported spend passes this the worker is killed and the session fails
+    pub max_budget_usd: Option<f64>,
+}
+
+/// Start a new agent session for a project.
+/// Failures carry a `SidecarError` code the UI can offer setup help for.
+#[tauri::command]
+pub async fn start_agent_session(
+    sidecar: State<'_, SidecarHandle>,
+    session_id: String,
+    project_path: String,
+    prompt: String,
+    model: Option<String>,
+    resume_session_id: Option<String>,
+    options: Option<StartSessionOptions>,
+) -> Result<String, SidecarError> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!(
+            "start_agent_session: sid={session_id}, path={project_path}, resume={}, prompt={}",
+            resume_session_id.as_deref().unwrap_or("none"),
+            &prompt[..prompt.len().min(50)]
+        ),
+    );
+    let options = options.unwrap_or_default();
+
+    let project_path = path_string(&resolve_project_path(&project_path).map_err(String::from)?);
+
+    let snapshot_id = if options.snapshot {
+        snapshot::create_snapshot(&project_path, &session_id)?
+    } else {
+        None
+    };
+
+    let command = SidecarCommand::StartSession {
+        session_id: session_id.clone(),
+        project_path: project_path.clone(),
+        prompt,
+        model,
+        max_budget_usd: options.max_budget_usd,
+        resume_session_id,
+    };
+    spawn_worker(
+        &sidecar,
+        &session_id,
+        &project_path,
+        &command,
+        options,
+        snapshot_id,
+    )?;
+
+    debug_log::log(
+        "RUST-CMD",
+        &format!("start_agent_session: worker spawned for sid={session_id}"),
+    );
+    Ok(session_id)
+}
+
+/// Spawn the session's worker, then attach its snapshot and keep-alive.
+/// A snapshot taken for a session that never started is dropped again.
+fn spawn_worker(
+    sidecar: &SidecarHandle,
+    session_id: &str,
+    project_path: &str,
+    command: &SidecarCommand,
+    options: StartSessionOptions,
+    snapshot_id: Option<String>,
+) -> Result<(), SidecarError> {
+    let started = sidecar
+        .lock()
+        .map_err(|e| {
+            let msg = format!("Failed to lock sidecar: {e}");
+            debug_log::log("RUST-CMD", &msg);
+            SidecarError::Other(msg)
+        })
+        .and_then(|mut manager| {
+            manager.start_session(command, options.window_label)?;
+            Ok(manager)
+        });
+    let mut manager = match started {
+        Ok(manager) => manager,
+        Err(e) => {
+            if snapshot_id.is_some() {
+                snapshot::discard_snapshot(project_path, session_id);
+            }
+            return Err(e);
+        }
+    };
+
+    if let Some(id) = snapshot_id {
+        manager.record_snapshot(session_id, id);
+    }
+    if options.keep_alive {
+        manager.set_keep_alive(session_id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_default_to_off_and_read_camel_case() {
+        let options: StartSessionOptions =
+            serde_json::from_str(r#"{"keepAlive":true,"maxBudgetUsd":1.5}"#).unwrap();
+
+        assert!(options.keep_alive);
+        assert!(!options.snapshot);
+        assert_eq!(options.window_label, None);
+        assert_eq!(options.max_budget_usd, Some(1.5));
+    }
+}
@@ -0,0 +1,199 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use tauri::State;
+
+use super::fs_helpers::{
+    atomic_write, canonical_project, check_read_size, resolve_existing_in_project,
+    resolve_for_write,
+};
+use super::types::FileBytes;
+use crate::file_locks::FileLockHandle;
+
+/// Leading bytes that identify common binary formats, checked before the extension
+const MAGIC_MIME_TYPES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x00\x00\x01\x00", "image/x-icon"),
+    (b"wOFF", "font/woff"),
+    (b"wOF2", "font/woff2"),
+];
+
+const EXTENSION_MIME_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("bmp", "image/bmp"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("wasm", "application/wasm"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("mp4", "video/mp4"),
+    ("json", "application/json"),
+    ("txt", "text/plain"),
+];
+
+/// Read a file as base64 so the UI can preview binaries, e.g. render an image.
+/// Subject to the same size guard as text reads.
+#[tauri::command]
+pub fn get_file_bytes(project_path: String, file_path: String) -> Result<FileBytes, String> {
+    let bytes = read_project_bytes(&project_path, &file_path)?;
+    Ok(FileBytes {
+        mime_type: detect_mime_type(&file_path, &bytes).to_string(),
+        base64: BASE64.encode(&bytes),
+        size: bytes.len() as u64,
+    })
+}
+
+/// Raw contents of a project file, boundary-checked and size-limited
+pub fn read_project_bytes(project_path: &str, file_path: &str) -> Result<Vec<u8>, String> {
+    let project = canonical_project(project_path)?;
+    let path = resolve_existing_in_project(&project, file_path)?;
+    check_read_size(&path)?;
+
+    std::fs::read(&path).map_err(|e| format!("Failed to read file: {e}"))
+}
+
+/// Magic bytes win over the extension; RIFF containers need a second look
+/// to tell WebP from WAV.
+fn detect_mime_type(file_path: &str, bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        return "image/webp";
+    }
+    if let Some((_, mime)) = MAGIC_MIME_TYPES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+    {
+        return mime;
+    }
+
+    let extension = std::path::Path::new(file_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    EXTENSION_MIME_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, mime)| *mime)
+        .unwrap_or("application/octet-stream")
+}
+
+/// Write raw bytes (base64-encoded over IPC), e.g. an image an agent generated.
+/// Shares `write_file`'s lock check, boundary check, and atomic write.
+#[tauri::command]
+pub fn write_file_bytes(
+    project_path: String,
+    file_path: String,
+    base64_content: String,
+    owner: Option<String>,
+    locks: State<'_, FileLockHandle>,
+) -> Result<(), String> {
+    locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?
+        .check_write(&project_path, &file_path, owner.as_deref())?;
+
+    write_project_bytes(&project_path, &file_path, &base64_content)
+}
+
+fn write_project_bytes(
+    project_path: &str,
+    file_path: &str,
+    base64_content: &str,
+) -> Result<(), String> {
+    let bytes = BASE64
+        .decode(base64_content.trim())
+        .map_err(|e| format!("Invalid base64 content: {e}"))?;
+
+    let target = resolve_for_write(project_path, file_path)?;
+    atomic_write(&target, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project() -> std::path::PathBuf {
+        let temp =
+            std::env::temp_dir().join(format!("central_binary_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        temp
+    }
+
+    #[test]
+    fn write_project_bytes_writes_decoded_bytes() {
+        let temp = temp_project();
+        let encoded = BASE64.encode([0u8, 159, 146, 150]);
+
+        write_project_bytes(&temp.to_string_lossy(), "img.bin", &encoded).unwrap();
+
+        assert_eq!(
+            std::fs::read(temp.join("img.bin")).unwrap(),
+            vec![0, 159, 146, 150]
+        );
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn write_project_bytes_rejects_invalid_base64() {
+        let temp = temp_project();
+
+        let result = write_project_bytes(&temp.to_string_lossy(), "img.bin", "not base64!");
+        assert!(result.unwrap_err().contains("Invalid base64"));
+        assert!(!temp.join("img.bin").exists());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn detect_mime_type_prefers_magic_bytes() {
+        assert_eq!(
+            detect_mime_type("logo.bin", b"\x89PNG\r\n\x1a\nrest"),
+            "image/png"
+        );
+        assert_eq!(
+            detect_mime_type("a.png", b"RIFF\0\0\0\0WEBPVP8"),
+            "image/webp"
+        );
+        assert_eq!(detect_mime_type("icon.svg", b"<svg/>"), "image/svg+xml");
+        assert_eq!(
+            detect_mime_type("data.xyz", b"\x01\x02"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn get_file_bytes_round_trips_written_bytes() {
+        let temp = temp_project();
+        let project = temp.to_string_lossy().to_string();
+        let encoded = BASE64.encode(b"GIF89a\x00\x01");
+        write_project_bytes(&project, "a.gif", &encoded).unwrap();
+
+        let result = get_file_bytes(project, "a.gif".to_string()).unwrap();
+        assert_eq!(result.base64, encoded);
+        assert_eq!(result.mime_type, "image/gif");
+        assert_eq!(result.size, 8);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn write_project_bytes_rejects_path_traversal() {
+        let temp = temp_project();
+
+        let result = write_project_bytes(&temp.to_string_lossy(), "../../evil.bin", "AAAA");
+        assert!(result.is_err());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
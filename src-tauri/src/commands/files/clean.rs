@@ -0,0 +1,173 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use super::fs_helpers::{canonical_project, dir_size};
+use crate::debug_log;
+
+/// Directory names that are always safe to regenerate. Nothing else can be cleaned.
+const ARTIFACT_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    ".next",
+    ".nuxt",
+    ".turbo",
+    "__pycache__",
+    ".pytest_cache",
+    ".mypy_cache",
+    ".gradle",
+    "coverage",
+];
+
+#[derive(Debug, Serialize)]
+pub struct CleanResult {
+    pub removed: Vec<String>,
+    pub bytes_freed: u64,
+}
+
+/// Delete build/dependency directories to reclaim space. `targets` are paths
+/// relative to the project whose final component must be an allowlisted
+/// artifact name. Every target is validated before anything is deleted;
+/// targets that don't exist are skipped.
+#[tauri::command]
+pub async fn clean_project_artifacts(
+    project_path: String,
+    targets: Vec<String>,
+) -> Result<CleanResult, String> {
+    clean_artifacts(&project_path, &targets)
+}
+
+fn clean_artifacts(project_path: &str, targets: &[String]) -> Result<CleanResult, String> {
+    let project = canonical_project(project_path)?;
+    let resolved: Vec<Option<PathBuf>> = targets
+        .iter()
+        .map(|t| resolve_artifact(&project, t))
+        .collect::<Result<_, _>>()?;
+
+    let mut result = CleanResult {
+        removed: vec![],
+        bytes_freed: 0,
+    };
+    for (target, path) in targets.iter().zip(resolved) {
+        let Some(path) = path else {
+            continue;
+        };
+        let size = dir_size(&path);
+        std::fs::remove_dir_all(&path).map_err(|e| format!("Failed to remove {target}: {e}"))?;
+        debug_log::log(
+            "CLEAN",
+            &format!("Removed {} ({size} bytes)", path.display()),
+        );
+        result.removed.push(target.clone());
+        result.bytes_freed += size;
+    }
+
+    Ok(result)
+}
+
+/// Check one target against the allowlist and the project boundary.
+/// Ok(None) means it doesn't exist, so there is nothing to remove.
+fn resolve_artifact(project: &Path, target: &str) -> Result<Option<PathBuf>, String> {
+    let relative_only = Path::new(target)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !relative_only {
+        return Err("Cannot clean outside project directory".to_string());
+    }
+
+    let name = Path::new(target)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if !ARTIFACT_DIRS.contains(&name.as_str()) {
+        return Err(format!("Not an allowed artifact directory: {target}"));
+    }
+
+    let full = project.join(target);
+    let Ok(meta) = std::fs::symlink_metadata(&full) else {
+        return Ok(None);
+    };
+    if !meta.is_dir() {
+        return Err(format!("Not a directory: {target}"));
+    }
+
+    let canonical = full
+        .canonicalize()
+        .map_err(|e| format!("Invalid path: {e}"))?;
+    if !canonical.starts_with(project) || canonical == project {
+        return Err("Cannot clean outside project directory".to_string());
+    }
+
+    Ok(Some(canonical))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project() -> PathBuf {
+        let temp =
+            std::env::temp_dir().join(format!("central_clean_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("node_modules").join("pkg")).unwrap();
+        std::fs::write(
+            temp.join("node_modules").join("pkg").join("index.js"),
+            "12345",
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        temp
+    }
+
+    #[test]
+    fn resolve_artifact_enforces_allowlist_and_boundary() {
+        let temp = temp_project();
+        let project = temp.canonicalize().unwrap();
+
+        assert!(resolve_artifact(&project, "src")
+            .unwrap_err()
+            .contains("Not an allowed"));
+        assert!(resolve_artifact(&project, "../node_modules").is_err());
+        assert!(resolve_artifact(&project, "/tmp/node_modules").is_err());
+        assert!(resolve_artifact(&project, "target").unwrap().is_none());
+        assert!(resolve_artifact(&project, "node_modules")
+            .unwrap()
+            .is_some());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn clean_removes_targets_and_reports_bytes() {
+        let temp = temp_project();
+
+        let result = clean_artifacts(
+            &temp.to_string_lossy(),
+            &["node_modules".to_string(), "dist".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(result.removed, vec!["node_modules"]);
+        assert_eq!(result.bytes_freed, 5);
+        assert!(!temp.join("node_modules").exists());
+        assert!(temp.join("src").exists());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn clean_rejects_everything_if_one_target_is_disallowed() {
+        let temp = temp_project();
+
+        let result = clean_artifacts(
+            &temp.to_string_lossy(),
+            &["node_modules".to_string(), "src".to_string()],
+        );
+
+        assert!(result.is_err());
+        assert!(temp.join("node_modules").exists());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use super::fs_helpers::{canonical_project, read_text_file, resolve_existing_in_project};
+use super::pool::{map_bounded, MAX_WORKERS};
+use super::types::FileContentResult;
+
+/// Read several files in one round trip. Failures (missing, binary, oversized,
+/// outside the project) are reported per path instead of failing the batch.
+/// Files are read on up to `concurrency` threads (default and cap
+/// `MAX_WORKERS`), off the async runtime.
+#[tauri::command]
+pub async fn get_files_content(
+    project_path: String,
+    file_paths: Vec<String>,
+    concurrency: Option<usize>,
+) -> Result<HashMap<String, FileContentResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || read_files(&project_path, file_paths, concurrency))
+        .await
+        .map_err(|e| format!("File read task failed: {e}"))?
+}
+
+fn read_files(
+    project_path: &str,
+    file_paths: Vec<String>,
+    concurrency: Option<usize>,
+) -> Result<HashMap<String, FileContentResult>, String> {
+    let project = canonical_project(project_path)?;
+    let workers = concurrency.unwrap_or(MAX_WORKERS).clamp(1, MAX_WORKERS);
+
+    let results = map_bounded(file_paths.clone(), workers, |file_path| {
+        let result =
+            resolve_existing_in_project(&project, file_path).and_then(|full| read_text_file(&full));
+        FileContentResult::from(result)
+    });
+
+    Ok(file_paths.into_iter().zip(results).collect())
+}
+
+impl From<Result<String, String>> for FileContentResult {
+    fn from(result: Result<String, String>) -> Self {
+        match result {
+            Ok(content) => Self {
+                content: Some(content),
+                error: None,
+            },
+            Err(error) => Self {
+                content: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_files_content_reports_per_path_errors() {
+        let temp =
+            std::env::temp_dir().join(format!("central_batch_read_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "alpha").unwrap();
+        std::fs::write(temp.join("bin.dat"), [0u8, 1, 2]).unwrap();
+
+        let results = read_files(
+            &temp.to_string_lossy(),
+            vec![
+                "a.txt".to_string(),
+                "bin.dat".to_string(),
+                "missing.txt".to_string(),
+            ],
+            Some(2),
+        )
+        .unwrap();
+
+        assert_eq!(results["a.txt"].content.as_deref(), Some("alpha"));
+        assert_eq!(results["bin.dat"].error.as_deref(), Some("Binary file"));
+        assert!(results["missing.txt"]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("File not found"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn out_of_range_concurrency_still_reads_everything() {
+        let temp =
+            std::env::temp_dir().join(format!("central_batch_read_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "alpha").unwrap();
+        let path = temp.to_string_lossy().to_string();
+
+        for concurrency in [0, usize::MAX] {
+            let results = read_files(&path, vec!["a.txt".to_string()], Some(concurrency)).unwrap();
+            assert_eq!(results["a.txt"].content.as_deref(), Some("alpha"));
+        }
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -1,20 +1,55 @@
 use git2::{DiffOptions, Repository};
 use std::path::Path;
 
+use super::git_helpers::{open_repo, project_prefix};
 use super::types::{DiffHunk, DiffLine, FileDiff};
 
+mod algorithm;
+
+pub(super) use algorithm::apply_diff_algorithm;
+
+/// HEAD-to-working-tree diff. `file_path` and the returned paths are
+/// relative to the repo root, like `get_git_status`'s changed files.
+/// `diff_algorithm` is "myers" (default), "patience", or "minimal".
 #[tauri::command]
 pub fn get_diff(
     project_path: String,
     file_path: Option<String>,
+    diff_algorithm: Option<String>,
+) -> Result<Vec<FileDiff>, String> {
+    let repo = open_repo(&project_path)?;
+    let pathspecs: Vec<String> = file_path.into_iter().collect();
+    workdir_diff(&repo, &pathspecs, diff_algorithm.as_deref())
+}
+
+/// HEAD-to-working-tree diff limited to the given project-relative paths
+pub fn diff_paths(project_path: &str, paths: &[String]) -> Result<Vec<FileDiff>, String> {
+    if paths.is_empty() {
+        return Ok(vec![]);
+    }
+    let repo = open_repo(project_path)?;
+    let prefix = project_prefix(&repo, Path::new(project_path));
+    let pathspecs: Vec<String> = paths
+        .iter()
+        .map(|p| prefix.join(p).to_string_lossy().to_string())
+        .collect();
+    workdir_diff(&repo, &pathspecs, None)
+}
+
+fn workdir_diff(
+    repo: &Repository,
+    pathspecs: &[String],
+    algorithm: Option<&str>,
 ) -> Result<Vec<FileDiff>, String> {
-    let root = Path::new(&project_path);
-    let repo = Repository::open(root)
-        .map_err(|e| format!("Not a git repository: {e}"))?;
 
+    // Brand-new files the agent created should render as all-added hunks
     let mut opts = DiffOptions::new();
-    if let Some(ref fp) = file_path {
-        opts.pathspec(fp);
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    apply_diff_algorithm(&mut opts, algorithm)?;
+    for spec in pathspecs {
+        opts.pathspec(spec);
     }
 
     let head_tree = repo
@@ -32,7 +67,7 @@ pub fn get_diff(
     collect_diff_output(&diff)
 }
 
-fn collect_diff_output(
+pub(super) fn collect_diff_output(
     diff: &git2::Diff,
 ) -> Result<Vec<FileDiff>, String> {
     let mut result: Vec<FileDiff> = Vec::new();
@@ -168,7 +203,7 @@ mod tests {
         ));
         std::fs::create_dir_all(&temp).unwrap();
 
-        let result = get_diff(temp.to_string_lossy().to_string(), None);
+        let result = get_diff(temp.to_string_lossy().to_string(), None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Not a git repository"));
 
@@ -191,7 +226,7 @@ mod tests {
         repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
             .unwrap();
 
-        let result = get_diff(temp.to_string_lossy().to_string(), None);
+        let result = get_diff(temp.to_string_lossy().to_string(), None, None);
         assert!(result.is_ok());
         assert!(result.unwrap().is_empty());
 
@@ -222,7 +257,7 @@ mod tests {
             .unwrap();
         index.write().unwrap();
 
-        let result = get_diff(temp.to_string_lossy().to_string(), None);
+        let result = get_diff(temp.to_string_lossy().to_string(), None, None);
         assert!(result.is_ok());
         let diffs = result.unwrap();
         assert!(!diffs.is_empty());
@@ -230,4 +265,35 @@ mod tests {
 
         std::fs::remove_dir_all(&temp).unwrap();
     }
+
+    #[test]
+    fn get_diff_shows_untracked_file_content_as_additions() {
+        let temp = std::env::temp_dir().join(format!(
+            "central_diff_untracked_{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(temp.join("nested")).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        let sig = git2::Signature::now("test", "test@test.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        std::fs::write(temp.join("nested").join("new.txt"), "first\nsecond\n").unwrap();
+
+        let diffs = get_diff(temp.to_string_lossy().to_string(), None, None).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "nested/new.txt");
+        let added: Vec<&str> = diffs[0].hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.origin == "add")
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(added, vec!["first\n", "second\n"]);
+        assert!(diffs[0].hunks[0].lines.iter().all(|l| l.origin != "del"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
 }
@@ -0,0 +1,38 @@
+use git2::DiffOptions;
+
+/// Map a diff algorithm name onto the matching `DiffOptions` flags
+pub(crate) fn apply_diff_algorithm(
+    opts: &mut DiffOptions,
+    algorithm: Option<&str>,
+) -> Result<(), String> {
+    match algorithm.unwrap_or("myers") {
+        "myers" => {}
+        "patience" => {
+            opts.patience(true);
+        }
+        "minimal" => {
+            opts.minimal(true);
+        }
+        other => {
+            return Err(format!(
+                "Unknown diff algorithm: {other} (expected myers, patience, or minimal)"
+            ))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_algorithm() {
+        let mut opts = DiffOptions::new();
+        assert!(apply_diff_algorithm(&mut opts, None).is_ok());
+        assert!(apply_diff_algorithm(&mut opts, Some("patience")).is_ok());
+        assert!(apply_diff_algorithm(&mut opts, Some("minimal")).is_ok());
+        let err = apply_diff_algorithm(&mut opts, Some("histogram")).unwrap_err();
+        assert!(err.contains("Unknown diff algorithm: histogram"));
+    }
+}
@@ -0,0 +1,274 @@
+use git2::{Delta, Diff, DiffFindOptions, DiffOptions, Patch, Repository, Tree};
+
+use super::git_helpers::open_repo;
+use super::types::{DiffSummary, FileDiffStat};
+
+/// Totals plus per-file line counts — no hunk contents, so it's cheap to ship
+/// over IPC. `mode` is "workdir" (HEAD → working tree, the default),
+/// "staged" (HEAD → index) or "unstaged" (index → working tree).
+#[tauri::command]
+pub fn get_diff_summary(project_path: String, mode: Option<String>) -> Result<DiffSummary, String> {
+    let repo = open_repo(&project_path)?;
+
+    let diff = diff_for_mode(&repo, mode.as_deref().unwrap_or("workdir"))?;
+    summarize_diff(&diff)
+}
+
+/// What a PR from the current branch would contain: the working tree
+/// (including uncommitted changes) diffed against the merge-base with `branch`.
+#[tauri::command]
+pub fn git_diff_against_branch(
+    project_path: String,
+    branch: String,
+) -> Result<DiffSummary, String> {
+    let repo = open_repo(&project_path)?;
+
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("Cannot resolve HEAD: {e}"))?;
+    let other = repo
+        .revparse_single(&branch)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|e| format!("Unknown branch {branch}: {e}"))?;
+
+    let base_oid = repo
+        .merge_base(head.id(), other.id())
+        .map_err(|e| format!("No merge base with {branch}: {e}"))?;
+    let base_tree = repo
+        .find_commit(base_oid)
+        .and_then(|c| c.tree())
+        .map_err(|e| format!("Failed to read merge base tree: {e}"))?;
+
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut DiffOptions::new()))
+        .map_err(|e| format!("Failed to get diff: {e}"))?;
+
+    summarize_diff(&diff)
+}
+
+/// The "Files changed" view of a PR from `head` into `base`: per-file stats
+/// between the two branch tips, ignoring the working tree. Renames are detected.
+#[tauri::command]
+pub fn git_compare_branches(
+    project_path: String,
+    base: String,
+    head: String,
+) -> Result<DiffSummary, String> {
+    let repo = open_repo(&project_path)?;
+    let base_tree = branch_tree(&repo, &base)?;
+    let head_tree = branch_tree(&repo, &head)?;
+
+    let mut diff = repo
+        .diff_tree_to_tree(
+            Some(&base_tree),
+            Some(&head_tree),
+            Some(&mut DiffOptions::new()),
+        )
+        .map_err(|e| format!("Failed to get diff: {e}"))?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("Failed to detect renames: {e}"))?;
+
+    summarize_diff(&diff)
+}
+
+fn branch_tree<'r>(repo: &'r Repository, name: &str) -> Result<Tree<'r>, String> {
+    repo.revparse_single(name)
+        .and_then(|obj| obj.peel_to_tree())
+        .map_err(|e| format!("Unknown branch {name}: {e}"))
+}
+
+pub(super) fn diff_for_mode<'r>(repo: &'r Repository, mode: &str) -> Result<Diff<'r>, String> {
+    let mut opts = DiffOptions::new();
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+    let diff = match mode {
+        "workdir" => repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts)),
+        "staged" => repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts)),
+        "unstaged" => repo.diff_index_to_workdir(None, Some(&mut opts)),
+        other => return Err(format!("Unknown diff mode: {other}")),
+    };
+
+    diff.map_err(|e| format!("Failed to get diff: {e}"))
+}
+
+pub(super) fn summarize_diff(diff: &Diff) -> Result<DiffSummary, String> {
+    let stats = diff
+        .stats()
+        .map_err(|e| format!("Failed to compute diff stats: {e}"))?;
+
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Binary files have no patch, so they report zero line changes
+        let (additions, deletions) = match Patch::from_diff(diff, idx) {
+            Ok(Some(patch)) => patch
+                .line_stats()
+                .map(|(_, add, del)| (add, del))
+                .unwrap_or((0, 0)),
+            _ => (0, 0),
+        };
+
+        files.push(FileDiffStat {
+            path,
+            additions,
+            deletions,
+            status: delta_to_label(delta.status()).to_string(),
+        });
+    }
+
+    Ok(DiffSummary {
+        files_changed: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+        files,
+    })
+}
+
+fn delta_to_label(delta: Delta) -> &'static str {
+    match delta {
+        Delta::Added | Delta::Untracked => "added",
+        Delta::Deleted => "deleted",
+        Delta::Modified | Delta::Typechange => "modified",
+        Delta::Renamed => "renamed",
+        Delta::Copied => "copied",
+        Delta::Conflicted => "conflicted",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn init_repo_with_file(name: &str, content: &str) -> std::path::PathBuf {
+        let temp =
+            std::env::temp_dir().join(format!("central_diff_stats_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+
+        std::fs::write(temp.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let sig = git2::Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        temp
+    }
+
+    #[test]
+    fn summary_counts_insertions_and_deletions() {
+        let temp = init_repo_with_file("a.txt", "one\ntwo\nthree\n");
+        std::fs::write(temp.join("a.txt"), "one\nTWO\nthree\nfour\n").unwrap();
+
+        let summary = get_diff_summary(temp.to_string_lossy().to_string(), None).unwrap();
+
+        assert_eq!(summary.files_changed, 1);
+        assert_eq!(summary.insertions, 2);
+        assert_eq!(summary.deletions, 1);
+        assert_eq!(summary.files[0].path, "a.txt");
+        assert_eq!(summary.files[0].status, "modified");
+        assert_eq!(summary.files[0].additions, 2);
+        assert_eq!(summary.files[0].deletions, 1);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn staged_mode_ignores_unstaged_edits() {
+        let temp = init_repo_with_file("a.txt", "one\n");
+        std::fs::write(temp.join("a.txt"), "changed\n").unwrap();
+
+        let summary = get_diff_summary(
+            temp.to_string_lossy().to_string(),
+            Some("staged".to_string()),
+        )
+        .unwrap();
+        assert_eq!(summary.files_changed, 0);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn diff_against_branch_includes_commits_and_workdir() {
+        let temp = init_repo_with_file("a.txt", "one\n");
+        let repo = Repository::open(&temp).unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("base", &base, false).unwrap();
+
+        std::fs::write(temp.join("b.txt"), "new\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("b.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "add b", &tree, &[&base])
+            .unwrap();
+        std::fs::write(temp.join("a.txt"), "two\n").unwrap();
+
+        let summary =
+            git_diff_against_branch(temp.to_string_lossy().to_string(), "base".to_string())
+                .unwrap();
+        let paths: Vec<&str> = summary.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+
+        let unknown =
+            git_diff_against_branch(temp.to_string_lossy().to_string(), "nope".to_string());
+        assert!(unknown.unwrap_err().contains("Unknown branch"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn compare_branches_uses_tips_not_workdir() {
+        let temp = init_repo_with_file("a.txt", "one\n");
+        let repo = Repository::open(&temp).unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("base", &base, false).unwrap();
+
+        std::fs::write(temp.join("a.txt"), "one\ntwo\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "edit a", &tree, &[&base])
+            .unwrap();
+        std::fs::write(temp.join("uncommitted.txt"), "x\n").unwrap();
+
+        let path = temp.to_string_lossy().to_string();
+        let head = repo.head().unwrap().shorthand().unwrap().to_string();
+        let summary = git_compare_branches(path.clone(), "base".to_string(), head).unwrap();
+        assert_eq!(summary.files_changed, 1);
+        assert_eq!(summary.files[0].path, "a.txt");
+        assert_eq!(summary.files[0].additions, 1);
+
+        let unknown = git_compare_branches(path, "base".to_string(), "nope".to_string());
+        assert!(unknown.unwrap_err().contains("Unknown branch nope"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn unknown_mode_is_rejected() {
+        let temp = init_repo_with_file("a.txt", "one\n");
+
+        let result = get_diff_summary(
+            temp.to_string_lossy().to_string(),
+            Some("sideways".to_string()),
+        );
+        assert!(result.unwrap_err().contains("Unknown diff mode"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+use git2::{Status, StatusOptions, StatusShow};
+
+use super::git_helpers::open_repo;
+use super::types::CleanState;
+
+const STAGED: Status = Status::INDEX_NEW
+    .union(Status::INDEX_MODIFIED)
+    .union(Status::INDEX_DELETED)
+    .union(Status::INDEX_RENAMED)
+    .union(Status::INDEX_TYPECHANGE);
+
+const UNSTAGED: Status = Status::WT_MODIFIED
+    .union(Status::WT_DELETED)
+    .union(Status::WT_RENAMED)
+    .union(Status::WT_TYPECHANGE)
+    .union(Status::CONFLICTED);
+
+/// Whether the working tree has uncommitted changes, without building the
+/// changed-file list. Untracked directories count once rather than per file,
+/// and submodules and rename detection are skipped to keep the scan cheap.
+#[tauri::command]
+pub fn git_is_clean(project_path: String) -> Result<CleanState, String> {
+    let repo = open_repo(&project_path)?;
+    let mut opts = StatusOptions::new();
+    opts.show(StatusShow::IndexAndWorkdir)
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(false)
+        .exclude_submodules(true);
+
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .map_err(|e| format!("Failed to get statuses: {e}"))?;
+    Ok(count_statuses(statuses.iter().map(|entry| entry.status())))
+}
+
+fn count_statuses(statuses: impl Iterator<Item = Status>) -> CleanState {
+    let mut state = CleanState {
+        clean: true,
+        staged: 0,
+        unstaged: 0,
+        untracked: 0,
+    };
+    for status in statuses {
+        // A file can be staged and then edited again, so it counts in both
+        if status.intersects(STAGED) {
+            state.staged += 1;
+        }
+        if status.intersects(UNSTAGED) {
+            state.unstaged += 1;
+        }
+        if status.contains(Status::WT_NEW) {
+            state.untracked += 1;
+        }
+    }
+    state.clean = state.staged == 0 && state.unstaged == 0 && state.untracked == 0;
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use std::path::Path;
+
+    #[test]
+    fn counts_staged_unstaged_and_untracked() {
+        let temp = std::env::temp_dir().join(format!("central_dirty_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "one\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let path = temp.to_string_lossy().to_string();
+
+        assert!(git_is_clean(path.clone()).unwrap().clean);
+
+        std::fs::write(temp.join("a.txt"), "two\n").unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(temp.join("a.txt"), "three\n").unwrap();
+        std::fs::create_dir_all(temp.join("new/deep")).unwrap();
+        std::fs::write(temp.join("new/deep/b.txt"), "b\n").unwrap();
+        std::fs::write(temp.join("new/c.txt"), "c\n").unwrap();
+
+        let state = git_is_clean(path).unwrap();
+        assert!(!state.clean);
+        assert_eq!((state.staged, state.unstaged, state.untracked), (1, 1, 1));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
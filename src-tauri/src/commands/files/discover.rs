@@ -1,6 +1,8 @@
 use serde::Serialize;
 use std::path::PathBuf;
 
+use super::skip::SkipRules;
+
 #[derive(Debug, Serialize)]
 pub struct DiscoveredDir {
     pub name: String,
@@ -42,7 +44,7 @@ pub fn list_project_directories() -> Vec<DiscoveredDir> {
                 }
                 let name = entry.file_name().to_string_lossy().to_string();
                 // Skip hidden dirs and common non-project dirs
-                if name.starts_with('.') || name == "node_modules" || name == "target" {
+                if SkipRules::default().skips(&name) {
                     continue;
                 }
                 let path = entry.path().to_string_lossy().to_string();
@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::fs_helpers::canonical_project;
+
+/// Capacity of the filesystem a project lives on
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DiskSpace {
+    pub total: u64,
+    /// Bytes usable by the current user (excludes root-reserved blocks)
+    pub available: u64,
+}
+
+/// Total and available bytes on the volume containing the project, so the UI
+/// can warn before a build or a big generation run fills the disk
+#[tauri::command]
+pub async fn disk_space(project_path: String) -> Result<DiskSpace, String> {
+    let project = canonical_project(&project_path)?;
+    tokio::task::spawn_blocking(move || volume_space(&project))
+        .await
+        .map_err(|e| format!("Disk space task failed: {e}"))?
+}
+
+/// Asks `df` in POSIX mode (one line per volume, 1K blocks) rather than
+/// calling statvfs directly
+#[cfg(unix)]
+fn volume_space(path: &Path) -> Result<DiskSpace, String> {
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run df: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to stat volume for {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_df(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| format!("Unexpected df output for {}", path.display()))
+}
+
+/// Sizes from the data line of `df -Pk`. Filesystem and mount names may
+/// contain spaces, so columns are found relative to the "Capacity" one.
+#[cfg(unix)]
+fn parse_df(output: &str) -> Option<DiskSpace> {
+    let fields: Vec<&str> = output.lines().nth(1)?.split_whitespace().collect();
+    let capacity = fields.iter().position(|f| f.ends_with('%'))?;
+    let kib = |offset: usize| -> Option<u64> {
+        fields
+            .get(capacity.checked_sub(offset)?)?
+            .parse::<u64>()
+            .ok()
+    };
+    Some(DiskSpace {
+        total: kib(3)? * 1024,
+        available: kib(1)? * 1024,
+    })
+}
+
+#[cfg(windows)]
+fn volume_space(path: &Path) -> Result<DiskSpace, String> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(
+            directory: *const u16,
+            free_to_caller: *mut u64,
+            total: *mut u64,
+            total_free: *mut u64,
+        ) -> i32;
+    }
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let (mut available, mut total, mut free) = (0u64, 0u64, 0u64);
+    // SAFETY: wide is NUL-terminated and the out-pointers are valid u64s
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, &mut total, &mut free) };
+    if ok == 0 {
+        let err = std::io::Error::last_os_error();
+        return Err(format!(
+            "Failed to stat volume for {}: {err}",
+            path.display()
+        ));
+    }
+    Ok(DiskSpace { total, available })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_space_for_temp_volume() {
+        let space = volume_space(&std::env::temp_dir()).unwrap();
+        assert!(space.total > 0);
+        assert!(space.available <= space.total);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parses_df_with_spaces_in_names() {
+        let output = "Filesystem 1024-blocks Used Available Capacity Mounted on\n\
+                      map auto home 100 40 50 45% /System/Volumes/Data/home dir\n";
+        assert_eq!(
+            parse_df(output),
+            Some(DiskSpace {
+                total: 100 * 1024,
+                available: 50 * 1024,
+            })
+        );
+        assert_eq!(parse_df("Filesystem\n"), None);
+    }
+}
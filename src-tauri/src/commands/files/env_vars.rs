@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use super::fs_helpers::{canonical_project, read_text_file};
+use super::types::EnvRequirement;
+
+/// Template files that list a project's variables. `.env` itself is never
+/// read: it holds real secrets.
+const ENV_TEMPLATES: &[&str] = &[".env.example", ".env.sample", ".env.template", ".env.dist"];
+
+/// Variables a project expects (from its `.env.example`-style templates) and
+/// whether each is set in the environment agents inherit. Only names are
+/// returned, never values.
+#[tauri::command]
+pub fn list_env_requirements(project_path: String) -> Result<Vec<EnvRequirement>, String> {
+    let project = canonical_project(&project_path)?;
+    let mut found: BTreeMap<String, String> = BTreeMap::new();
+
+    for template in ENV_TEMPLATES {
+        let path = project.join(template);
+        if !path.is_file() {
+            continue;
+        }
+        for name in parse_var_names(&read_text_file(&path)?) {
+            found.entry(name).or_insert_with(|| template.to_string());
+        }
+    }
+
+    Ok(found
+        .into_iter()
+        .map(|(name, source)| EnvRequirement {
+            present: is_set(&name),
+            name,
+            source,
+        })
+        .collect())
+}
+
+fn is_set(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|v| !v.is_empty())
+}
+
+/// Names from `KEY=value` / `export KEY=value` lines, skipping comments
+fn parse_var_names(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, _) = line.split_once('=')?;
+            let name = name.trim();
+            let valid = !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            valid.then(|| name.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_never_reads_dot_env() {
+        assert_eq!(
+            parse_var_names(
+                "# Database\nDATABASE_URL=postgres://\nexport API_KEY=\n\nnot a var\n1BAD=x\n"
+            ),
+            vec!["DATABASE_URL", "API_KEY"]
+        );
+
+        let temp = std::env::temp_dir().join(format!("central_env_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(
+            temp.join(".env.example"),
+            "PATH=\nCENTRAL_SURELY_UNSET_VAR=changeme\n",
+        )
+        .unwrap();
+        std::fs::write(temp.join(".env"), "SECRET_ONLY_IN_DOT_ENV=hunter2\n").unwrap();
+
+        let reqs = list_env_requirements(temp.to_string_lossy().to_string()).unwrap();
+        let summary: Vec<(&str, bool)> =
+            reqs.iter().map(|r| (r.name.as_str(), r.present)).collect();
+        assert_eq!(
+            summary,
+            vec![("CENTRAL_SURELY_UNSET_VAR", false), ("PATH", true)]
+        );
+        assert_eq!(reqs[0].source, ".env.example");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
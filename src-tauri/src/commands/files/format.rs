@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use super::fs_helpers::{canonical_project, read_text_file, resolve_existing_in_project};
+use super::types::FileFormat;
+
+/// Detect a file's line endings and indentation so the editor can show and keep them
+#[tauri::command]
+pub fn detect_file_format(project_path: String, file_path: String) -> Result<FileFormat, String> {
+    let project = canonical_project(&project_path)?;
+    let path = resolve_existing_in_project(&project, &file_path)?;
+    let content = read_text_file(&path)?;
+
+    let (indent, indent_width) = detect_indent(&content);
+    Ok(FileFormat {
+        line_ending: detect_line_ending(&content).to_string(),
+        indent: indent.to_string(),
+        indent_width,
+    })
+}
+
+/// Files without any newline count as "lf"
+fn detect_line_ending(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+
+    match (crlf, lf) {
+        (0, _) => "lf",
+        (_, 0) => "crlf",
+        _ => "mixed",
+    }
+}
+
+/// Indent style from leading whitespace; the width is the most common
+/// increase in indentation between consecutive space-indented lines
+fn detect_indent(content: &str) -> (&'static str, Option<u8>) {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut steps: HashMap<usize, usize> = HashMap::new();
+    let mut prev_spaces = 0;
+
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            continue;
+        }
+        let spaces = line.len() - line.trim_start_matches(' ').len();
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        if spaces > prev_spaces {
+            *steps.entry(spaces - prev_spaces).or_default() += 1;
+        }
+        prev_spaces = spaces;
+    }
+
+    let indent = match (tab_lines, space_lines) {
+        (0, _) => "spaces",
+        (_, 0) => "tabs",
+        _ => "mixed",
+    };
+    let width = steps
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .and_then(|(step, _)| u8::try_from(step).ok())
+        .filter(|_| space_lines > 0);
+
+    (indent, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_line_endings() {
+        assert_eq!(detect_line_ending("a\nb\n"), "lf");
+        assert_eq!(detect_line_ending("a\r\nb\r\n"), "crlf");
+        assert_eq!(detect_line_ending("a\r\nb\n"), "mixed");
+        assert_eq!(detect_line_ending("no newline"), "lf");
+    }
+
+    #[test]
+    fn detects_space_indent_width() {
+        let content = "fn a() {\n    if x {\n        y();\n    }\n}\n";
+        assert_eq!(detect_indent(content), ("spaces", Some(4)));
+
+        let two = "a:\n  b:\n    c: 1\n  d: 2\n";
+        assert_eq!(detect_indent(two), ("spaces", Some(2)));
+    }
+
+    #[test]
+    fn detects_tabs_and_mixed() {
+        assert_eq!(detect_indent("a\n\tb\n\t\tc\n"), ("tabs", None));
+        assert_eq!(detect_indent("a\n\tb\n  c\n").0, "mixed");
+        assert_eq!(detect_indent("flat\nfile\n"), ("spaces", None));
+    }
+
+    #[test]
+    fn detect_file_format_reads_project_file() {
+        let temp =
+            std::env::temp_dir().join(format!("central_format_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.py"), "def f():\r\n    return 1\r\n").unwrap();
+
+        let format =
+            detect_file_format(temp.to_string_lossy().to_string(), "a.py".to_string()).unwrap();
+        assert_eq!(
+            format,
+            FileFormat {
+                line_ending: "crlf".to_string(),
+                indent: "spaces".to_string(),
+                indent_width: Some(4),
+            }
+        );
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,276 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::types::DirUsage;
+use crate::project_path::resolve_project_path;
+
+/// Largest file we'll ship to the frontend as text in one read
+pub const MAX_READ_BYTES: u64 = 5 * 1024 * 1024;
+
+/// How much of a file to sniff for NUL bytes when deciding if it's binary
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+pub fn canonical_project(project_path: &str) -> Result<PathBuf, String> {
+    Ok(resolve_project_path(project_path)?)
+}
+
+/// Resolve an existing file inside the project, refusing anything that
+/// escapes it via `..` or symlinks
+pub fn resolve_existing_in_project(
+    canonical_project: &Path,
+    file_path: &str,
+) -> Result<PathBuf, String> {
+    let full = canonical_project.join(file_path);
+    if !full.exists() {
+        return Err(format!("File not found: {file_path}"));
+    }
+
+    let canonical = full
+        .canonicalize()
+        .map_err(|e| format!("Invalid path: {e}"))?;
+    if !canonical.starts_with(canonical_project) {
+        return Err("Cannot read outside project directory".to_string());
+    }
+
+    Ok(canonical)
+}
+
+/// Resolve a file that may not exist (any more) to where it lives inside the
+/// project: the deepest existing ancestor is canonicalized and the missing
+/// rest appended, so `..`, `//`, symlinks and absolute paths all resolve to
+/// the same path. Refuses anything that ends up outside the project.
+pub fn resolve_inside_project(
+    canonical_project: &Path,
+    file_path: &str,
+) -> Result<PathBuf, String> {
+    let mut existing = canonical_project.join(file_path);
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        let name = existing
+            .file_name()
+            .ok_or_else(|| format!("Invalid path: {file_path}"))?;
+        missing.push(name.to_os_string());
+        existing.pop();
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|e| format!("Invalid path: {e}"))?;
+    resolved.extend(missing.iter().rev());
+    if !resolved.starts_with(canonical_project) {
+        return Err(format!(
+            "Path is outside the project directory: {file_path}"
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Resolve a (possibly new) file path for writing, refusing to write outside
+/// the project directory. The parent directory must already exist.
+pub fn resolve_for_write(project_path: &str, file_path: &str) -> Result<PathBuf, String> {
+    let canonical_project = canonical_project(project_path)?;
+    let full = canonical_project.join(file_path);
+
+    let parent = full
+        .parent()
+        .ok_or_else(|| "Invalid file path".to_string())?;
+    // Ensure parent directory exists before canonicalizing
+    if !parent.exists() {
+        return Err(format!(
+            "Parent directory does not exist: {}",
+            parent.display()
+        ));
+    }
+    let canonical_full = parent
+        .canonicalize()
+        .map_err(|e| format!("Invalid path: {e}"))?
+        .join(full.file_name().ok_or("Invalid file name")?);
+    if !canonical_full.starts_with(&canonical_project) {
+        return Err("Cannot write outside project directory".to_string());
+    }
+
+    Ok(canonical_full)
+}
+
+/// Write via a sibling temp file + rename so readers never see a half-written
+/// file. Keeps the existing file's permissions (e.g. executable scripts).
+pub fn atomic_write(target: &Path, bytes: &[u8]) -> Result<(), String> {
+    let tmp = write_temp_sibling(target, bytes)?;
+    std::fs::rename(&tmp, target).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to write file: {e}")
+    })
+}
+
+/// A hidden sibling path of `target`, unique per call
+pub fn sibling_path(target: &Path, tag: &str) -> Result<PathBuf, String> {
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| "Invalid file name".to_string())?
+        .to_string_lossy();
+    Ok(target.with_file_name(format!(
+        ".{file_name}.central-{tag}-{}",
+        uuid::Uuid::new_v4()
+    )))
+}
+
+/// Write `bytes` to a temp file next to `target`, carrying over the existing
+/// file's permissions. The caller renames it into place.
+pub fn write_temp_sibling(target: &Path, bytes: &[u8]) -> Result<PathBuf, String> {
+    let tmp = sibling_path(target, "tmp")?;
+
+    let result = std::fs::write(&tmp, bytes).and_then(|_| match std::fs::metadata(target) {
+        Ok(meta) => std::fs::set_permissions(&tmp, meta.permissions()),
+        Err(_) => Ok(()),
+    });
+
+    result.map(|_| tmp.clone()).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to write file: {e}")
+    })
+}
+
+/// Total size of the regular files below `path`. Symlinks are not followed.
+pub fn dir_size(path: &Path) -> u64 {
+    dir_usage(path, &AtomicBool::new(false)).map_or(0, |usage| usage.bytes)
+}
+
+/// Bytes and file count below `path`, checking `cancel` between directories
+/// so a huge walk can be abandoned. Symlinks are not followed.
+pub fn dir_usage(path: &Path, cancel: &AtomicBool) -> Result<DirUsage, String> {
+    let mut usage = DirUsage { bytes: 0, files: 0 };
+    let mut stack = vec![path.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("Cancelled".to_string());
+        }
+        let Ok(read) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for item in read.flatten() {
+            match item.metadata() {
+                Ok(meta) if meta.is_dir() => stack.push(item.path()),
+                Ok(meta) if meta.is_file() => {
+                    usage.bytes += meta.len();
+                    usage.files += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(usage)
+}
+
+/// Same heuristic git uses: a NUL byte near the start means binary
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Return the file's size, rejecting anything over `MAX_READ_BYTES`
+pub fn check_read_size(path: &Path) -> Result<u64, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read file: {e}"))?
+        .len();
+    if size > MAX_READ_BYTES {
+        return Err(format!(
+            "File too large: {size} bytes (limit {MAX_READ_BYTES})"
+        ));
+    }
+    Ok(size)
+}
+
+/// Read a UTF-8 text file, rejecting oversized and binary files
+pub fn read_text_file(path: &Path) -> Result<String, String> {
+    check_read_size(path)?;
+
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read file: {e}"))?;
+    if looks_binary(&bytes) {
+        return Err("Binary file".to_string());
+    }
+
+    String::from_utf8(bytes).map_err(|_| "File is not valid UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_binary_detects_nul() {
+        assert!(looks_binary(b"abc\0def"));
+        assert!(!looks_binary(b"plain text\n"));
+        assert!(!looks_binary(b""));
+    }
+
+    #[test]
+    fn atomic_write_replaces_content_and_leaves_no_temp_files() {
+        let temp =
+            std::env::temp_dir().join(format!("central_atomic_write_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let target = temp.join("a.txt");
+        std::fs::write(&target, "old").unwrap();
+
+        atomic_write(&target, b"new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(&temp).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn resolve_rejects_traversal() {
+        let temp =
+            std::env::temp_dir().join(format!("central_fs_helpers_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("inner")).unwrap();
+        std::fs::write(temp.join("outside.txt"), "x").unwrap();
+
+        let project = canonical_project(&temp.join("inner").to_string_lossy()).unwrap();
+        let result = resolve_existing_in_project(&project, "../outside.txt");
+        assert!(result.unwrap_err().contains("outside project"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn resolve_for_write_rejects_traversal_and_absolute_paths() {
+        let temp =
+            std::env::temp_dir().join(format!("central_fs_helpers_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("inner")).unwrap();
+        let project = temp.join("inner").to_string_lossy().to_string();
+
+        for path in ["../../etc/passwd", "../../../etc/passwd", "../outside.txt"] {
+            assert!(resolve_for_write(&project, path).is_err(), "{path}");
+        }
+        let err = resolve_for_write(&project, "/etc/passwd").unwrap_err();
+        assert!(err.contains("outside project"), "{err}");
+        let inside = resolve_for_write(&format!("{project}/../inner"), "a.txt").unwrap();
+        assert_eq!(inside, canonical_project(&project).unwrap().join("a.txt"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn resolve_inside_project_normalizes_missing_files() {
+        let temp =
+            std::env::temp_dir().join(format!("central_fs_helpers_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("inner/src")).unwrap();
+        let project = canonical_project(&temp.join("inner").to_string_lossy()).unwrap();
+        let expected = project.join("src/new/a.rs");
+
+        for path in [
+            "src/new/a.rs",
+            "src/../src/new/a.rs",
+            "src//new/a.rs",
+            &expected.to_string_lossy(),
+        ] {
+            assert_eq!(resolve_inside_project(&project, path).unwrap(), expected);
+        }
+        assert!(resolve_inside_project(&project, "../outside.txt").is_err());
+        assert!(resolve_inside_project(&project, "/etc/passwd").is_err());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -3,6 +3,10 @@ use std::collections::HashMap;
 
 use super::types::ChangedFile;
 
+mod location;
+
+pub use location::{common_dir, open_repo, project_prefix};
+
 pub fn collect_git_statuses(
     repo: &Repository,
 ) -> Result<HashMap<String, String>, String> {
@@ -57,6 +61,18 @@ pub fn get_branch_name(repo: &Repository) -> String {
         .unwrap_or_else(|| "HEAD (detached)".to_string())
 }
 
+/// The commit HEAD points at, or None for an unborn HEAD (no commits yet)
+pub fn head_commit(repo: &Repository) -> Result<Option<git2::Commit<'_>>, String> {
+    match repo.head() {
+        Ok(head) => head
+            .peel_to_commit()
+            .map(Some)
+            .map_err(|e| format!("Failed to resolve HEAD commit: {e}")),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(format!("Failed to read HEAD: {e}")),
+    }
+}
+
 pub fn get_ahead_behind(repo: &Repository) -> (usize, usize) {
     let head = match repo.head() {
         Ok(h) => h,
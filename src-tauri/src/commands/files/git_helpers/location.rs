@@ -0,0 +1,128 @@
+//! Finding the repository a project lives in, and where in it
+
+use git2::Repository;
+use std::path::{Path, PathBuf};
+
+use crate::project_path::resolve_project_path;
+
+/// Open the repo containing `project_path`, which may be a subdirectory of it
+/// or a linked worktree (whose HEAD, index and status are its own). Bare
+/// repos are refused: there's no working tree to show.
+pub fn open_repo(project_path: &str) -> Result<Repository, String> {
+    let project = resolve_project_path(project_path)?;
+    let repo = Repository::discover(&project).map_err(|e| format!("Not a git repository: {e}"))?;
+    if repo.is_bare() {
+        return Err(format!(
+            "{project_path} is a bare repository; open one of its worktrees instead"
+        ));
+    }
+    Ok(repo)
+}
+
+/// The `.git` directory shared by every worktree. A linked worktree's own
+/// git dir (`.git/worktrees/<name>`) names it in its `commondir` file.
+pub fn common_dir(repo: &Repository) -> PathBuf {
+    let git_dir = repo.path();
+    match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(rel) => {
+            let dir = git_dir.join(rel.trim());
+            dir.canonicalize().unwrap_or(dir)
+        }
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// Where the project sits inside the repo's working tree ("" when the
+/// project is the repo root). Git reports paths relative to the repo root.
+pub fn project_prefix(repo: &Repository, project_root: &Path) -> PathBuf {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    let project = resolve_project_path(&project_root.to_string_lossy())
+        .unwrap_or_else(|_| canonical(project_root));
+    repo.workdir()
+        .and_then(|workdir| {
+            project
+                .strip_prefix(canonical(workdir))
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::files::diff::get_diff;
+    use crate::commands::files::git_info::is_git_repo;
+    use crate::commands::files::status::get_git_status;
+
+    #[test]
+    fn open_repo_refuses_bare_repos() {
+        let temp = std::env::temp_dir().join(format!("central_bare_{}", uuid::Uuid::new_v4()));
+        Repository::init_bare(&temp).unwrap();
+
+        let err = open_repo(&temp.to_string_lossy()).err().unwrap();
+        assert!(err.contains("bare repository"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn project_prefix_is_the_path_below_the_repo_root() {
+        let temp = std::env::temp_dir().join(format!("central_prefix_{}", uuid::Uuid::new_v4()));
+        let nested = temp.join("packages").join("app");
+        std::fs::create_dir_all(&nested).unwrap();
+        Repository::init(&temp).unwrap();
+
+        let repo = open_repo(&nested.to_string_lossy()).unwrap();
+        assert_eq!(project_prefix(&repo, &nested), Path::new("packages/app"));
+        assert_eq!(project_prefix(&repo, &temp), Path::new(""));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn linked_worktree_opens_with_its_own_branch_and_changes() {
+        let temp =
+            std::env::temp_dir().join(format!("central_status_worktree_{}", uuid::Uuid::new_v4()));
+        let main = temp.join("main");
+        std::fs::create_dir_all(&main).unwrap();
+        let repo = Repository::init(&main).unwrap();
+        let sig = git2::Signature::now("test", "t@t.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let head = repo
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let branch = repo
+            .branch("feature", &repo.find_commit(head).unwrap(), false)
+            .unwrap();
+
+        let linked = temp.join("linked");
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(branch.get()));
+        repo.worktree("linked", &linked, Some(&opts)).unwrap();
+        std::fs::write(linked.join("only-here.txt"), "hi").unwrap();
+
+        let status = get_git_status(linked.to_string_lossy().to_string()).unwrap();
+        assert_eq!(status.branch, "feature");
+        let paths: Vec<&str> = status
+            .changed_files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["only-here.txt"]);
+        let diff = get_diff(linked.to_string_lossy().to_string(), None, None).unwrap();
+        assert_eq!(diff[0].path, "only-here.txt");
+
+        let info = is_git_repo(linked.to_string_lossy().to_string());
+        assert!(info.is_worktree);
+        assert!(info.common_dir.unwrap().contains("main"));
+
+        let main_status = get_git_status(main.to_string_lossy().to_string()).unwrap();
+        assert_ne!(main_status.branch, "feature");
+        assert!(main_status.changed_files.is_empty());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+use git2::{Config, ConfigLevel};
+
+use super::git_helpers::open_repo;
+use super::types::GitIdentity;
+
+/// The `user.name` / `user.email` git would sign commits with here, resolved
+/// through local, global and system config
+#[tauri::command]
+pub fn get_git_identity(project_path: String) -> Result<GitIdentity, String> {
+    let repo = open_repo(&project_path)?;
+    let config = repo
+        .config()
+        .and_then(|mut c| c.snapshot())
+        .map_err(|e| format!("Failed to read git config: {e}"))?;
+
+    let name = config
+        .get_string("user.name")
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+    let email = config
+        .get_string("user.email")
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+    Ok(GitIdentity {
+        configured: name.is_some() && email.is_some(),
+        name,
+        email,
+    })
+}
+
+/// Write `user.name` / `user.email` to the repo's own config, or to the
+/// user's global config with `global`
+#[tauri::command]
+pub fn set_git_identity(
+    project_path: String,
+    name: String,
+    email: String,
+    global: bool,
+) -> Result<(), String> {
+    let (name, email) = (name.trim(), email.trim());
+    if name.is_empty() || email.is_empty() {
+        return Err("Name and email are required".to_string());
+    }
+
+    let mut config = match global {
+        true => global_config()?,
+        false => open_repo(&project_path)?
+            .config()
+            .and_then(|c| c.open_level(ConfigLevel::Local))
+            .map_err(|e| format!("Failed to open repo config: {e}"))?,
+    };
+
+    config
+        .set_str("user.name", name)
+        .and_then(|_| config.set_str("user.email", email))
+        .map_err(|e| format!("Failed to write git identity: {e}"))
+}
+
+/// `~/.gitconfig`, created if this is a fresh install without one
+fn global_config() -> Result<Config, String> {
+    let path = Config::find_global()
+        .ok()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".gitconfig")))
+        .ok_or("Cannot locate the global git config")?;
+    Config::open(&path).map_err(|e| format!("Failed to open global git config: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn set_identity_locally_is_read_back() {
+        let temp = std::env::temp_dir().join(format!("central_identity_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        Repository::init(&temp).unwrap();
+        let path = temp.to_string_lossy().to_string();
+
+        assert!(set_git_identity(path.clone(), " ".into(), "a@b.c".into(), false).is_err());
+        set_git_identity(path.clone(), "Ada".into(), "ada@example.com".into(), false).unwrap();
+
+        let identity = get_git_identity(path).unwrap();
+        assert!(identity.configured);
+        assert_eq!(identity.name.as_deref(), Some("Ada"));
+        assert_eq!(identity.email.as_deref(), Some("ada@example.com"));
+    }
+}
@@ -0,0 +1,207 @@
+use git2::Repository;
+
+use super::git_helpers::{common_dir, head_commit, open_repo};
+use super::types::{HeadInfo, RepoInfo};
+
+pub(super) const SHORT_HASH_LEN: usize = 7;
+
+/// Local branches tried, in order, when `origin/HEAD` isn't set
+const DEFAULT_BRANCH_CANDIDATES: &[&str] = &["main", "master"];
+
+/// Details of the current HEAD commit for "on commit abc123 — Fix parser".
+/// A repo with no commits yet returns `has_commits: false` instead of an error.
+#[tauri::command]
+pub fn git_head_info(project_path: String) -> Result<HeadInfo, String> {
+    let repo = open_repo(&project_path)?;
+
+    let detached = repo.head_detached().unwrap_or(false);
+    let branch = match detached {
+        true => None,
+        false => unborn_or_current_branch(&repo),
+    };
+
+    let Some(commit) = head_commit(&repo)? else {
+        return Ok(HeadInfo {
+            has_commits: false,
+            detached,
+            branch,
+            hash: None,
+            short_hash: None,
+            author: None,
+            timestamp: None,
+            summary: None,
+        });
+    };
+
+    let hash = commit.id().to_string();
+    let author = commit.author().name().map(String::from);
+    let info = HeadInfo {
+        has_commits: true,
+        detached,
+        branch,
+        short_hash: Some(hash.chars().take(SHORT_HASH_LEN).collect()),
+        hash: Some(hash),
+        author,
+        timestamp: Some(commit.time().seconds()),
+        summary: commit.summary().map(String::from),
+    };
+    Ok(info)
+}
+
+/// The repo's default branch ("main", "master", "develop", ...) for
+/// branch-based diffs. Read from the `origin/HEAD` symref when present,
+/// else the first of main/master that exists locally; None if neither.
+#[tauri::command]
+pub fn git_default_branch(project_path: String) -> Result<Option<String>, String> {
+    let repo = open_repo(&project_path)?;
+
+    let from_origin = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|r| r.symbolic_target().map(String::from))
+        .map(|target| {
+            target
+                .trim_start_matches("refs/remotes/origin/")
+                .to_string()
+        });
+    if from_origin.is_some() {
+        return Ok(from_origin);
+    }
+
+    let local = DEFAULT_BRANCH_CANDIDATES
+        .iter()
+        .find(|name| repo.find_branch(name, git2::BranchType::Local).is_ok())
+        .map(|name| name.to_string());
+    Ok(local)
+}
+
+/// Cheap check for whether git features apply to a path. A subdirectory of a
+/// repo counts, and `repo_root` then points at the enclosing working tree.
+#[tauri::command]
+pub fn is_git_repo(project_path: String) -> RepoInfo {
+    let repo = open_repo(&project_path).ok();
+    let repo_root = repo
+        .as_ref()
+        .and_then(|repo| repo.workdir().map(|dir| dir.to_string_lossy().to_string()));
+    RepoInfo {
+        is_repo: repo_root.is_some(),
+        repo_root,
+        is_worktree: repo.as_ref().is_some_and(Repository::is_worktree),
+        common_dir: repo.map(|repo| common_dir(&repo).to_string_lossy().to_string()),
+    }
+}
+
+/// Branch name HEAD refers to — works even before the first commit
+fn unborn_or_current_branch(repo: &Repository) -> Option<String> {
+    let head = repo.find_reference("HEAD").ok()?;
+    let target = head.symbolic_target()?;
+    Some(target.trim_start_matches("refs/heads/").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_repo() -> (std::path::PathBuf, Repository) {
+        let temp = std::env::temp_dir().join(format!("central_git_info_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        (temp, repo)
+    }
+
+    fn commit(repo: &Repository, msg: &str) -> git2::Oid {
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let sig = git2::Signature::now("tester", "test@test.com").unwrap();
+        let parents: Vec<git2::Commit> = head_commit(repo).unwrap().into_iter().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[test]
+    fn unborn_head_reports_no_commits() {
+        let (temp, _repo) = temp_repo();
+
+        let info = git_head_info(temp.to_string_lossy().to_string()).unwrap();
+        assert!(!info.has_commits);
+        assert!(!info.detached);
+        assert!(info.branch.is_some());
+        assert!(info.hash.is_none());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn head_info_describes_latest_commit() {
+        let (temp, repo) = temp_repo();
+        let oid = commit(&repo, "Fix parser\n\nLonger body");
+
+        let info = git_head_info(temp.to_string_lossy().to_string()).unwrap();
+        assert!(info.has_commits);
+        assert_eq!(info.hash, Some(oid.to_string()));
+        assert_eq!(
+            info.short_hash.as_deref().map(str::len),
+            Some(SHORT_HASH_LEN)
+        );
+        assert_eq!(info.summary.as_deref(), Some("Fix parser"));
+        assert_eq!(info.author.as_deref(), Some("tester"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn default_branch_prefers_origin_head() {
+        let (temp, repo) = temp_repo();
+        let oid = commit(&repo, "init");
+        let path = temp.to_string_lossy().to_string();
+
+        // No remote yet: falls back to the local main/master git init created
+        let initial = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert_eq!(git_default_branch(path.clone()).unwrap(), Some(initial));
+
+        repo.reference("refs/remotes/origin/develop", oid, true, "test")
+            .unwrap();
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/develop",
+            true,
+            "test",
+        )
+        .unwrap();
+        assert_eq!(
+            git_default_branch(path).unwrap().as_deref(),
+            Some("develop")
+        );
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn is_git_repo_reports_root_for_subdirectories() {
+        let (temp, _repo) = temp_repo();
+        let nested = temp.join("src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let info = is_git_repo(nested.to_string_lossy().to_string());
+        assert!(info.is_repo);
+        let root = std::path::PathBuf::from(info.repo_root.unwrap());
+        assert_eq!(root.canonicalize().unwrap(), temp.canonicalize().unwrap());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+        assert!(!is_git_repo(temp.to_string_lossy().to_string()).is_repo);
+    }
+
+    #[test]
+    fn detached_head_has_no_branch() {
+        let (temp, repo) = temp_repo();
+        let oid = commit(&repo, "init");
+        repo.set_head_detached(oid).unwrap();
+
+        let info = git_head_info(temp.to_string_lossy().to_string()).unwrap();
+        assert!(info.detached);
+        assert!(info.branch.is_none());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use super::fs_helpers::{canonical_project, resolve_existing_in_project};
+use super::types::FileHash;
+use crate::content_hash::content_hash;
+
+/// Content hash and size of a project file. The editor hashes on open and
+/// passes the hash back as `write_file`'s `expected_hash`, so a save can't
+/// clobber an edit made on disk in the meantime.
+#[tauri::command]
+pub fn hash_file(project_path: String, file_path: String) -> Result<FileHash, String> {
+    let project = canonical_project(&project_path)?;
+    let full = resolve_existing_in_project(&project, &file_path)?;
+    let bytes = std::fs::read(&full).map_err(|e| format!("Failed to read {file_path}: {e}"))?;
+    Ok(FileHash {
+        hash: content_hash(&bytes),
+        size: bytes.len() as u64,
+    })
+}
+
+/// Refuse to overwrite `target` unless it still hashes to `expected`
+pub fn check_unchanged(target: &Path, expected: &str) -> Result<(), String> {
+    let current = match std::fs::read(target) {
+        Ok(bytes) => content_hash(&bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err("File was deleted on disk since it was opened".to_string())
+        }
+        Err(e) => return Err(format!("Failed to read {}: {e}", target.display())),
+    };
+    match current == expected {
+        true => Ok(()),
+        false => Err("File changed on disk since it was opened".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_detects_external_edits() {
+        let temp = std::env::temp_dir().join(format!("central_hash_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "hello").unwrap();
+
+        let opened = hash_file(temp.to_string_lossy().to_string(), "a.txt".to_string()).unwrap();
+        assert_eq!(opened.size, 5);
+        assert!(check_unchanged(&temp.join("a.txt"), &opened.hash).is_ok());
+
+        std::fs::write(temp.join("a.txt"), "hello, world").unwrap();
+        let err = check_unchanged(&temp.join("a.txt"), &opened.hash).unwrap_err();
+        assert!(err.contains("changed on disk"));
+
+        std::fs::remove_file(temp.join("a.txt")).unwrap();
+        assert!(check_unchanged(&temp.join("a.txt"), &opened.hash).is_err());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
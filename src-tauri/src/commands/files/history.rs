@@ -0,0 +1,177 @@
+use std::path::{Path, PathBuf};
+
+use git2::{Commit, Delta, DiffFindOptions, Repository, Sort, Tree};
+
+use super::git_helpers::{open_repo, project_prefix};
+use super::git_info::SHORT_HASH_LEN;
+use super::types::FileCommit;
+
+/// Commits returned when no limit is given
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// How a commit touched the file being followed
+enum Touch {
+    Changed,
+    RenamedFrom(PathBuf),
+}
+
+/// Commits that changed a project file, newest first, like `git log --follow`:
+/// with `follow` (the default) history continues under the file's old name
+/// across renames. Merges are compared against their first parent only.
+#[tauri::command]
+pub fn git_file_history(
+    project_path: String,
+    file_path: String,
+    limit: Option<usize>,
+    follow: Option<bool>,
+) -> Result<Vec<FileCommit>, String> {
+    let repo = open_repo(&project_path)?;
+    let mut path = project_prefix(&repo, Path::new(&project_path)).join(&file_path);
+    let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT);
+    let follow = follow.unwrap_or(true);
+
+    let mut walk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to walk history: {e}"))?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| format!("Failed to walk history: {e}"))?;
+    if walk.push_head().is_err() {
+        // Unborn HEAD: no commits yet
+        return Ok(vec![]);
+    }
+
+    let mut history = Vec::new();
+    for oid in walk {
+        if history.len() >= limit {
+            break;
+        }
+        let oid = oid.map_err(|e| format!("Failed to walk history: {e}"))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("Failed to read commit {oid}: {e}"))?;
+        let Some(touch) = touch(&repo, &commit, &path, follow)? else {
+            continue;
+        };
+        history.push(file_commit(&commit, &path));
+        if let Touch::RenamedFrom(old) = touch {
+            path = old;
+        }
+    }
+    Ok(history)
+}
+
+fn touch(
+    repo: &Repository,
+    commit: &Commit,
+    path: &Path,
+    follow: bool,
+) -> Result<Option<Touch>, String> {
+    let tree = commit
+        .tree()
+        .map_err(|e| format!("Failed to read commit tree: {e}"))?;
+    let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+    let current = tree.get_path(path).ok().map(|e| e.id());
+    let previous = parent_tree
+        .as_ref()
+        .and_then(|t| t.get_path(path).ok())
+        .map(|e| e.id());
+
+    if current == previous {
+        return Ok(None);
+    }
+    if follow && current.is_some() && previous.is_none() {
+        if let Some(old) = renamed_from(repo, parent_tree.as_ref(), &tree, path)? {
+            return Ok(Some(Touch::RenamedFrom(old)));
+        }
+    }
+    Ok(Some(Touch::Changed))
+}
+
+/// The path `path` was renamed from between the two trees, if it was
+fn renamed_from(
+    repo: &Repository,
+    old: Option<&Tree>,
+    new: &Tree,
+    path: &Path,
+) -> Result<Option<PathBuf>, String> {
+    let Some(old) = old else {
+        return Ok(None);
+    };
+    let mut diff = repo
+        .diff_tree_to_tree(Some(old), Some(new), None)
+        .map_err(|e| format!("Failed to diff commit: {e}"))?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("Failed to detect renames: {e}"))?;
+
+    let old_path = diff
+        .deltas()
+        .filter(|d| d.status() == Delta::Renamed && d.new_file().path() == Some(path))
+        .find_map(|d| d.old_file().path().map(Path::to_path_buf));
+    Ok(old_path)
+}
+
+fn file_commit(commit: &Commit, path: &Path) -> FileCommit {
+    let hash = commit.id().to_string();
+    FileCommit {
+        short_hash: hash.chars().take(SHORT_HASH_LEN).collect(),
+        hash,
+        summary: commit.summary().map(String::from),
+        author: commit.author().name().map(String::from),
+        timestamp: commit.time().seconds(),
+        path: path.to_string_lossy().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn history_follows_renames() {
+        let temp = std::env::temp_dir().join(format!("central_history_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        let body: String = (0..20).map(|i| format!("line {i}\n")).collect();
+
+        std::fs::write(temp.join("old.rs"), &body).unwrap();
+        commit_all(&repo, "add old");
+        std::fs::write(temp.join("other.rs"), "x\n").unwrap();
+        commit_all(&repo, "unrelated");
+        std::fs::remove_file(temp.join("old.rs")).unwrap();
+        std::fs::write(temp.join("new.rs"), &body).unwrap();
+        commit_all(&repo, "rename");
+        std::fs::write(temp.join("new.rs"), format!("{body}more\n")).unwrap();
+        commit_all(&repo, "edit");
+
+        let path = temp.to_string_lossy().to_string();
+        let history = git_file_history(path.clone(), "new.rs".to_string(), None, None).unwrap();
+        let summaries: Vec<&str> = history
+            .iter()
+            .filter_map(|c| c.summary.as_deref())
+            .collect();
+        assert_eq!(summaries, vec!["edit", "rename", "add old"]);
+        assert_eq!(history[2].path, "old.rs");
+
+        let unfollowed =
+            git_file_history(path, "new.rs".to_string(), Some(10), Some(false)).unwrap();
+        assert_eq!(unfollowed.len(), 2);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::debug_log;
+use crate::project_index::{build_index, query_index, IndexMatch, ProjectIndexHandle};
+
+const DEFAULT_QUERY_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize)]
+pub struct IndexSummary {
+    pub files: usize,
+    pub symbols: usize,
+    pub built_at_ms: u64,
+}
+
+/// Walk the project once and cache its file/symbol index. Rebuilds if already cached.
+#[tauri::command]
+pub async fn build_project_index(
+    project_path: String,
+    index: State<'_, ProjectIndexHandle>,
+) -> Result<IndexSummary, String> {
+    let built = build_index(&project_path)?;
+    let summary = IndexSummary {
+        files: built.files.len(),
+        symbols: built.symbols.len(),
+        built_at_ms: built.built_at_ms,
+    };
+    debug_log::log(
+        "INDEX",
+        &format!(
+            "{project_path}: {} files, {} symbols",
+            summary.files, summary.symbols
+        ),
+    );
+
+    index
+        .lock()
+        .map_err(|e| format!("Project index lock error: {e}"))?
+        .insert(project_path, built);
+    Ok(summary)
+}
+
+/// Fuzzy-find files and symbols. Builds the index first if it is missing or was
+/// invalidated by file changes.
+#[tauri::command]
+pub async fn query_project_index(
+    project_path: String,
+    query: String,
+    limit: Option<usize>,
+    index: State<'_, ProjectIndexHandle>,
+) -> Result<Vec<IndexMatch>, String> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+    let mut indexes = index
+        .lock()
+        .map_err(|e| format!("Project index lock error: {e}"))?;
+
+    if !indexes.contains_key(&project_path) {
+        let built = build_index(&project_path)?;
+        indexes.insert(project_path.clone(), built);
+    }
+
+    Ok(indexes
+        .get(&project_path)
+        .map(|built| query_index(built, &query, limit))
+        .unwrap_or_default())
+}
@@ -0,0 +1,164 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use super::list::walk_files_limited;
+use super::types::{LanguageShare, ProjectLanguages};
+
+/// Files looked at before giving up on the rest of the tree
+const MAX_SAMPLED_FILES: usize = 5000;
+
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("js", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("py", "Python"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("swift", "Swift"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("scala", "Scala"),
+    ("dart", "Dart"),
+    ("lua", "Lua"),
+    ("zig", "Zig"),
+    ("sh", "Shell"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+];
+
+/// Marker file names and the build tool they imply
+const BUILD_TOOLS: &[(&str, &str)] = &[
+    ("Cargo.toml", "cargo"),
+    ("package.json", "npm"),
+    ("pnpm-lock.yaml", "pnpm"),
+    ("yarn.lock", "yarn"),
+    ("bun.lockb", "bun"),
+    ("deno.json", "deno"),
+    ("go.mod", "go"),
+    ("pyproject.toml", "pyproject"),
+    ("requirements.txt", "pip"),
+    ("Gemfile", "bundler"),
+    ("pom.xml", "maven"),
+    ("build.gradle", "gradle"),
+    ("build.gradle.kts", "gradle"),
+    ("CMakeLists.txt", "cmake"),
+    ("Makefile", "make"),
+    ("Package.swift", "swiftpm"),
+    ("composer.json", "composer"),
+    ("mix.exs", "mix"),
+];
+
+/// Rank the project's languages by file count from a capped, ignore-aware
+/// sample of the tree, and list the build tools whose marker files appear
+/// anywhere in that sample (so monorepo packages count too).
+#[tauri::command]
+pub async fn detect_project_language(project_path: String) -> Result<ProjectLanguages, String> {
+    let root = Path::new(&project_path);
+    if !root.is_dir() {
+        return Err(format!("Path does not exist: {project_path}"));
+    }
+    Ok(detect(root))
+}
+
+fn detect(root: &Path) -> ProjectLanguages {
+    let files = walk_files_limited(root, false, MAX_SAMPLED_FILES);
+    let names: Vec<&str> = files
+        .iter()
+        .filter_map(|(path, _)| path.file_name()?.to_str())
+        .collect();
+
+    let build_tools: BTreeSet<&str> = names
+        .iter()
+        .filter_map(|name| BUILD_TOOLS.iter().find(|(marker, _)| marker == name))
+        .map(|(_, tool)| *tool)
+        .collect();
+
+    ProjectLanguages {
+        languages: rank_languages(&names),
+        build_tools: build_tools.into_iter().map(String::from).collect(),
+        sampled_files: files.len(),
+        truncated: files.len() >= MAX_SAMPLED_FILES,
+    }
+}
+
+fn rank_languages(names: &[&str]) -> Vec<LanguageShare> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in names {
+        let ext = Path::new(name).extension().and_then(|e| e.to_str());
+        if let Some((_, language)) =
+            ext.and_then(|e| LANGUAGES.iter().find(|(known, _)| *known == e))
+        {
+            *counts.entry(language).or_default() += 1;
+        }
+    }
+
+    let total: usize = counts.values().sum();
+    let mut ranked: Vec<LanguageShare> = counts
+        .into_iter()
+        .map(|(language, files)| LanguageShare {
+            language: language.to_string(),
+            files,
+            percent: (files as f64 * 1000.0 / total as f64).round() / 10.0,
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.files
+            .cmp(&a.files)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_languages_and_finds_nested_build_tools() {
+        let temp = std::env::temp_dir().join(format!("central_language_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        std::fs::create_dir_all(temp.join("web/node_modules/dep")).unwrap();
+        for name in [
+            "src/main.rs",
+            "src/lib.rs",
+            "src/util.rs",
+            "web/app.ts",
+            "Cargo.toml",
+            "web/package.json",
+            "README.md",
+        ] {
+            std::fs::write(temp.join(name), "").unwrap();
+        }
+        std::fs::write(temp.join("web/node_modules/dep/index.js"), "").unwrap();
+
+        let report = detect(&temp);
+
+        assert_eq!(
+            report.languages[0],
+            LanguageShare {
+                language: "Rust".to_string(),
+                files: 3,
+                percent: 75.0
+            }
+        );
+        assert_eq!(report.languages[1].language, "TypeScript");
+        assert_eq!(report.languages.len(), 2);
+        assert_eq!(report.build_tools, vec!["cargo", "npm"]);
+        assert!(!report.truncated);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
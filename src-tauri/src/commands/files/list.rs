@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use super::git_helpers::{open_repo, project_prefix};
+use super::skip::should_skip;
+
+/// Every file in the project as a flat, sorted list of relative paths — cheaper
+/// than the nested tree for pickers and prompts. Build dirs and git-ignored
+/// paths are left out unless `include_ignored`; `.git` itself never is listed.
+#[tauri::command]
+pub async fn list_project_files(
+    project_path: String,
+    include_ignored: bool,
+) -> Result<Vec<String>, String> {
+    project_files(&project_path, include_ignored)
+}
+
+fn project_files(project_path: &str, include_ignored: bool) -> Result<Vec<String>, String> {
+    let root = Path::new(project_path);
+    if !root.is_dir() {
+        return Err(format!("Path does not exist: {project_path}"));
+    }
+
+    let mut files: Vec<String> = walk_files(root, include_ignored)
+        .into_iter()
+        .map(|(_, rel)| rel)
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Absolute and project-relative path of each file under `root`, unordered.
+/// Unreadable directories are skipped rather than failing the walk.
+pub(crate) fn walk_files(root: &Path, include_ignored: bool) -> Vec<(PathBuf, String)> {
+    walk_files_limited(root, include_ignored, usize::MAX)
+}
+
+/// `walk_files`, stopping once `limit` files have been found
+pub(crate) fn walk_files_limited(
+    root: &Path,
+    include_ignored: bool,
+    limit: usize,
+) -> Vec<(PathBuf, String)> {
+    // Ignore rules take repo-root-relative paths
+    let repo = match include_ignored {
+        true => None,
+        false => open_repo(&root.to_string_lossy()).ok(),
+    };
+    let prefix = repo
+        .as_ref()
+        .map(|r| project_prefix(r, root))
+        .unwrap_or_default();
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(read) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for item in read.flatten() {
+            let name = item.file_name().to_string_lossy().to_string();
+            if name == ".git" || (!include_ignored && should_skip(&name)) {
+                continue;
+            }
+            let path = item.path();
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            if repo
+                .as_ref()
+                .is_some_and(|r| r.is_path_ignored(prefix.join(rel)).unwrap_or(false))
+            {
+                continue;
+            }
+
+            let rel = rel.to_string_lossy().to_string();
+            match item.file_type() {
+                Ok(t) if t.is_dir() => stack.push(path),
+                Ok(t) if t.is_file() => files.push((path, rel)),
+                _ => {}
+            }
+            if files.len() >= limit {
+                return files;
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn lists_files_respecting_ignore_rules_unless_asked() {
+        let temp =
+            std::env::temp_dir().join(format!("central_list_files_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        std::fs::create_dir_all(temp.join("node_modules/pkg")).unwrap();
+        Repository::init(&temp).unwrap();
+        std::fs::write(temp.join(".gitignore"), "*.log\n").unwrap();
+        std::fs::write(temp.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(temp.join("debug.log"), "noise").unwrap();
+        std::fs::write(temp.join("node_modules/pkg/index.js"), "").unwrap();
+
+        let list = |include| project_files(&temp.to_string_lossy(), include).unwrap();
+
+        assert_eq!(list(false), vec![".gitignore", "src/main.rs"]);
+        assert_eq!(
+            list(true),
+            vec![
+                ".gitignore",
+                "debug.log",
+                "node_modules/pkg/index.js",
+                "src/main.rs"
+            ]
+        );
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
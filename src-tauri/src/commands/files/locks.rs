@@ -0,0 +1,48 @@
+use tauri::State;
+
+use crate::debug_log;
+use crate::file_locks::{FileLock, FileLockHandle};
+
+#[tauri::command]
+pub fn acquire_file_lock(
+    project_path: String,
+    file_path: String,
+    owner: String,
+    locks: State<'_, FileLockHandle>,
+) -> Result<FileLock, String> {
+    let mut registry = locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?;
+
+    let lock = registry.acquire(&project_path, &file_path, &owner)?;
+    debug_log::log("LOCKS", &format!("acquired {file_path} for {owner}"));
+    Ok(lock)
+}
+
+#[tauri::command]
+pub fn release_file_lock(
+    project_path: String,
+    file_path: String,
+    owner: String,
+    locks: State<'_, FileLockHandle>,
+) -> Result<(), String> {
+    let mut registry = locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?;
+
+    registry.release(&project_path, &file_path, &owner)?;
+    debug_log::log("LOCKS", &format!("released {file_path} for {owner}"));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_file_locks(
+    project_path: Option<String>,
+    locks: State<'_, FileLockHandle>,
+) -> Result<Vec<FileLock>, String> {
+    let registry = locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?;
+
+    Ok(registry.list(project_path.as_deref()))
+}
@@ -1,6 +1,33 @@
+pub mod binary;
+pub mod clean;
+pub mod content;
 pub mod diff;
+pub mod diff_stats;
+pub mod dirty;
 pub mod discover;
-mod git_helpers;
+pub mod disk;
+pub mod env_vars;
+pub mod format;
+pub(crate) mod fs_helpers;
+pub(crate) mod git_helpers;
+pub mod git_identity;
+pub mod git_info;
+pub mod hash;
+pub mod history;
+pub mod index;
+pub mod language;
+pub mod list;
+pub mod locks;
+mod pool;
+pub mod renames;
+pub mod repo_path;
+pub mod repo_state;
+pub mod size;
+pub mod skip;
+pub mod staged;
 pub mod status;
+pub mod tail;
+pub mod transaction;
 pub mod tree;
 pub mod types;
+pub mod watch;
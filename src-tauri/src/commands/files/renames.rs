@@ -0,0 +1,112 @@
+use git2::{Delta, Diff, DiffFindOptions, DiffOptions, Patch, Repository};
+
+use super::git_helpers::open_repo;
+use super::types::RenameEntry;
+
+/// Just the moves between HEAD and the working tree (staged or not), without
+/// any hunks. Paths are relative to the repo root, like `get_diff`'s.
+#[tauri::command]
+pub fn get_renames(project_path: String) -> Result<Vec<RenameEntry>, String> {
+    let repo = open_repo(&project_path)?;
+
+    // Untracked files have to be in the diff to be matched as rename targets
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let mut diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))
+        .map_err(|e| format!("Failed to get diff: {e}"))?;
+
+    let mut find = DiffFindOptions::new();
+    find.renames(true).for_untracked(true);
+    diff.find_similar(Some(&mut find))
+        .map_err(|e| format!("Failed to detect renames: {e}"))?;
+
+    Ok(collect_renames(&repo, &diff))
+}
+
+fn collect_renames(repo: &Repository, diff: &Diff) -> Vec<RenameEntry> {
+    (0..diff.deltas().len())
+        .filter_map(|idx| {
+            let delta = diff.get_delta(idx)?;
+            if delta.status() != Delta::Renamed {
+                return None;
+            }
+            let path = |f: git2::DiffFile| f.path().map(|p| p.to_string_lossy().to_string());
+            Some(RenameEntry {
+                old_path: path(delta.old_file())?,
+                new_path: path(delta.new_file())?,
+                similarity: similarity(repo, diff, idx),
+            })
+        })
+        .collect()
+}
+
+/// Line-based estimate of how much of the file survived the move. git2
+/// doesn't expose libgit2's own score, so this uses the patch's line counts.
+fn similarity(repo: &Repository, diff: &Diff, idx: usize) -> u8 {
+    let Some(delta) = diff.get_delta(idx) else {
+        return 0;
+    };
+    if delta.old_file().id() == delta.new_file().id() {
+        return 100;
+    }
+    let old_lines = repo
+        .find_blob(delta.old_file().id())
+        .map(|blob| line_count(blob.content()))
+        .unwrap_or(0);
+    let Ok(Some(patch)) = Patch::from_diff(diff, idx) else {
+        return 0;
+    };
+    let Ok((_, additions, deletions)) = patch.line_stats() else {
+        return 0;
+    };
+    let new_lines = (old_lines + additions).saturating_sub(deletions);
+    let total = old_lines.max(new_lines);
+    if total == 0 {
+        return 100;
+    }
+    let changed = additions.max(deletions).min(total);
+    (100 * (total - changed) / total) as u8
+}
+
+fn line_count(content: &[u8]) -> usize {
+    let newlines = content.iter().filter(|&&b| b == b'\n').count();
+    match content.last() {
+        Some(b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::path::Path;
+
+    #[test]
+    fn reports_moved_files_with_similarity() {
+        let temp = std::env::temp_dir().join(format!("central_renames_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        let body: String = (0..20).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(temp.join("old.txt"), &body).unwrap();
+        std::fs::write(temp.join("keep.txt"), "unchanged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("old.txt")).unwrap();
+        index.add_path(Path::new("keep.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        std::fs::remove_file(temp.join("old.txt")).unwrap();
+        std::fs::write(temp.join("new.txt"), format!("{body}extra\n")).unwrap();
+
+        let renames = get_renames(temp.to_string_lossy().to_string()).unwrap();
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames[0].old_path, "old.txt");
+        assert_eq!(renames[0].new_path, "new.txt");
+        assert_eq!(renames[0].similarity, 95);
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::{Component, Path};
+
+use super::git_helpers::{open_repo, project_prefix};
+
+/// Map a project-relative path to the repo-relative form git commands and
+/// statuses use. They differ when the project is a subdirectory of its repo
+/// ("src/main.rs" in project `app/` is "app/src/main.rs" to git). The file
+/// needn't exist, so deleted files map too. Separators are always `/`.
+#[tauri::command]
+pub fn repo_relative_path(project_path: String, file_path: String) -> Result<String, String> {
+    let rel = Path::new(&file_path);
+    let escapes = rel
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if escapes {
+        return Err(format!("Path must stay inside the project: {file_path}"));
+    }
+
+    let repo = open_repo(&project_path)?;
+    let full = project_prefix(&repo, Path::new(&project_path)).join(rel);
+    let parts: Vec<String> = full
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn prefixes_paths_for_subdirectory_projects() {
+        let temp = std::env::temp_dir().join(format!("central_repo_path_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("app/src")).unwrap();
+        Repository::init(&temp).unwrap();
+        let root = temp.to_string_lossy().to_string();
+        let app = temp.join("app").to_string_lossy().to_string();
+
+        let path =
+            |project: &str, file: &str| repo_relative_path(project.to_string(), file.to_string());
+        assert_eq!(path(&app, "src/main.rs").unwrap(), "app/src/main.rs");
+        assert_eq!(path(&app, "./gone.rs").unwrap(), "app/gone.rs");
+        assert_eq!(path(&root, "README.md").unwrap(), "README.md");
+        assert!(path(&app, "../secrets").is_err());
+        assert!(path(&app, "/etc/passwd").is_err());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use git2::{Repository, RepositoryState};
+
+use super::git_helpers::open_repo;
+use super::types::OperationState;
+
+/// Whether the repo is mid-merge, mid-rebase, etc. (e.g. started from a
+/// terminal), and which files still have conflicts. Paths are relative to
+/// the repo root.
+#[tauri::command]
+pub fn git_operation_state(project_path: String) -> Result<OperationState, String> {
+    let repo = open_repo(&project_path)?;
+    let state = repo.state();
+    Ok(OperationState {
+        state: state_name(state).to_string(),
+        in_progress: state != RepositoryState::Clean,
+        conflicted_files: conflicted_files(&repo)?,
+    })
+}
+
+fn state_name(state: RepositoryState) -> &'static str {
+    match state {
+        RepositoryState::Clean => "clean",
+        RepositoryState::Merge => "merge",
+        RepositoryState::Revert => "revert",
+        RepositoryState::RevertSequence => "revert_sequence",
+        RepositoryState::CherryPick => "cherry_pick",
+        RepositoryState::CherryPickSequence => "cherry_pick_sequence",
+        RepositoryState::Bisect => "bisect",
+        RepositoryState::Rebase => "rebase",
+        RepositoryState::RebaseInteractive => "rebase_interactive",
+        RepositoryState::RebaseMerge => "rebase_merge",
+        RepositoryState::ApplyMailbox => "apply_mailbox",
+        RepositoryState::ApplyMailboxOrRebase => "apply_mailbox_or_rebase",
+    }
+}
+
+/// Paths with unresolved conflict entries in the index
+fn conflicted_files(repo: &Repository) -> Result<Vec<String>, String> {
+    let index = repo
+        .index()
+        .map_err(|e| format!("Failed to read index: {e}"))?;
+    if !index.has_conflicts() {
+        return Ok(vec![]);
+    }
+    let conflicts = index
+        .conflicts()
+        .map_err(|e| format!("Failed to read conflicts: {e}"))?;
+
+    let mut paths = Vec::new();
+    for conflict in conflicts {
+        let conflict = conflict.map_err(|e| format!("Failed to read conflict: {e}"))?;
+        // A side is missing when the file was added or deleted on one branch only
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+
+    fn commit_file(
+        repo: &Repository,
+        content: &str,
+        parents: &[&git2::Commit],
+        branch: &str,
+    ) -> git2::Oid {
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder.insert("a.txt", blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let sig = Signature::new("test", "test@test.com", &Time::new(0, 0)).unwrap();
+        repo.commit(Some(branch), &sig, &sig, content, &tree, parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn reports_merge_with_conflicted_files() {
+        let temp =
+            std::env::temp_dir().join(format!("central_repo_state_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        let path = temp.to_string_lossy().to_string();
+
+        let base = repo
+            .find_commit(commit_file(&repo, "base\n", &[], "HEAD"))
+            .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let state = git_operation_state(path.clone()).unwrap();
+        assert_eq!(state.state, "clean");
+        assert!(!state.in_progress);
+
+        commit_file(&repo, "ours\n", &[&base], "HEAD");
+        let theirs = commit_file(&repo, "theirs\n", &[&base], "refs/heads/other");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let annotated = repo.find_annotated_commit(theirs).unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+
+        let state = git_operation_state(path).unwrap();
+        assert_eq!(state.state, "merge");
+        assert!(state.in_progress);
+        assert_eq!(state.conflicted_files, vec!["a.txt"]);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
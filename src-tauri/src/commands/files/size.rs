@@ -0,0 +1,72 @@
+use std::sync::atomic::AtomicBool;
+
+use tauri::State;
+
+use super::fs_helpers::{canonical_project, dir_usage, resolve_existing_in_project};
+use super::types::DirUsage;
+use crate::operations::{Operation, OperationHandle};
+
+/// Bytes and file count under a project directory ("node_modules: 412 MB"),
+/// so the UI can show what a cleanup would free. `rel_dir` must stay inside
+/// the project; an empty path measures the whole project. Pass a
+/// `request_id` to make it stoppable with `cancel_operation`.
+#[tauri::command]
+pub async fn get_dir_size(
+    operations: State<'_, OperationHandle>,
+    project_path: String,
+    rel_dir: String,
+    request_id: Option<String>,
+) -> Result<DirUsage, String> {
+    let op = Operation::begin(&operations, request_id)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        measure_dir(&project_path, &rel_dir, op.cancel_flag())
+    })
+    .await
+    .map_err(|e| format!("Dir size task failed: {e}"))?
+}
+
+fn measure_dir(project_path: &str, rel_dir: &str, cancel: &AtomicBool) -> Result<DirUsage, String> {
+    let project = canonical_project(project_path)?;
+    let dir = match rel_dir {
+        "" | "." => project,
+        rel => resolve_existing_in_project(&project, rel)?,
+    };
+    if !dir.is_dir() {
+        return Err(format!("Not a directory: {rel_dir}"));
+    }
+    dir_usage(&dir, cancel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn measures_subtree_and_honours_boundary_and_cancel() {
+        let temp = std::env::temp_dir().join(format!("central_dir_size_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("node_modules/pkg")).unwrap();
+        std::fs::write(temp.join("node_modules/a.js"), "12345").unwrap();
+        std::fs::write(temp.join("node_modules/pkg/b.js"), "123").unwrap();
+        std::fs::write(temp.join("top.txt"), "1").unwrap();
+        let path = temp.to_string_lossy().to_string();
+        let never = AtomicBool::new(false);
+
+        assert_eq!(
+            measure_dir(&path, "node_modules", &never).unwrap(),
+            DirUsage { bytes: 8, files: 2 }
+        );
+        assert_eq!(
+            measure_dir(&path, "", &never).unwrap(),
+            DirUsage { bytes: 9, files: 3 }
+        );
+        assert!(measure_dir(&path, "..", &never).is_err());
+        assert!(measure_dir(&path, "top.txt", &never).is_err());
+
+        let cancelled = AtomicBool::new(false);
+        cancelled.store(true, Ordering::Relaxed);
+        assert_eq!(measure_dir(&path, "", &cancelled).unwrap_err(), "Cancelled");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+/// Build output, dependency, and OS clutter directories hidden by default
+pub const DEFAULT_SKIP: &[&str] = &[
+    "node_modules",
+    "target",
+    ".DS_Store",
+    "__pycache__",
+    ".next",
+    "dist",
+    ".turbo",
+];
+
+/// Which directory entries a listing leaves out. `.git` is always skipped;
+/// dotfiles and dotdirs only appear with `show_hidden`, and `names` (the
+/// built-in `DEFAULT_SKIP` unless overridden) is skipped either way.
+///
+/// These rules are name-based and independent of .gitignore: ignored files
+/// that aren't named here are still listed.
+#[derive(Debug, Clone)]
+pub struct SkipRules {
+    pub show_hidden: bool,
+    pub names: Vec<String>,
+}
+
+impl Default for SkipRules {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl SkipRules {
+    pub fn new(show_hidden: Option<bool>, names: Option<Vec<String>>) -> Self {
+        Self {
+            show_hidden: show_hidden.unwrap_or(false),
+            names: names.unwrap_or_else(|| DEFAULT_SKIP.iter().map(|n| n.to_string()).collect()),
+        }
+    }
+
+    pub fn skips(&self, name: &str) -> bool {
+        name == ".git"
+            || (!self.show_hidden && name.starts_with('.'))
+            || self.names.iter().any(|n| n == name)
+    }
+}
+
+/// The built-in skip list plus `.git`, for walkers that always show dotfiles
+/// (the watcher and the project file index)
+pub(crate) fn should_skip(name: &str) -> bool {
+    name == ".git" || DEFAULT_SKIP.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_entries_follow_show_hidden_and_overrides() {
+        let default = SkipRules::default();
+        assert!(default.skips(".github"));
+        assert!(default.skips("node_modules"));
+        assert!(!default.skips("src"));
+
+        let hidden = SkipRules::new(Some(true), None);
+        assert!(!hidden.skips(".github"));
+        assert!(hidden.skips(".git"));
+        assert!(hidden.skips(".next"));
+
+        let custom = SkipRules::new(Some(true), Some(vec!["vendor".to_string()]));
+        assert!(custom.skips("vendor"));
+        assert!(!custom.skips("node_modules"));
+        assert!(custom.skips(".git"));
+    }
+
+    #[test]
+    fn should_skip_git_directory() {
+        assert!(should_skip(".git"));
+    }
+
+    #[test]
+    fn should_skip_node_modules() {
+        assert!(should_skip("node_modules"));
+    }
+
+    #[test]
+    fn should_skip_target() {
+        assert!(should_skip("target"));
+    }
+
+    #[test]
+    fn should_skip_ds_store() {
+        assert!(should_skip(".DS_Store"));
+    }
+
+    #[test]
+    fn should_skip_pycache() {
+        assert!(should_skip("__pycache__"));
+    }
+
+    #[test]
+    fn should_skip_next() {
+        assert!(should_skip(".next"));
+    }
+
+    #[test]
+    fn should_skip_dist() {
+        assert!(should_skip("dist"));
+    }
+
+    #[test]
+    fn should_skip_turbo() {
+        assert!(should_skip(".turbo"));
+    }
+
+    #[test]
+    fn should_not_skip_src() {
+        assert!(!should_skip("src"));
+    }
+
+    #[test]
+    fn should_not_skip_regular_files() {
+        assert!(!should_skip("main.rs"));
+        assert!(!should_skip("package.json"));
+        assert!(!should_skip("README.md"));
+    }
+}
@@ -0,0 +1,74 @@
+use git2::DiffOptions;
+
+use super::diff::{apply_diff_algorithm, collect_diff_output};
+use super::git_helpers::{head_commit, open_repo};
+use super::types::FileDiff;
+
+/// HEAD-to-index diff: exactly what the next commit will contain, ignoring
+/// unstaged edits. Before the first commit everything staged shows as added.
+/// Paths are relative to the repo root, like `get_diff`'s.
+#[tauri::command]
+pub fn get_staged_diff(
+    project_path: String,
+    diff_algorithm: Option<String>,
+) -> Result<Vec<FileDiff>, String> {
+    let repo = open_repo(&project_path)?;
+    let head_tree = head_commit(&repo)?
+        .map(|commit| commit.tree())
+        .transpose()
+        .map_err(|e| format!("Failed to read HEAD tree: {e}"))?;
+
+    let mut opts = DiffOptions::new();
+    apply_diff_algorithm(&mut opts, diff_algorithm.as_deref())?;
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))
+        .map_err(|e| format!("Failed to get staged diff: {e}"))?;
+
+    collect_diff_output(&diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use std::path::Path;
+
+    #[test]
+    fn staged_diff_ignores_unstaged_edits() {
+        let temp = std::env::temp_dir().join(format!("central_staged_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let repo = Repository::init(&temp).unwrap();
+        let path = temp.to_string_lossy().to_string();
+        std::fs::write(temp.join("a.txt"), "one\n").unwrap();
+        std::fs::write(temp.join("b.txt"), "one\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+
+        // Unborn HEAD: the staged file is all additions
+        let diffs = get_staged_diff(path.clone(), None).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "a.txt");
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        std::fs::write(temp.join("a.txt"), "two\n").unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(temp.join("a.txt"), "three\n").unwrap();
+
+        let diffs = get_staged_diff(path, None).unwrap();
+        assert_eq!(diffs.len(), 1);
+        let added: Vec<&str> = diffs[0].hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.origin == "add")
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(added, vec!["two\n"]);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -1,18 +1,39 @@
-use git2::Repository;
-use std::path::Path;
+use std::collections::HashMap;
+use tauri::State;
 
+use super::fs_helpers::{
+    atomic_write, canonical_project, read_text_file, resolve_existing_in_project,
+    resolve_for_write,
+};
+use super::hash::check_unchanged;
 use super::git_helpers::{
-    get_ahead_behind, get_branch_name, get_changed_files,
+    get_ahead_behind, get_branch_name, get_changed_files, open_repo,
 };
-use super::types::GitStatusInfo;
+use super::pool::{map_bounded, MAX_WORKERS};
+use super::types::{GitStatusInfo, GitStatusResult};
+use crate::file_locks::FileLockHandle;
 
 #[tauri::command]
 pub fn get_git_status(
     project_path: String,
 ) -> Result<GitStatusInfo, String> {
-    let root = Path::new(&project_path);
-    let repo = Repository::open(root)
-        .map_err(|e| format!("Not a git repository: {e}"))?;
+    git_status_for(&project_path)
+}
+
+/// Status for several repos in one round trip, computed concurrently.
+/// Non-repos and other failures are reported per path.
+#[tauri::command]
+pub async fn get_multi_git_status(
+    project_paths: Vec<String>,
+) -> Result<HashMap<String, GitStatusResult>, String> {
+    let results = map_bounded(project_paths.clone(), MAX_WORKERS, |path| {
+        GitStatusResult::from(git_status_for(path))
+    });
+    Ok(project_paths.into_iter().zip(results).collect())
+}
+
+fn git_status_for(project_path: &str) -> Result<GitStatusInfo, String> {
+    let repo = open_repo(project_path)?;
 
     let branch = get_branch_name(&repo);
     let (ahead, behind) = get_ahead_behind(&repo);
@@ -27,57 +48,69 @@ pub fn get_git_status(
     })
 }
 
+impl From<Result<GitStatusInfo, String>> for GitStatusResult {
+    fn from(result: Result<GitStatusInfo, String>) -> Self {
+        match result {
+            Ok(status) => Self {
+                status: Some(status),
+                error: None,
+            },
+            Err(error) => Self {
+                status: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
 #[tauri::command]
 pub fn get_file_content(
     project_path: String,
     file_path: String,
 ) -> Result<String, String> {
-    let full = Path::new(&project_path).join(&file_path);
-
-    if !full.exists() {
-        return Err(format!("File not found: {file_path}"));
-    }
-
-    std::fs::read_to_string(&full)
-        .map_err(|e| format!("Failed to read file: {e}"))
+    let project = canonical_project(&project_path)?;
+    let full = resolve_existing_in_project(&project, &file_path)?;
+    read_text_file(&full)
 }
 
+/// Write a file, rejecting the write if another owner holds its advisory lock.
+/// With `expected_hash` (from `hash_file`), the write is also rejected if the
+/// file on disk no longer matches it.
 #[tauri::command]
 pub fn write_file(
     project_path: String,
     file_path: String,
     content: String,
+    owner: Option<String>,
+    expected_hash: Option<String>,
+    locks: State<'_, FileLockHandle>,
 ) -> Result<(), String> {
-    let full = Path::new(&project_path).join(&file_path);
-
-    // Refuse to write outside the project directory
-    let canonical_project = Path::new(&project_path)
-        .canonicalize()
-        .map_err(|e| format!("Invalid project path: {e}"))?;
-    let parent = full
-        .parent()
-        .ok_or_else(|| "Invalid file path".to_string())?;
-    // Ensure parent directory exists before canonicalizing
-    if !parent.exists() {
-        return Err(format!("Parent directory does not exist: {}", parent.display()));
-    }
-    let canonical_full = full
-        .parent()
-        .unwrap()
-        .canonicalize()
-        .map_err(|e| format!("Invalid path: {e}"))?
-        .join(full.file_name().ok_or("Invalid file name")?);
-    if !canonical_full.starts_with(&canonical_project) {
-        return Err("Cannot write outside project directory".to_string());
-    }
+    locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?
+        .check_write(&project_path, &file_path, owner.as_deref())?;
+
+    write_project_file(project_path, file_path, content, expected_hash)
+}
 
-    std::fs::write(&full, content)
-        .map_err(|e| format!("Failed to write file: {e}"))
+fn write_project_file(
+    project_path: String,
+    file_path: String,
+    content: String,
+    expected_hash: Option<String>,
+) -> Result<(), String> {
+    let target = resolve_for_write(&project_path, &file_path)?;
+    if let Some(expected) = expected_hash {
+        check_unchanged(&target, &expected)?;
+    }
+    atomic_write(&target, content.as_bytes())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use git2::Repository;
+    use std::path::Path;
 
     #[test]
     fn get_git_status_fails_for_non_repo() {
@@ -129,6 +162,39 @@ mod tests {
         std::fs::remove_dir_all(&temp).unwrap();
     }
 
+    #[test]
+    fn nested_subdirectory_uses_enclosing_repo() {
+        let temp = std::env::temp_dir().join(format!(
+            "central_status_nested_{}",
+            uuid::Uuid::new_v4()
+        ));
+        let nested = temp.join("packages/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        Repository::init(&temp).unwrap();
+        std::fs::write(nested.join("new.txt"), "hello").unwrap();
+        let nested_path = nested.to_string_lossy().to_string();
+
+        let status = get_git_status(nested_path.clone()).unwrap();
+        let paths: Vec<&str> = status.changed_files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["packages/app/new.txt"]);
+
+        // The tree stays project-relative, with statuses mapped onto it
+        let tree_with = |with_status| {
+            let never = std::sync::atomic::AtomicBool::new(false);
+            let options = crate::commands::files::tree::TreeOptions {
+                with_status,
+                ..Default::default()
+            };
+            crate::commands::files::tree::build_file_tree(&nested_path, &options, &never).unwrap()
+        };
+        let tree = tree_with(None);
+        assert_eq!(tree[0].path, "new.txt");
+        assert_eq!(tree[0].git_status.as_deref(), Some("added"));
+        assert_eq!(tree_with(Some(false))[0].git_status, None);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
     #[test]
     fn get_file_content_reads_file() {
         let temp = std::env::temp_dir().join(format!(
@@ -166,6 +232,20 @@ mod tests {
         std::fs::remove_dir_all(&temp).unwrap();
     }
 
+    #[test]
+    fn get_file_content_rejects_paths_outside_the_project() {
+        let temp = std::env::temp_dir().join(format!("central_content_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "inside").unwrap();
+        let read = |path: &str| get_file_content(temp.to_string_lossy().to_string(), path.into());
+
+        assert!(read("../../etc/passwd").unwrap_err().contains("outside project"));
+        assert!(read("/etc/passwd").unwrap_err().contains("outside project"));
+        assert_eq!(read("sub/../a.txt").unwrap_err(), "File not found: sub/../a.txt");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
     #[test]
     fn write_file_creates_and_writes() {
         let temp = std::env::temp_dir().join(format!(
@@ -175,10 +255,11 @@ mod tests {
         std::fs::create_dir_all(&temp).unwrap();
         std::fs::write(temp.join("test.txt"), "original").unwrap();
 
-        let result = write_file(
+        let result = write_project_file(
             temp.to_string_lossy().to_string(),
             "test.txt".to_string(),
             "updated content".to_string(),
+            None,
         );
         assert!(result.is_ok());
 
@@ -196,10 +277,11 @@ mod tests {
         ));
         std::fs::create_dir_all(&temp).unwrap();
 
-        let result = write_file(
+        let result = write_project_file(
             temp.to_string_lossy().to_string(),
             "../../../etc/passwd".to_string(),
             "malicious".to_string(),
+            None,
         );
         assert!(result.is_err());
 
@@ -0,0 +1,92 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use super::fs_helpers::{
+    canonical_project, looks_binary, resolve_existing_in_project, MAX_READ_BYTES,
+};
+
+/// How much is read from the end of the file per step
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Most lines one call will return
+const MAX_TAIL_LINES: usize = 10_000;
+
+/// The last `lines` lines of a project file (e.g. a build log), read backwards
+/// from the end so large files aren't loaded whole. At most `MAX_READ_BYTES`
+/// are read, so a single enormous line comes back truncated at the front.
+#[tauri::command]
+pub fn tail_file(project_path: String, file_path: String, lines: usize) -> Result<String, String> {
+    let project = canonical_project(&project_path)?;
+    let full = resolve_existing_in_project(&project, &file_path)?;
+    read_tail(&full, lines.min(MAX_TAIL_LINES))
+}
+
+fn read_tail(path: &Path, lines: usize) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to read file: {e}"))?;
+    let mut start = file
+        .metadata()
+        .map_err(|e| format!("Failed to read file: {e}"))?
+        .len();
+    let mut buf: Vec<u8> = Vec::new();
+
+    // One more newline than wanted guarantees the first kept line is whole
+    while start > 0 && (buf.len() as u64) < MAX_READ_BYTES && complete_lines(&buf) < lines {
+        let chunk = TAIL_CHUNK_BYTES.min(start);
+        start -= chunk;
+        let mut piece = vec![0; chunk as usize];
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.read_exact(&mut piece))
+            .map_err(|e| format!("Failed to read file: {e}"))?;
+        piece.extend_from_slice(&buf);
+        buf = piece;
+    }
+
+    if looks_binary(&buf) {
+        return Err("Binary file".to_string());
+    }
+    Ok(last_lines(&String::from_utf8_lossy(&buf), lines))
+}
+
+/// Newlines before the final line terminator
+fn complete_lines(buf: &[u8]) -> usize {
+    let body = buf.strip_suffix(b"\n").unwrap_or(buf);
+    body.iter().filter(|&&b| b == b'\n').count()
+}
+
+fn last_lines(text: &str, lines: usize) -> String {
+    if lines == 0 {
+        return String::new();
+    }
+    let (body, trailing) = match text.strip_suffix('\n') {
+        Some(body) => (body, "\n"),
+        None => (text, ""),
+    };
+    let mut kept: Vec<&str> = body.rsplit('\n').take(lines).collect();
+    kept.reverse();
+    format!("{}{trailing}", kept.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_reads_across_chunks_and_caps_lines() {
+        let temp = std::env::temp_dir().join(format!("central_tail_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        // ~190 KB, so the last lines span several chunks from the end
+        let log: String = (0..20_000).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(temp.join("build.log"), &log).unwrap();
+        let project = temp.to_string_lossy().to_string();
+        let tail = |n| tail_file(project.clone(), "build.log".to_string(), n).unwrap();
+
+        assert_eq!(tail(2), "line 19998\nline 19999\n");
+        assert_eq!(tail(0), "");
+        assert_eq!(tail(15_000).lines().count(), MAX_TAIL_LINES);
+        assert_eq!(tail(15_000).lines().next(), Some("line 10000"));
+        assert!(tail_file(project.clone(), "../outside.log".to_string(), 5).is_err());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+
+use tauri::State;
+
+use super::fs_helpers::{resolve_for_write, sibling_path, write_temp_sibling};
+use super::types::{FileWrite, FileWriteStatus, WriteFilesResult};
+use crate::file_locks::FileLockHandle;
+
+/// A file whose new content sits in a temp sibling, waiting to be renamed in
+struct Staged {
+    target: PathBuf,
+    tmp: PathBuf,
+    /// Where the original was moved while the batch commits
+    backup: Option<PathBuf>,
+}
+
+/// Write several files so that either all of them change or none do. Every
+/// path is lock- and boundary-checked first, then all contents are written to
+/// temp files, and only then renamed into place. If a rename fails, the files
+/// already replaced are restored from backups.
+#[tauri::command]
+pub fn write_files(
+    project_path: String,
+    files: Vec<FileWrite>,
+    owner: Option<String>,
+    locks: State<'_, FileLockHandle>,
+) -> Result<WriteFilesResult, String> {
+    let registry = locks
+        .lock()
+        .map_err(|e| format!("File lock registry error: {e}"))?;
+    let targets = resolve_targets(&project_path, &files, |path| {
+        registry.check_write(&project_path, path, owner.as_deref())
+    });
+    drop(registry);
+
+    Ok(write_batch(&files, targets))
+}
+
+fn resolve_targets(
+    project_path: &str,
+    files: &[FileWrite],
+    check: impl Fn(&str) -> Result<(), String>,
+) -> Vec<Result<PathBuf, String>> {
+    let mut seen = Vec::new();
+    files
+        .iter()
+        .map(|file| {
+            check(&file.path)?;
+            let target = resolve_for_write(project_path, &file.path)?;
+            if seen.contains(&target) {
+                return Err(format!("Duplicate path in batch: {}", file.path));
+            }
+            seen.push(target.clone());
+            Ok(target)
+        })
+        .collect()
+}
+
+fn write_batch(files: &[FileWrite], targets: Vec<Result<PathBuf, String>>) -> WriteFilesResult {
+    let errors: Vec<Option<String>> = targets.iter().map(|t| t.as_ref().err().cloned()).collect();
+    if errors.iter().any(Option::is_some) {
+        return outcome(files, &errors, false);
+    }
+    let targets: Vec<PathBuf> = targets.into_iter().flatten().collect();
+
+    let result = stage(files, &targets).and_then(|mut staged| commit(&mut staged));
+    match result {
+        Ok(()) => outcome(files, &vec![None; files.len()], true),
+        Err((idx, error)) => {
+            let mut errors = vec![None; files.len()];
+            errors[idx] = Some(error);
+            outcome(files, &errors, false)
+        }
+    }
+}
+
+/// Write every file's content to a temp sibling; on failure, discard them all
+fn stage(files: &[FileWrite], targets: &[PathBuf]) -> Result<Vec<Staged>, (usize, String)> {
+    let mut staged: Vec<Staged> = Vec::with_capacity(files.len());
+    for (idx, (file, target)) in files.iter().zip(targets).enumerate() {
+        match write_temp_sibling(target, file.content.as_bytes()) {
+            Ok(tmp) => staged.push(Staged {
+                target: target.clone(),
+                tmp,
+                backup: None,
+            }),
+            Err(e) => {
+                staged.iter().for_each(|s| {
+                    let _ = std::fs::remove_file(&s.tmp);
+                });
+                return Err((idx, e));
+            }
+        }
+    }
+    Ok(staged)
+}
+
+/// Rename every temp file into place, undoing the whole batch on the first failure
+fn commit(staged: &mut [Staged]) -> Result<(), (usize, String)> {
+    for idx in 0..staged.len() {
+        if let Err(e) = place(&mut staged[idx]) {
+            staged[..idx].iter().rev().for_each(restore);
+            staged[idx..].iter().for_each(|s| {
+                let _ = std::fs::remove_file(&s.tmp);
+            });
+            return Err((idx, e));
+        }
+    }
+    staged
+        .iter()
+        .filter_map(|s| s.backup.as_ref())
+        .for_each(|backup| {
+            let _ = std::fs::remove_file(backup);
+        });
+    Ok(())
+}
+
+/// Move the original aside, then the new content in. Leaves nothing changed on error.
+fn place(staged: &mut Staged) -> Result<(), String> {
+    if staged.target.exists() {
+        let backup = sibling_path(&staged.target, "bak")?;
+        std::fs::rename(&staged.target, &backup)
+            .map_err(|e| format!("Failed to back up {}: {e}", staged.target.display()))?;
+        staged.backup = Some(backup);
+    }
+    std::fs::rename(&staged.tmp, &staged.target).map_err(|e| {
+        restore(staged);
+        format!("Failed to write file: {e}")
+    })
+}
+
+/// Put a placed file back the way it was before the batch
+fn restore(staged: &Staged) {
+    let _ = std::fs::remove_file(&staged.target);
+    if let Some(backup) = &staged.backup {
+        let _ = std::fs::rename(backup, &staged.target);
+    }
+}
+
+fn outcome(files: &[FileWrite], errors: &[Option<String>], committed: bool) -> WriteFilesResult {
+    let status = |error: &Option<String>| match (committed, error) {
+        (true, _) => "written",
+        (false, Some(_)) => "failed",
+        (false, None) => "skipped",
+    };
+    WriteFilesResult {
+        committed,
+        files: files
+            .iter()
+            .zip(errors)
+            .map(|(file, error)| FileWriteStatus {
+                path: file.path.clone(),
+                status: status(error).to_string(),
+                error: error.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> FileWrite {
+        FileWrite {
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn batch_applies_fully_or_not_at_all() {
+        let temp =
+            std::env::temp_dir().join(format!("central_write_files_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("a.txt"), "old a").unwrap();
+        let project = temp.to_string_lossy().to_string();
+        let write =
+            |files: &[FileWrite]| write_batch(files, resolve_targets(&project, files, |_| Ok(())));
+
+        let rejected = write(&[file("a.txt", "new a"), file("../escape.txt", "x")]);
+        assert!(!rejected.committed);
+        assert_eq!(rejected.files[0].status, "skipped");
+        assert_eq!(rejected.files[1].status, "failed");
+        assert_eq!(
+            std::fs::read_to_string(temp.join("a.txt")).unwrap(),
+            "old a"
+        );
+
+        let written = write(&[file("a.txt", "new a"), file("b.txt", "new b")]);
+        assert!(written.committed);
+        assert_eq!(
+            std::fs::read_to_string(temp.join("a.txt")).unwrap(),
+            "new a"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.join("b.txt")).unwrap(),
+            "new b"
+        );
+
+        // A rename failing part-way restores the files already replaced
+        let a = temp.join("a.txt");
+        let mut staged = vec![
+            Staged {
+                target: a.clone(),
+                tmp: write_temp_sibling(&a, b"newer a").unwrap(),
+                backup: None,
+            },
+            Staged {
+                target: temp.join("c.txt"),
+                tmp: temp.join("missing-tmp"),
+                backup: None,
+            },
+        ];
+        assert_eq!(commit(&mut staged).unwrap_err().0, 1);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "new a");
+        assert!(!temp.join("c.txt").exists());
+        assert_eq!(std::fs::read_dir(&temp).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
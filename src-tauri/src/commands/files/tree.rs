@@ -1,275 +1,136 @@
-use git2::Repository;
 use std::collections::HashMap;
-use std::path::Path;
+use std::sync::atomic::AtomicBool;
 
-use super::git_helpers::collect_git_statuses;
+use serde::Deserialize;
+use tauri::State;
+
+use super::fs_helpers::resolve_existing_in_project;
+use super::skip::SkipRules;
 use super::types::FileTreeEntry;
+use crate::operations::{Operation, OperationHandle};
+use crate::project_path::resolve_project_path;
+
+mod status;
+mod walk;
+
+use status::project_statuses;
+use walk::{build_tree_recursive, TreeWalk};
+
+/// Directory levels expanded below the listed directory; also the ceiling
+/// for a caller-supplied `max_depth`
+const MAX_TREE_DEPTH: usize = 20;
+
+/// Optional listing controls for `get_file_tree`; every field may be omitted
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TreeOptions {
+    /// List this directory (relative to the project) instead of the root;
+    /// it must stay inside the project
+    pub dir_path: Option<String>,
+    /// Levels expanded below the listed directory (0 = direct entries only,
+    /// capped at `MAX_TREE_DEPTH`) so the UI can load the tree lazily
+    pub max_depth: Option<usize>,
+    /// Include dotfiles
+    pub show_hidden: Option<bool>,
+    /// Replaces the built-in skip list (see `SkipRules`)
+    pub skip_names: Option<Vec<String>>,
+    /// `false` skips git status collection (the slow part on huge repos)
+    /// and leaves every `git_status` as None
+    pub with_status: Option<bool>,
+}
 
+/// Build the file tree. Gitignored files are listed like any other. The walk
+/// runs off the async runtime; pass a `request_id` to make it stoppable with
+/// `cancel_operation`.
 #[tauri::command]
-pub fn get_file_tree(
+pub async fn get_file_tree(
+    operations: State<'_, OperationHandle>,
     project_path: String,
+    options: Option<TreeOptions>,
+    request_id: Option<String>,
 ) -> Result<Vec<FileTreeEntry>, String> {
-    let root = Path::new(&project_path);
-    if !root.exists() {
-        return Err(format!("Path does not exist: {project_path}"));
-    }
-
-    let statuses = match Repository::open(root) {
-        Ok(repo) => collect_git_statuses(&repo).unwrap_or_default(),
-        Err(_) => HashMap::new(),
-    };
-
-    build_tree_recursive(root, root, &statuses, 0)
+    let op = Operation::begin(&operations, request_id)?;
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        build_file_tree(&project_path, &options, op.cancel_flag())
+    })
+    .await
+    .map_err(|e| format!("File tree task failed: {e}"))?
 }
 
-fn build_tree_recursive(
-    dir: &Path,
-    root: &Path,
-    statuses: &HashMap<String, String>,
-    depth: usize,
+pub(super) fn build_file_tree(
+    project_path: &str,
+    options: &TreeOptions,
+    cancel: &AtomicBool,
 ) -> Result<Vec<FileTreeEntry>, String> {
-    if depth > 20 {
-        return Ok(vec![]);
-    }
-
-    let mut entries: Vec<FileTreeEntry> = Vec::new();
-    let read = std::fs::read_dir(dir)
-        .map_err(|e| format!("Failed to read dir: {e}"))?;
-
-    for item in read {
-        let item = item.map_err(|e| format!("Dir entry error: {e}"))?;
-        let name = item.file_name().to_string_lossy().to_string();
+    let root = resolve_project_path(project_path)?;
+    let root = root.as_path();
 
-        if should_skip(&name) {
-            continue;
-        }
-
-        let entry = build_entry(&item, root, statuses, depth)?;
-        entries.push(entry);
+    let start = match options.dir_path.as_deref() {
+        Some(rel) if !rel.is_empty() => resolve_existing_in_project(root, rel)?,
+        _ => root.to_path_buf(),
+    };
+    if !start.is_dir() {
+        return Err(format!("Not a directory: {}", start.display()));
     }
 
-    entries.sort_by(|a, b| {
-        b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name))
-    });
-
-    Ok(entries)
-}
-
-fn build_entry(
-    item: &std::fs::DirEntry,
-    root: &Path,
-    statuses: &HashMap<String, String>,
-    depth: usize,
-) -> Result<FileTreeEntry, String> {
-    let name = item.file_name().to_string_lossy().to_string();
-    let full_path = item.path();
-    let rel_path = full_path
-        .strip_prefix(root)
-        .unwrap_or(&full_path)
-        .to_string_lossy()
-        .to_string();
-
-    let is_dir = full_path.is_dir();
-    let git_status = statuses.get(&rel_path).cloned();
-
-    let children = if is_dir {
-        build_tree_recursive(&full_path, root, statuses, depth + 1)?
-    } else {
-        vec![]
+    let statuses = match options.with_status.unwrap_or(true) {
+        true => project_statuses(project_path, root),
+        false => HashMap::new(),
     };
 
-    let dir_status = if is_dir {
-        infer_dir_status(&children)
-    } else {
-        git_status
+    let walk = TreeWalk {
+        root,
+        statuses: &statuses,
+        max_depth: options
+            .max_depth
+            .map_or(MAX_TREE_DEPTH, |d| d.min(MAX_TREE_DEPTH)),
+        skip: SkipRules::new(options.show_hidden, options.skip_names.clone()),
+        cancel,
     };
-
-    Ok(FileTreeEntry {
-        name,
-        path: rel_path,
-        is_dir,
-        children,
-        git_status: dir_status,
-    })
-}
-
-fn should_skip(name: &str) -> bool {
-    matches!(
-        name,
-        ".git"
-            | "node_modules"
-            | "target"
-            | ".DS_Store"
-            | "__pycache__"
-            | ".next"
-            | "dist"
-            | ".turbo"
-    )
-}
-
-fn infer_dir_status(children: &[FileTreeEntry]) -> Option<String> {
-    let has_modified = children
-        .iter()
-        .any(|c| c.git_status.as_deref() == Some("modified"));
-    let has_added = children
-        .iter()
-        .any(|c| c.git_status.as_deref() == Some("added"));
-    let has_deleted = children
-        .iter()
-        .any(|c| c.git_status.as_deref() == Some("deleted"));
-
-    if has_modified {
-        Some("modified".to_string())
-    } else if has_added {
-        Some("added".to_string())
-    } else if has_deleted {
-        Some("deleted".to_string())
-    } else {
-        None
-    }
+    build_tree_recursive(&start, &walk, 0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn should_skip_git_directory() {
-        assert!(should_skip(".git"));
-    }
-
-    #[test]
-    fn should_skip_node_modules() {
-        assert!(should_skip("node_modules"));
-    }
-
-    #[test]
-    fn should_skip_target() {
-        assert!(should_skip("target"));
-    }
-
-    #[test]
-    fn should_skip_ds_store() {
-        assert!(should_skip(".DS_Store"));
-    }
-
-    #[test]
-    fn should_skip_pycache() {
-        assert!(should_skip("__pycache__"));
-    }
-
-    #[test]
-    fn should_skip_next() {
-        assert!(should_skip(".next"));
-    }
-
-    #[test]
-    fn should_skip_dist() {
-        assert!(should_skip("dist"));
-    }
-
-    #[test]
-    fn should_skip_turbo() {
-        assert!(should_skip(".turbo"));
-    }
-
-    #[test]
-    fn should_not_skip_src() {
-        assert!(!should_skip("src"));
-    }
-
-    #[test]
-    fn should_not_skip_regular_files() {
-        assert!(!should_skip("main.rs"));
-        assert!(!should_skip("package.json"));
-        assert!(!should_skip("README.md"));
+    /// The walk minus Tauri state, never cancelled
+    fn get_file_tree(
+        project_path: String,
+        options: TreeOptions,
+    ) -> Result<Vec<FileTreeEntry>, String> {
+        let never = AtomicBool::new(false);
+        build_file_tree(&project_path, &options, &never)
     }
 
-    #[test]
-    fn infer_dir_status_modified_takes_priority() {
-        let children = vec![
-            FileTreeEntry {
-                name: "a.rs".to_string(),
-                path: "a.rs".to_string(),
-                is_dir: false,
-                children: vec![],
-                git_status: Some("modified".to_string()),
-            },
-            FileTreeEntry {
-                name: "b.rs".to_string(),
-                path: "b.rs".to_string(),
-                is_dir: false,
-                children: vec![],
-                git_status: Some("added".to_string()),
-            },
-        ];
-
-        assert_eq!(infer_dir_status(&children), Some("modified".to_string()));
-    }
-
-    #[test]
-    fn infer_dir_status_added_when_no_modified() {
-        let children = vec![FileTreeEntry {
-            name: "new.rs".to_string(),
-            path: "new.rs".to_string(),
-            is_dir: false,
-            children: vec![],
-            git_status: Some("added".to_string()),
-        }];
-
-        assert_eq!(infer_dir_status(&children), Some("added".to_string()));
-    }
-
-    #[test]
-    fn infer_dir_status_deleted_when_no_modified_or_added() {
-        let children = vec![FileTreeEntry {
-            name: "old.rs".to_string(),
-            path: "old.rs".to_string(),
-            is_dir: false,
-            children: vec![],
-            git_status: Some("deleted".to_string()),
-        }];
-
-        assert_eq!(infer_dir_status(&children), Some("deleted".to_string()));
-    }
-
-    #[test]
-    fn infer_dir_status_none_for_clean_children() {
-        let children = vec![FileTreeEntry {
-            name: "clean.rs".to_string(),
-            path: "clean.rs".to_string(),
-            is_dir: false,
-            children: vec![],
-            git_status: None,
-        }];
-
-        assert_eq!(infer_dir_status(&children), None);
-    }
-
-    #[test]
-    fn infer_dir_status_none_for_empty() {
-        assert_eq!(infer_dir_status(&[]), None);
+    fn without_status() -> TreeOptions {
+        TreeOptions {
+            with_status: Some(false),
+            ..TreeOptions::default()
+        }
     }
 
     #[test]
     fn get_file_tree_returns_error_for_nonexistent_path() {
-        let result = get_file_tree("/nonexistent/path/abc123".to_string());
+        let result = get_file_tree(
+            "/nonexistent/path/abc123".to_string(),
+            TreeOptions::default(),
+        );
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("does not exist"));
     }
 
     #[test]
     fn get_file_tree_sorts_dirs_before_files() {
-        let temp = std::env::temp_dir().join(format!(
-            "central_tree_test_{}",
-            uuid::Uuid::new_v4()
-        ));
+        let temp = std::env::temp_dir().join(format!("central_tree_test_{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(&temp).unwrap();
         std::fs::write(temp.join("b_file.txt"), "content").unwrap();
         std::fs::write(temp.join("a_file.txt"), "content").unwrap();
         std::fs::create_dir_all(temp.join("z_dir")).unwrap();
 
-        let tree = get_file_tree(temp.to_string_lossy().to_string()).unwrap();
+        let tree =
+            get_file_tree(temp.to_string_lossy().to_string(), TreeOptions::default()).unwrap();
 
         // Directories should come first
         assert!(tree[0].is_dir, "First entry should be a directory");
@@ -287,16 +148,14 @@ mod tests {
 
     #[test]
     fn get_file_tree_skips_hidden_dirs() {
-        let temp = std::env::temp_dir().join(format!(
-            "central_skip_test_{}",
-            uuid::Uuid::new_v4()
-        ));
+        let temp = std::env::temp_dir().join(format!("central_skip_test_{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(temp.join("node_modules")).unwrap();
         std::fs::create_dir_all(temp.join(".git")).unwrap();
         std::fs::create_dir_all(temp.join("src")).unwrap();
         std::fs::write(temp.join("src").join("main.rs"), "fn main() {}").unwrap();
 
-        let tree = get_file_tree(temp.to_string_lossy().to_string()).unwrap();
+        let tree =
+            get_file_tree(temp.to_string_lossy().to_string(), TreeOptions::default()).unwrap();
         let names: Vec<&str> = tree.iter().map(|e| e.name.as_str()).collect();
 
         assert!(names.contains(&"src"));
@@ -305,4 +164,123 @@ mod tests {
 
         std::fs::remove_dir_all(&temp).unwrap();
     }
+
+    #[test]
+    fn get_file_tree_depth_limit_marks_expandable_dirs() {
+        let temp = std::env::temp_dir().join(format!("central_lazy_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("full")).unwrap();
+        std::fs::write(temp.join("full").join("a.rs"), "").unwrap();
+        std::fs::create_dir_all(temp.join("empty")).unwrap();
+        std::fs::create_dir_all(temp.join("only_skipped").join("node_modules")).unwrap();
+
+        let tree = get_file_tree(
+            temp.to_string_lossy().to_string(),
+            TreeOptions {
+                max_depth: Some(0),
+                ..TreeOptions::default()
+            },
+        )
+        .unwrap();
+        let find = |name: &str| tree.iter().find(|e| e.name == name).unwrap();
+
+        assert!(find("full").children.is_empty());
+        assert_eq!(find("full").has_children, Some(true));
+        assert_eq!(find("empty").has_children, Some(false));
+        assert_eq!(find("only_skipped").has_children, Some(false));
+
+        let sub = get_file_tree(
+            temp.to_string_lossy().to_string(),
+            TreeOptions {
+                dir_path: Some("full".to_string()),
+                max_depth: Some(0),
+                ..TreeOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(sub[0].path, "full/a.rs");
+        assert_eq!(sub[0].has_children, None);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn cancelled_walk_stops_with_an_error() {
+        let temp =
+            std::env::temp_dir().join(format!("central_cancel_tree_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        let cancelled = AtomicBool::new(true);
+
+        let path = temp.to_string_lossy().to_string();
+        let result = build_file_tree(&path, &without_status(), &cancelled);
+        assert_eq!(result.unwrap_err(), "Cancelled");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn cancel_operation_stops_a_running_walk() {
+        let temp =
+            std::env::temp_dir().join(format!("central_cancel_running_{}", uuid::Uuid::new_v4()));
+        for a in 0..15 {
+            for b in 0..15 {
+                for c in 0..15 {
+                    std::fs::create_dir_all(temp.join(format!("{a}/{b}/{c}"))).unwrap();
+                }
+            }
+        }
+        let operations = crate::operations::create_operation_handle();
+        let op = Operation::begin(&operations, Some("walk".to_string())).unwrap();
+
+        let path = temp.to_string_lossy().to_string();
+        let walk =
+            std::thread::spawn(move || build_file_tree(&path, &without_status(), op.cancel_flag()));
+        assert!(operations.lock().unwrap().cancel("walk"));
+
+        assert_eq!(walk.join().unwrap().unwrap_err(), "Cancelled");
+        assert!(!operations.lock().unwrap().cancel("walk"));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[test]
+    fn dir_path_outside_the_project_is_rejected() {
+        let temp =
+            std::env::temp_dir().join(format!("central_tree_escape_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("project")).unwrap();
+        std::fs::create_dir_all(temp.join("sibling")).unwrap();
+        let project = temp.join("project").to_string_lossy().to_string();
+        let outside = temp.join("sibling").to_string_lossy().to_string();
+
+        for dir in ["../sibling".to_string(), outside] {
+            let options = TreeOptions {
+                dir_path: Some(dir),
+                ..without_status()
+            };
+            let result = get_file_tree(project.clone(), options);
+            assert!(result.unwrap_err().contains("outside project"));
+        }
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loops_are_listed_but_not_followed() {
+        let temp = std::env::temp_dir().join(format!("central_tree_loop_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("a")).unwrap();
+        std::os::unix::fs::symlink(&temp, temp.join("a").join("back")).unwrap();
+
+        let path = temp.to_string_lossy().to_string();
+        let options = TreeOptions {
+            max_depth: Some(usize::MAX),
+            ..without_status()
+        };
+        let tree = get_file_tree(path, options).unwrap();
+        let back = &tree[0].children[0];
+        assert_eq!(back.path, "a/back");
+        assert!(back.is_dir);
+        assert!(back.children.is_empty());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
 }
@@ -0,0 +1,122 @@
+//! Git status decoration for tree entries
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::super::git_helpers::{collect_git_statuses, open_repo, project_prefix};
+use super::super::types::FileTreeEntry;
+
+/// Git statuses keyed by project-relative path. Git reports paths from the
+/// repo root, which differs when a subdirectory of a repo is opened.
+pub(super) fn project_statuses(project_path: &str, root: &Path) -> HashMap<String, String> {
+    let Ok(repo) = open_repo(project_path) else {
+        return HashMap::new();
+    };
+    let prefix = project_prefix(&repo, root);
+    collect_git_statuses(&repo)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(path, status)| {
+            let rel = Path::new(&path).strip_prefix(&prefix).ok()?;
+            Some((rel.to_string_lossy().to_string(), status))
+        })
+        .collect()
+}
+
+pub(super) fn infer_dir_status(children: &[FileTreeEntry]) -> Option<String> {
+    let has_modified = children
+        .iter()
+        .any(|c| c.git_status.as_deref() == Some("modified"));
+    let has_added = children
+        .iter()
+        .any(|c| c.git_status.as_deref() == Some("added"));
+    let has_deleted = children
+        .iter()
+        .any(|c| c.git_status.as_deref() == Some("deleted"));
+
+    if has_modified {
+        Some("modified".to_string())
+    } else if has_added {
+        Some("added".to_string())
+    } else if has_deleted {
+        Some("deleted".to_string())
+    } else {
+        None
+    }
+}
+
+/// Same priority as `infer_dir_status`, but over every status below `dir`
+pub(super) fn infer_dir_status_from_map(
+    statuses: &HashMap<String, String>,
+    dir: &str,
+) -> Option<String> {
+    let prefix = format!("{dir}/");
+    let labels: Vec<&String> = statuses
+        .iter()
+        .filter(|(path, _)| path.starts_with(&prefix))
+        .map(|(_, label)| label)
+        .collect();
+
+    ["modified", "added", "deleted"]
+        .into_iter()
+        .find(|wanted| labels.iter().any(|l| l.as_str() == *wanted))
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, git_status: Option<&str>) -> FileTreeEntry {
+        FileTreeEntry {
+            name: name.to_string(),
+            path: name.to_string(),
+            is_dir: false,
+            children: vec![],
+            git_status: git_status.map(String::from),
+            has_children: None,
+        }
+    }
+
+    #[test]
+    fn infer_dir_status_modified_takes_priority() {
+        let children = vec![file("a.rs", Some("modified")), file("b.rs", Some("added"))];
+        assert_eq!(infer_dir_status(&children), Some("modified".to_string()));
+    }
+
+    #[test]
+    fn infer_dir_status_added_when_no_modified() {
+        let children = vec![file("new.rs", Some("added"))];
+        assert_eq!(infer_dir_status(&children), Some("added".to_string()));
+    }
+
+    #[test]
+    fn infer_dir_status_deleted_when_no_modified_or_added() {
+        let children = vec![file("old.rs", Some("deleted"))];
+        assert_eq!(infer_dir_status(&children), Some("deleted".to_string()));
+    }
+
+    #[test]
+    fn infer_dir_status_none_for_clean_children() {
+        let children = vec![file("clean.rs", None)];
+        assert_eq!(infer_dir_status(&children), None);
+    }
+
+    #[test]
+    fn infer_dir_status_none_for_empty() {
+        assert_eq!(infer_dir_status(&[]), None);
+    }
+
+    #[test]
+    fn infer_dir_status_from_map_uses_descendants() {
+        let mut statuses = HashMap::new();
+        statuses.insert("src/deep/a.rs".to_string(), "added".to_string());
+        statuses.insert("srcx/b.rs".to_string(), "modified".to_string());
+
+        assert_eq!(
+            infer_dir_status_from_map(&statuses, "src"),
+            Some("added".to_string())
+        );
+        assert_eq!(infer_dir_status_from_map(&statuses, "lib"), None);
+    }
+}
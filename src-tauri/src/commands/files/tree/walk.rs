@@ -0,0 +1,113 @@
+//! Recursive directory walk behind `get_file_tree`
+
+use std::collections::HashMap;
+use std::fs::DirEntry;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::super::skip::SkipRules;
+use super::super::types::FileTreeEntry;
+use super::status::{infer_dir_status, infer_dir_status_from_map};
+
+/// Walk context shared by every level of one tree build
+pub(super) struct TreeWalk<'a> {
+    pub root: &'a Path,
+    pub statuses: &'a HashMap<String, String>,
+    pub max_depth: usize,
+    pub skip: SkipRules,
+    pub cancel: &'a AtomicBool,
+}
+
+pub(super) fn build_tree_recursive(
+    dir: &Path,
+    walk: &TreeWalk,
+    depth: usize,
+) -> Result<Vec<FileTreeEntry>, String> {
+    if walk.cancel.load(Ordering::Relaxed) {
+        return Err("Cancelled".to_string());
+    }
+
+    let mut entries: Vec<FileTreeEntry> = Vec::new();
+    let read = std::fs::read_dir(dir).map_err(|e| format!("Failed to read dir: {e}"))?;
+
+    for item in read {
+        let item = item.map_err(|e| format!("Dir entry error: {e}"))?;
+        let name = item.file_name().to_string_lossy().to_string();
+
+        if walk.skip.skips(&name) {
+            continue;
+        }
+
+        let entry = build_entry(&item, walk, depth)?;
+        entries.push(entry);
+    }
+
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then(a.name.cmp(&b.name)));
+
+    Ok(entries)
+}
+
+fn build_entry(item: &DirEntry, walk: &TreeWalk, depth: usize) -> Result<FileTreeEntry, String> {
+    let name = item.file_name().to_string_lossy().to_string();
+    let full_path = item.path();
+    let rel_path = full_path
+        .strip_prefix(walk.root)
+        .unwrap_or(&full_path)
+        .to_string_lossy()
+        .to_string();
+
+    let is_dir = full_path.is_dir();
+    if !is_dir {
+        return Ok(FileTreeEntry {
+            name,
+            git_status: walk.statuses.get(&rel_path).cloned(),
+            path: rel_path,
+            is_dir,
+            children: vec![],
+            has_children: None,
+        });
+    }
+
+    // Symlinked directories are listed but never followed, so a link back up
+    // the tree can't loop and a link out of the project isn't walked
+    let expand = depth < walk.max_depth && !is_symlink(item);
+    let children = if expand {
+        build_tree_recursive(&full_path, walk, depth + 1)?
+    } else {
+        vec![]
+    };
+
+    // Unexpanded placeholders have no children to infer from — use the status map
+    let (git_status, has_children) = if expand {
+        (infer_dir_status(&children), !children.is_empty())
+    } else {
+        (
+            infer_dir_status_from_map(walk.statuses, &rel_path),
+            dir_has_visible_entries(&full_path, &walk.skip),
+        )
+    };
+
+    Ok(FileTreeEntry {
+        name,
+        path: rel_path,
+        is_dir,
+        children,
+        git_status,
+        has_children: Some(has_children),
+    })
+}
+
+/// `DirEntry::file_type` doesn't follow symlinks, unlike `Path::is_dir`
+fn is_symlink(item: &DirEntry) -> bool {
+    item.file_type().map_or(true, |t| t.is_symlink())
+}
+
+/// Cheap check (single read_dir, no recursion) for any non-skipped entry
+fn dir_has_visible_entries(dir: &Path, skip: &SkipRules) -> bool {
+    std::fs::read_dir(dir)
+        .map(|read| {
+            read.flatten()
+                .any(|item| !skip.skips(&item.file_name().to_string_lossy()))
+        })
+        .unwrap_or(false)
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct FileTreeEntry {
@@ -7,6 +7,9 @@ pub struct FileTreeEntry {
     pub is_dir: bool,
     pub children: Vec<FileTreeEntry>,
     pub git_status: Option<String>,
+    /// For directories: whether any non-skipped entry exists inside, even when
+    /// `children` was left empty because the depth limit was reached. None for files.
+    pub has_children: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -43,3 +46,194 @@ pub struct DiffLine {
     pub old_lineno: Option<u32>,
     pub new_lineno: Option<u32>,
 }
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FileDiffStat {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiffSummary {
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub files: Vec<FileDiffStat>,
+}
+
+/// One entry of a batch read — exactly one of `content` / `error` is set
+#[derive(Debug, Serialize, Clone)]
+pub struct FileContentResult {
+    pub content: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Result of `hash_file`, for detecting external edits before a save
+#[derive(Debug, Serialize, Clone)]
+pub struct FileHash {
+    pub hash: String,
+    pub size: u64,
+}
+
+/// A variable named in a project's `.env.example`-style template. Values are
+/// never included.
+#[derive(Debug, Serialize, Clone)]
+pub struct EnvRequirement {
+    pub name: String,
+    /// Template file that lists it, e.g. ".env.example"
+    pub source: String,
+    /// Whether it's set (non-empty) in the environment agents inherit
+    pub present: bool,
+}
+
+/// One entry of a multi-repo status — exactly one of `status` / `error` is set
+#[derive(Debug, Serialize, Clone)]
+pub struct GitStatusResult {
+    pub status: Option<GitStatusInfo>,
+    pub error: Option<String>,
+}
+
+/// Disk usage of a directory subtree
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DirUsage {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// Raw file content for binary previews (images etc.)
+#[derive(Debug, Serialize, Clone)]
+pub struct FileBytes {
+    pub base64: String,
+    pub mime_type: String,
+    pub size: u64,
+}
+
+/// The commit HEAD points at. In a fresh repo (unborn HEAD) `has_commits`
+/// is false and the commit fields are empty.
+#[derive(Debug, Serialize, Clone)]
+pub struct HeadInfo {
+    pub has_commits: bool,
+    pub detached: bool,
+    pub branch: Option<String>,
+    pub hash: Option<String>,
+    pub short_hash: Option<String>,
+    pub author: Option<String>,
+    pub timestamp: Option<i64>,
+    pub summary: Option<String>,
+}
+
+/// Uncommitted-change counts for a pre-checkout "are you sure?" check.
+/// Untracked directories count as a single entry.
+#[derive(Debug, Serialize, Clone)]
+pub struct CleanState {
+    pub clean: bool,
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+}
+
+/// An operation git has paused mid-way, for a "merge in progress" banner.
+/// `state` is e.g. "clean", "merge", "rebase_interactive", "cherry_pick".
+#[derive(Debug, Serialize, Clone)]
+pub struct OperationState {
+    pub state: String,
+    pub in_progress: bool,
+    pub conflicted_files: Vec<String>,
+}
+
+/// Whether a path is inside a git working tree, and where that tree starts
+#[derive(Debug, Serialize, Clone)]
+pub struct RepoInfo {
+    pub is_repo: bool,
+    pub repo_root: Option<String>,
+    /// True for a `git worktree add` checkout rather than the main one
+    pub is_worktree: bool,
+    /// The `.git` directory shared by all worktrees of the repo
+    pub common_dir: Option<String>,
+}
+
+/// Who git will record as the author of new commits. `configured` is false
+/// when either field is missing, so the UI can prompt before committing.
+#[derive(Debug, Serialize, Clone)]
+pub struct GitIdentity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub configured: bool,
+}
+
+/// Line-ending and indentation conventions of a text file
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FileFormat {
+    /// "lf", "crlf", or "mixed"
+    pub line_ending: String,
+    /// "spaces", "tabs", or "mixed"
+    pub indent: String,
+    /// Most common indent step for space-indented files
+    pub indent_width: Option<u8>,
+}
+
+/// A file git detected as moved between HEAD and the working tree
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RenameEntry {
+    pub old_path: String,
+    pub new_path: String,
+    /// 0–100; 100 means the content is unchanged
+    pub similarity: u8,
+}
+
+/// One file of a `write_files` batch
+#[derive(Debug, Deserialize, Clone)]
+pub struct FileWrite {
+    pub path: String,
+    pub content: String,
+}
+
+/// Outcome for one file of a `write_files` batch. `status` is "written",
+/// "failed" (this file caused the batch to abort) or "skipped" (left untouched
+/// because another file failed).
+#[derive(Debug, Serialize, Clone)]
+pub struct FileWriteStatus {
+    pub path: String,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Result of a `write_files` batch: either every file was written or none were
+#[derive(Debug, Serialize, Clone)]
+pub struct WriteFilesResult {
+    pub committed: bool,
+    pub files: Vec<FileWriteStatus>,
+}
+
+/// Share of the sampled source files written in one language
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LanguageShare {
+    pub language: String,
+    pub files: usize,
+    /// Percentage of recognized source files, 0–100
+    pub percent: f64,
+}
+
+/// Languages (most files first) and build tools found in a project
+#[derive(Debug, Serialize, Clone)]
+pub struct ProjectLanguages {
+    pub languages: Vec<LanguageShare>,
+    pub build_tools: Vec<String>,
+    pub sampled_files: usize,
+    /// True when the sample cap was hit before the whole tree was seen
+    pub truncated: bool,
+}
+
+/// A commit that touched a file, from `git_file_history`
+#[derive(Debug, Serialize, Clone)]
+pub struct FileCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub summary: Option<String>,
+    pub author: Option<String>,
+    pub timestamp: i64,
+    /// The file's repo-relative path in this commit (differs before a rename)
+    pub path: String,
+}
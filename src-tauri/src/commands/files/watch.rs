@@ -0,0 +1,36 @@
+use tauri::State;
+
+use super::fs_helpers::{canonical_project, resolve_existing_in_project};
+use crate::watcher::WatcherHandle;
+
+/// Emit debounced `file-changed` events for one project file, for an open
+/// editor tab that needs to reload when e.g. an agent edits it. Cheaper than
+/// a project watcher and needs no filtering on the frontend.
+#[tauri::command]
+pub fn watch_file(
+    project_path: String,
+    file_path: String,
+    watcher: State<'_, WatcherHandle>,
+) -> Result<(), String> {
+    let project = canonical_project(&project_path)?;
+    let full_path = resolve_existing_in_project(&project, &file_path)?;
+
+    watcher
+        .lock()
+        .map_err(|e| format!("Watcher lock error: {e}"))?
+        .watch_file(&project_path, &file_path, full_path);
+    Ok(())
+}
+
+/// Stop `watch_file` events for a file. Returns whether it was watched.
+#[tauri::command]
+pub fn unwatch_file(
+    project_path: String,
+    file_path: String,
+    watcher: State<'_, WatcherHandle>,
+) -> Result<bool, String> {
+    Ok(watcher
+        .lock()
+        .map_err(|e| format!("Watcher lock error: {e}"))?
+        .unwatch_file(&project_path, &file_path))
+}
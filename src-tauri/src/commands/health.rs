@@ -0,0 +1,84 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::debug_log;
+use crate::pty::PtyHandle;
+use crate::shell_env::{find_executable, shell_info, ShellInfo};
+use crate::sidecar::{
+    verify_worker_script, worker_env_report, SidecarHandle, SidecarStats, WorkerEnvReport,
+    WorkerVerification,
+};
+
+#[derive(Debug, Serialize)]
+pub struct BackendHealth {
+    pub healthy: bool,
+    pub sidecar_reachable: bool,
+    pub active_sessions: usize,
+    pub active_terminals: usize,
+    pub log_writable: bool,
+    pub node_found: bool,
+    pub claude_found: bool,
+}
+
+/// Cheap snapshot of backend health for a UI status indicator. Never spawns processes.
+#[tauri::command]
+pub fn backend_health(
+    sidecar: State<'_, SidecarHandle>,
+    pty: State<'_, PtyHandle>,
+) -> BackendHealth {
+    let active_sessions = sidecar.lock().ok().map(|m| m.active_session_ids().len());
+    let active_terminals = pty.lock().ok().map(|m| m.session_count());
+
+    let sidecar_reachable = active_sessions.is_some();
+    let log_writable = debug_log::is_writable();
+    let node_found = find_executable("node").is_some();
+    let claude_found = find_executable("claude").is_some();
+
+    BackendHealth {
+        healthy: sidecar_reachable && active_terminals.is_some() && log_writable && node_found,
+        sidecar_reachable,
+        active_sessions: active_sessions.unwrap_or(0),
+        active_terminals: active_terminals.unwrap_or(0),
+        log_writable,
+        node_found,
+        claude_found,
+    }
+}
+
+/// Live, exited-but-unreaped, and restarted worker counts. Exited workers
+/// found along the way are reaped, so calling this also fixes the leak it reports.
+#[tauri::command]
+pub fn sidecar_stats(sidecar: State<'_, SidecarHandle>) -> Result<SidecarStats, String> {
+    let stats = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?
+        .stats_and_reap();
+    debug_log::log("RUST-CMD", &format!("sidecar_stats: {stats:?}"));
+    Ok(stats)
+}
+
+/// PATH, tool resolution, CA bundle and proxy settings exactly as a spawned
+/// worker would inherit them
+#[tauri::command]
+pub fn inspect_worker_env() -> WorkerEnvReport {
+    worker_env_report()
+}
+
+/// Check that the worker script resolves, is readable, and matches the hash
+/// recorded at build time, so a corrupted install gets a clear diagnosis
+#[tauri::command]
+pub fn verify_worker() -> WorkerVerification {
+    let report = verify_worker_script();
+    debug_log::log(
+        "RUST-CMD",
+        &format!("verify_worker: {} ({:?})", report.status, report.path),
+    );
+    report
+}
+
+/// The user's shell and the profile files it reads, for the "edit your shell
+/// profile" hint when `node` or `claude` isn't on the in-app PATH
+#[tauri::command]
+pub fn detect_shell() -> ShellInfo {
+    shell_info()
+}
@@ -1,8 +1,14 @@
+pub mod about;
 pub mod agents;
 pub mod files;
+pub mod health;
 pub mod notifications;
+pub mod projects;
+pub mod sessions;
 pub mod settings;
+pub mod task;
 pub mod terminal;
+pub mod terminal_children;
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
@@ -1,8 +1,23 @@
+use std::time::Duration;
+
+use crate::commands::settings::load_setting;
+use crate::notifications;
+
+/// Milliseconds during which a session's repeat notifications are dropped
+/// and the rest of a burst is folded into one
+const THROTTLE_SETTING: &str = "notification_throttle_ms";
+
 #[tauri::command]
 pub fn send_native_notification(
+    app: tauri::AppHandle,
     title: String,
     body: String,
     session_id: String,
 ) -> Result<(), String> {
-    crate::notifications::send(&title, &body, &session_id)
+    let window = load_setting(&app, THROTTLE_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse().ok())
+        .map_or(notifications::DEFAULT_WINDOW, Duration::from_millis);
+    notifications::send(&title, &body, &session_id, window)
 }
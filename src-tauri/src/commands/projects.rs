@@ -0,0 +1,183 @@
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::clock::now_ms;
+use crate::commands::files::status::get_git_status;
+use crate::commands::settings::{load_setting, store_setting};
+use crate::debug_log;
+use crate::project_path::{path_string, resolve_project_path, ProjectPathError};
+use crate::projects::{OpenProject, ProjectsHandle};
+use crate::pty::PtyHandle;
+use crate::watcher::WatcherHandle;
+
+const RECENT_PROJECTS_KEY: &str = "recent_projects";
+const MAX_RECENT_PROJECTS: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+struct ProjectClosedPayload {
+    project_path: String,
+}
+
+/// Open a project: validate the path, start its watcher, record it in recents,
+/// warm its git status, and emit `project-opened`.
+#[tauri::command]
+pub fn open_project(
+    app: AppHandle,
+    project_path: String,
+    projects: State<'_, ProjectsHandle>,
+    watcher: State<'_, WatcherHandle>,
+) -> Result<OpenProject, String> {
+    let path = normalize_project_path(&project_path)?;
+
+    watcher
+        .lock()
+        .map_err(|e| format!("Watcher lock error: {e}"))?
+        .watch(&path)?;
+
+    if let Err(e) = touch_recent_project(&app, &path) {
+        debug_log::log("PROJECTS", &format!("Failed to update recents: {e}"));
+    }
+
+    let project = OpenProject {
+        git_status: get_git_status(path.clone()).ok(),
+        project_path: path,
+        opened_at_ms: now_ms(),
+    };
+
+    projects
+        .lock()
+        .map_err(|e| format!("Project registry lock error: {e}"))?
+        .insert(project.clone());
+
+    debug_log::log("PROJECTS", &format!("Opened {}", project.project_path));
+    if let Err(e) = app.emit("project-opened", &project) {
+        debug_log::log("PROJECTS", &format!("EMIT ERROR: {e}"));
+    }
+
+    Ok(project)
+}
+
+/// Tear down everything `open_project` set up, including the project's
+/// terminals. Returns whether it was open.
+#[tauri::command]
+pub fn close_project(
+    app: AppHandle,
+    project_path: String,
+    projects: State<'_, ProjectsHandle>,
+    watcher: State<'_, WatcherHandle>,
+    pty: State<'_, PtyHandle>,
+) -> Result<bool, String> {
+    // The directory may already be gone, so fall back to the raw path
+    let path = normalize_project_path(&project_path).unwrap_or(project_path);
+
+    watcher
+        .lock()
+        .map_err(|e| format!("Watcher lock error: {e}"))?
+        .unwatch(&path);
+
+    let terminals = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?
+        .close_for_project(&path);
+
+    let was_open = projects
+        .lock()
+        .map_err(|e| format!("Project registry lock error: {e}"))?
+        .remove(&path)
+        .is_some();
+
+    debug_log::log(
+        "PROJECTS",
+        &format!("Closed {path} (was_open={was_open}, terminals={terminals})"),
+    );
+    let payload = ProjectClosedPayload { project_path: path };
+    if let Err(e) = app.emit("project-closed", &payload) {
+        debug_log::log("PROJECTS", &format!("EMIT ERROR: {e}"));
+    }
+
+    Ok(was_open)
+}
+
+#[tauri::command]
+pub fn list_open_projects(projects: State<'_, ProjectsHandle>) -> Result<Vec<OpenProject>, String> {
+    let registry = projects
+        .lock()
+        .map_err(|e| format!("Project registry lock error: {e}"))?;
+
+    Ok(registry.list())
+}
+
+/// Most recently opened project paths, newest first
+#[tauri::command]
+pub fn get_recent_projects(app: AppHandle) -> Result<Vec<String>, String> {
+    load_recent_projects(&app)
+}
+
+/// Expand `~`, resolve symlinks and `..`, and check the path is an existing
+/// directory. Returns the absolute path every other command should be given,
+/// or a `{ code, message }` error the UI can show next to the path input.
+#[tauri::command]
+pub fn validate_project_path(path: String) -> Result<String, ProjectPathError> {
+    resolve_project_path(&path).map(|p| path_string(&p))
+}
+
+fn normalize_project_path(project_path: &str) -> Result<String, String> {
+    Ok(validate_project_path(project_path.to_string())?)
+}
+
+pub(crate) fn load_recent_projects(app: &AppHandle) -> Result<Vec<String>, String> {
+    match load_setting(app, RECENT_PROJECTS_KEY)? {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| format!("Failed to parse recent projects: {e}"))
+        }
+        None => Ok(vec![]),
+    }
+}
+
+fn touch_recent_project(app: &AppHandle, path: &str) -> Result<(), String> {
+    let recents = push_recent(load_recent_projects(app).unwrap_or_default(), path);
+    let json = serde_json::to_string(&recents)
+        .map_err(|e| format!("Failed to serialize recent projects: {e}"))?;
+    store_setting(app, RECENT_PROJECTS_KEY, json)
+}
+
+/// Move `path` to the front, dropping duplicates and the oldest overflow
+fn push_recent(mut recents: Vec<String>, path: &str) -> Vec<String> {
+    recents.retain(|p| p != path);
+    recents.insert(0, path.to_string());
+    recents.truncate(MAX_RECENT_PROJECTS);
+    recents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_recent_moves_existing_to_front() {
+        let recents = vec!["/a".to_string(), "/b".to_string(), "/c".to_string()];
+        assert_eq!(push_recent(recents, "/b"), vec!["/b", "/a", "/c"]);
+    }
+
+    #[test]
+    fn push_recent_caps_length() {
+        let recents: Vec<String> = (0..MAX_RECENT_PROJECTS).map(|i| format!("/p{i}")).collect();
+        let updated = push_recent(recents, "/new");
+        assert_eq!(updated.len(), MAX_RECENT_PROJECTS);
+        assert_eq!(updated[0], "/new");
+        assert!(!updated.contains(&format!("/p{}", MAX_RECENT_PROJECTS - 1)));
+    }
+
+    #[test]
+    fn normalize_project_path_rejects_files() {
+        let temp = std::env::temp_dir().join(format!("central_normalize_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("file.txt"), "").unwrap();
+
+        let result = normalize_project_path(&temp.join("file.txt").to_string_lossy());
+        assert!(result.unwrap_err().contains("Not a directory"));
+        assert!(normalize_project_path(&temp.to_string_lossy()).is_ok());
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
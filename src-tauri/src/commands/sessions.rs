@@ -0,0 +1,68 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::pty::PtyHandle;
+use crate::sidecar::SidecarHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    Agent,
+    Terminal,
+}
+
+/// One running agent worker or terminal, for a system-monitor-style panel
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub kind: SessionKind,
+    /// Project path for agents, working directory for terminals
+    pub cwd: Option<String>,
+    pub started_at_ms: Option<u64>,
+    pub pid: Option<u32>,
+}
+
+/// Live agent and terminal sessions in one list, oldest first
+#[tauri::command]
+pub fn list_all_sessions(
+    sidecar: State<'_, SidecarHandle>,
+    pty: State<'_, PtyHandle>,
+) -> Result<Vec<SessionSummary>, String> {
+    let mut sessions = agent_sessions(&sidecar)?;
+    let terminals = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?
+        .list();
+    sessions.extend(terminals.into_iter().map(|t| SessionSummary {
+        id: t.session_id,
+        kind: SessionKind::Terminal,
+        cwd: Some(t.cwd),
+        started_at_ms: Some(t.started_at_ms),
+        pid: t.pid,
+    }));
+
+    sessions.sort_by_key(|s| s.started_at_ms.unwrap_or(u64::MAX));
+    Ok(sessions)
+}
+
+fn agent_sessions(sidecar: &SidecarHandle) -> Result<Vec<SessionSummary>, String> {
+    let manager = sidecar
+        .lock()
+        .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+
+    let sessions = manager
+        .active_session_ids()
+        .into_iter()
+        .map(|id| {
+            let meta = manager.session_metadata(&id);
+            SessionSummary {
+                pid: manager.worker_pid(&id),
+                cwd: meta.as_ref().map(|m| m.project_path.clone()),
+                started_at_ms: meta.map(|m| m.started_at_ms),
+                kind: SessionKind::Agent,
+                id,
+            }
+        })
+        .collect();
+    Ok(sessions)
+}
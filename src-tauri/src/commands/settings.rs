@@ -1,52 +1,89 @@
 use std::collections::HashMap;
-use std::fs;
 use std::path::PathBuf;
 
-use tauri::Manager;
+use serde_json::Value;
+use tauri::Emitter;
 
 use crate::debug_log;
 
-/// Resolve the settings.json path inside the app data directory.
-fn settings_file_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
-    let data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+mod atomic;
+mod file;
+mod schema;
 
-    if !data_dir.exists() {
-        fs::create_dir_all(&data_dir)
-            .map_err(|e| format!("Failed to create app data dir: {e}"))?;
-    }
+use file::{
+    read_settings, read_settings_or_last_good, remember_good, settings_file_path, write_settings,
+};
 
-    Ok(data_dir.join("settings.json"))
+/// Read a setting from the backend without going through IPC.
+/// JSON settings come back as their JSON text.
+pub(crate) fn load_setting(app: &tauri::AppHandle, key: &str) -> Result<Option<String>, String> {
+    Ok(load_setting_json(app, key)?.as_ref().map(schema::as_string))
 }
 
-/// Read the entire settings map from disk.
-fn read_settings(path: &PathBuf) -> Result<HashMap<String, String>, String> {
-    if !path.exists() {
-        return Ok(HashMap::new());
-    }
+/// Write a setting from the backend without going through IPC.
+pub(crate) fn store_setting(app: &tauri::AppHandle, key: &str, value: String) -> Result<(), String> {
+    store_setting_json(app, key, Value::String(value))
+}
+
+/// Read a setting with its JSON type intact.
+pub(crate) fn load_setting_json(app: &tauri::AppHandle, key: &str) -> Result<Option<Value>, String> {
+    Ok(load_settings_json(app, &[key.to_string()])?.remove(key).flatten())
+}
+
+/// Write a setting of any JSON type.
+pub(crate) fn store_setting_json(app: &tauri::AppHandle, key: &str, value: Value) -> Result<(), String> {
+    store_settings_json(app, HashMap::from([(key.to_string(), value)]))
+}
+
+/// Read several settings with one parse of the file; unset keys map to None.
+pub(crate) fn load_settings_json(
+    app: &tauri::AppHandle,
+    keys: &[String],
+) -> Result<HashMap<String, Option<Value>>, String> {
+    let path = settings_file_path(app)?;
+    let map = read_settings_or_last_good(&path)?;
+    Ok(pick(&map, keys))
+}
+
+/// Apply several writes with one read and one write, so they land together.
+pub(crate) fn store_settings_json(
+    app: &tauri::AppHandle,
+    entries: HashMap<String, Value>,
+) -> Result<(), String> {
+    let path = settings_file_path(app)?;
+    let mut map = read_settings(&path)?;
 
-    let contents =
-        fs::read_to_string(path).map_err(|e| format!("Failed to read settings file: {e}"))?;
+    map.extend(entries);
+    write_settings(&path, &map)
+}
 
-    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse settings JSON: {e}"))
+/// Remove `key` from the file, rewriting it only when the key was set.
+/// Returns whether anything was removed.
+fn remove_setting(path: &PathBuf, key: &str) -> Result<bool, String> {
+    let mut map = read_settings(path)?;
+    if map.remove(key).is_none() {
+        return Ok(false);
+    }
+    write_settings(path, &map)?;
+    Ok(true)
 }
 
-/// Write the entire settings map to disk.
-fn write_settings(path: &PathBuf, map: &HashMap<String, String>) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(map)
-        .map_err(|e| format!("Failed to serialize settings: {e}"))?;
+fn sorted_keys(map: HashMap<String, Value>) -> Vec<String> {
+    let mut keys: Vec<String> = map.into_keys().collect();
+    keys.sort();
+    keys
+}
 
-    fs::write(path, json).map_err(|e| format!("Failed to write settings file: {e}"))
+fn pick(map: &HashMap<String, Value>, keys: &[String]) -> HashMap<String, Option<Value>> {
+    keys.iter()
+        .map(|key| (key.clone(), map.get(key).cloned()))
+        .collect()
 }
 
 /// Read a single setting by key.
 #[tauri::command]
 pub fn get_setting(app: tauri::AppHandle, key: String) -> Result<Option<String>, String> {
-    let path = settings_file_path(&app)?;
-    let map = read_settings(&path)?;
-    let value = map.get(&key).cloned();
+    let value = load_setting(&app, &key)?;
 
     debug_log::log(
         "SETTINGS",
@@ -59,41 +96,141 @@ pub fn get_setting(app: tauri::AppHandle, key: String) -> Result<Option<String>,
 /// Write a single setting by key.
 #[tauri::command]
 pub fn set_setting(app: tauri::AppHandle, key: String, value: String) -> Result<(), String> {
-    let path = settings_file_path(&app)?;
-    let mut map = read_settings(&path)?;
-
-    map.insert(key.clone(), value);
-    write_settings(&path, &map)?;
+    store_setting(&app, &key, value)?;
 
     debug_log::log("SETTINGS", &format!("set_setting key={key} written"));
 
     Ok(())
 }
 
+/// Read several settings at once, e.g. everything needed at startup.
+#[tauri::command]
+pub fn get_settings(
+    app: tauri::AppHandle,
+    keys: Vec<String>,
+) -> Result<HashMap<String, Option<String>>, String> {
+    let values = load_settings_json(&app, &keys)?;
+
+    debug_log::log("SETTINGS", &format!("get_settings keys={}", keys.len()));
+
+    Ok(values
+        .into_iter()
+        .map(|(key, value)| (key, value.as_ref().map(schema::as_string)))
+        .collect())
+}
+
+/// Write several settings in one go; either all of them land or none do.
+#[tauri::command]
+pub fn set_settings(app: tauri::AppHandle, entries: HashMap<String, String>) -> Result<(), String> {
+    let count = entries.len();
+    let entries = entries
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect();
+    store_settings_json(&app, entries)?;
+
+    debug_log::log("SETTINGS", &format!("set_settings keys={count} written"));
+
+    Ok(())
+}
+
+/// Read a setting as a number, boolean, object, … rather than a string.
+#[tauri::command]
+pub fn get_setting_json(app: tauri::AppHandle, key: String) -> Result<Option<Value>, String> {
+    let value = load_setting_json(&app, &key)?;
+
+    debug_log::log(
+        "SETTINGS",
+        &format!("get_setting_json key={key} found={}", value.is_some()),
+    );
+
+    Ok(value)
+}
+
+/// Write a setting of any JSON type, without stringifying it first.
+#[tauri::command]
+pub fn set_setting_json(app: tauri::AppHandle, key: String, value: Value) -> Result<(), String> {
+    store_setting_json(&app, &key, value)?;
+
+    debug_log::log("SETTINGS", &format!("set_setting_json key={key} written"));
+
+    Ok(())
+}
+
+/// Remove a setting so its default applies again; a no-op if it isn't set.
+#[tauri::command]
+pub fn delete_setting(app: tauri::AppHandle, key: String) -> Result<(), String> {
+    let path = settings_file_path(&app)?;
+    let removed = remove_setting(&path, &key)?;
+
+    debug_log::log(
+        "SETTINGS",
+        &format!("delete_setting key={key} removed={removed}"),
+    );
+
+    Ok(())
+}
+
+/// Every key currently set, sorted, for a settings inspector.
+#[tauri::command]
+pub fn list_settings(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let path = settings_file_path(&app)?;
+    Ok(sorted_keys(read_settings_or_last_good(&path)?))
+}
+
+/// Re-read settings.json after an external edit or a change from another
+/// window, and emit `settings-reloaded` with the keys now set. A file that
+/// doesn't parse is reported and the last good copy stays in use.
+#[tauri::command]
+pub fn reload_settings(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let path = settings_file_path(&app)?;
+    let map = read_settings(&path).map_err(|e| {
+        debug_log::log("SETTINGS", &format!("reload_settings failed: {e}"));
+        format!("{e} (keeping the previously loaded settings)")
+    })?;
+    remember_good(path, map.clone())?;
+
+    let keys = sorted_keys(map);
+    debug_log::log("SETTINGS", &format!("reload_settings: {} keys", keys.len()));
+    if let Err(e) = app.emit("settings-reloaded", &keys) {
+        debug_log::log("SETTINGS", &format!("EMIT ERROR: {e}"));
+    }
+    Ok(keys)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn read_settings_nonexistent_returns_empty() {
-        let path = PathBuf::from("/tmp/_central_test_nonexistent.json");
-        let result = read_settings(&path).unwrap();
-        assert!(result.is_empty());
+    fn pick_reports_unset_keys_as_none() {
+        let map = HashMap::from([("theme".to_string(), Value::from("dark"))]);
+        let keys = ["theme".to_string(), "missing".to_string()];
+
+        let picked = pick(&map, &keys);
+        assert_eq!(picked.len(), 2);
+        assert_eq!(picked["theme"], Some(Value::from("dark")));
+        assert_eq!(picked["missing"], None);
     }
 
     #[test]
-    fn read_write_roundtrip() {
-        let path = PathBuf::from("/tmp/_central_test_settings_roundtrip.json");
-
-        let mut map = HashMap::new();
-        map.insert("foo".to_string(), "bar".to_string());
-
+    fn delete_and_list_reflect_changes() {
+        let path = std::env::temp_dir()
+            .join(format!("central_settings_{}.json", uuid::Uuid::new_v4()));
+        assert!(!remove_setting(&path, "theme").unwrap());
+        assert!(!path.exists());
+
+        let map = HashMap::from([
+            ("theme".to_string(), Value::from("dark")),
+            ("font_size".to_string(), Value::from(14)),
+        ]);
         write_settings(&path, &map).unwrap();
+        assert_eq!(sorted_keys(read_settings(&path).unwrap()), ["font_size", "theme"]);
 
-        let loaded = read_settings(&path).unwrap();
-        assert_eq!(loaded.get("foo").unwrap(), "bar");
+        assert!(remove_setting(&path, "theme").unwrap());
+        assert!(!remove_setting(&path, "theme").unwrap());
+        assert_eq!(sorted_keys(read_settings(&path).unwrap()), ["font_size"]);
 
-        // Clean up
-        let _ = fs::remove_file(&path);
+        let _ = std::fs::remove_file(&path);
     }
 }
@@ -0,0 +1,57 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Replace `path` with whatever `write` produces. The data goes to a sibling
+/// `.tmp` file that is renamed over the target only once fully written and
+/// synced, so a crash or full disk leaves the old file intact.
+pub(super) fn replace_file(
+    path: &Path,
+    write: impl FnOnce(&mut File) -> std::io::Result<()>,
+) -> Result<(), String> {
+    let tmp = tmp_path(path);
+    let result = File::create(&tmp)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.flush()?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp, path));
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to write settings file: {e}")
+    })
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_write_keeps_previous_file() {
+        let path =
+            std::env::temp_dir().join(format!("central_settings_{}.json", uuid::Uuid::new_v4()));
+        fs::write(&path, r#"{"theme":"dark"}"#).unwrap();
+
+        let result = replace_file(&path, |file| {
+            file.write_all(b"{\"the")?;
+            Err(std::io::Error::other("disk full"))
+        });
+
+        assert!(result.unwrap_err().contains("disk full"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"theme":"dark"}"#);
+        assert!(!tmp_path(&path).exists());
+
+        replace_file(&path, |file| file.write_all(b"{}")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+
+        let _ = fs::remove_file(&path);
+    }
+}
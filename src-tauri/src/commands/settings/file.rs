@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::Manager;
+
+use super::{atomic, schema};
+use crate::debug_log;
+
+/// Last settings map that parsed, per file, so a broken external edit
+/// doesn't make every setting read fail until it's fixed
+static LAST_GOOD: Mutex<Option<HashMap<PathBuf, HashMap<String, Value>>>> = Mutex::new(None);
+
+/// Resolve the settings.json path inside the app data directory.
+pub(super) fn settings_file_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {e}"))?;
+
+    if !data_dir.exists() {
+        fs::create_dir_all(&data_dir).map_err(|e| format!("Failed to create app data dir: {e}"))?;
+    }
+
+    Ok(data_dir.join("settings.json"))
+}
+
+/// Read the entire settings map from disk, migrating an old string-only file.
+pub(super) fn read_settings(path: &PathBuf) -> Result<HashMap<String, Value>, String> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read settings file: {e}"))?;
+
+    schema::parse(&contents)
+}
+
+/// Read the settings map, falling back to the last copy that parsed when
+/// the file has been corrupted since.
+pub(super) fn read_settings_or_last_good(path: &PathBuf) -> Result<HashMap<String, Value>, String> {
+    let mut last_good = LAST_GOOD
+        .lock()
+        .map_err(|e| format!("Settings cache lock error: {e}"))?;
+    let cache = last_good.get_or_insert_with(HashMap::new);
+    match read_settings(path) {
+        Ok(map) => {
+            cache.insert(path.clone(), map.clone());
+            Ok(map)
+        }
+        Err(e) => match cache.get(path) {
+            Some(map) => {
+                debug_log::log("SETTINGS", &format!("WARN: {e}; using last good settings"));
+                Ok(map.clone())
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Write the entire settings map to disk. Serialization happens before the
+/// file is touched, and the swap is atomic, so a failure keeps the old file.
+pub(super) fn write_settings(path: &Path, map: &HashMap<String, Value>) -> Result<(), String> {
+    let json = schema::to_json(map)?;
+
+    atomic::replace_file(path, |file| file.write_all(json.as_bytes()))
+}
+
+/// Record `map` as the last good copy, e.g. after an explicit reload.
+pub(super) fn remember_good(path: PathBuf, map: HashMap<String, Value>) -> Result<(), String> {
+    LAST_GOOD
+        .lock()
+        .map_err(|e| format!("Settings cache lock error: {e}"))?
+        .get_or_insert_with(HashMap::new)
+        .insert(path, map);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_settings_nonexistent_returns_empty() {
+        let path = PathBuf::from("/tmp/_central_test_nonexistent.json");
+        let result = read_settings(&path).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn read_write_roundtrip() {
+        let path = PathBuf::from("/tmp/_central_test_settings_roundtrip.json");
+
+        let mut map = HashMap::new();
+        map.insert("foo".to_string(), Value::from("bar"));
+
+        write_settings(&path, &map).unwrap();
+
+        let loaded = read_settings(&path).unwrap();
+        assert_eq!(loaded.get("foo").unwrap(), "bar");
+
+        // Clean up
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn legacy_string_file_is_migrated_on_next_write() {
+        let path =
+            std::env::temp_dir().join(format!("central_settings_{}.json", uuid::Uuid::new_v4()));
+        fs::write(&path, r#"{ "theme": "dark" }"#).unwrap();
+
+        let mut map = read_settings(&path).unwrap();
+        assert_eq!(map.get("theme"), Some(&Value::from("dark")));
+        map.insert("font_size".to_string(), Value::from(14));
+        write_settings(&path, &map).unwrap();
+
+        let on_disk: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(on_disk["version"], 2);
+        assert_eq!(read_settings(&path).unwrap(), map);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_last_good_copy() {
+        let path =
+            std::env::temp_dir().join(format!("central_settings_{}.json", uuid::Uuid::new_v4()));
+        let mut map = HashMap::new();
+        map.insert("theme".to_string(), Value::from("dark"));
+        write_settings(&path, &map).unwrap();
+        assert_eq!(read_settings_or_last_good(&path).unwrap(), map);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert!(read_settings(&path).is_err());
+        assert_eq!(read_settings_or_last_good(&path).unwrap(), map);
+
+        let _ = fs::remove_file(&path);
+    }
+}
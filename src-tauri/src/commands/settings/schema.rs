@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Bumped from the implicit v1: a flat `{ key: "string" }` map
+const SCHEMA_VERSION: u32 = 2;
+
+/// On-disk layout of settings.json. String settings are `Value::String`,
+/// so plain and JSON settings share one map.
+#[derive(Serialize, Deserialize)]
+struct SettingsFile {
+    version: u32,
+    values: HashMap<String, Value>,
+}
+
+/// Parse settings.json, migrating a v1 flat string map on the fly. The
+/// migrated layout is written out by the next save.
+pub(super) fn parse(contents: &str) -> Result<HashMap<String, Value>, String> {
+    let raw: Value = serde_json::from_str(contents)
+        .map_err(|e| format!("Failed to parse settings JSON: {e}"))?;
+    if is_versioned(&raw) {
+        let file: SettingsFile = serde_json::from_value(raw)
+            .map_err(|e| format!("Failed to parse settings JSON: {e}"))?;
+        return Ok(file.values);
+    }
+    let legacy: HashMap<String, String> =
+        serde_json::from_value(raw).map_err(|e| format!("Failed to parse settings JSON: {e}"))?;
+    Ok(legacy
+        .into_iter()
+        .map(|(key, value)| (key, Value::String(value)))
+        .collect())
+}
+
+pub(super) fn to_json(values: &HashMap<String, Value>) -> Result<String, String> {
+    let file = SettingsFile {
+        version: SCHEMA_VERSION,
+        values: values.clone(),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize settings: {e}"))
+}
+
+fn is_versioned(raw: &Value) -> bool {
+    raw.get("version").is_some_and(Value::is_u64) && raw.get("values").is_some_and(Value::is_object)
+}
+
+/// A setting as the string API sees it: strings as-is, anything else as JSON text
+pub(super) fn as_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn roundtrips_numbers_booleans_and_nested_objects() {
+        let mut values = HashMap::new();
+        values.insert("limit".to_string(), json!(2.5));
+        values.insert("enabled".to_string(), json!(false));
+        values.insert(
+            "layout".to_string(),
+            json!({ "panes": [1, 2], "split": { "ratio": 0.4 } }),
+        );
+        values.insert("theme".to_string(), json!("dark"));
+
+        let parsed = parse(&to_json(&values).unwrap()).unwrap();
+        assert_eq!(parsed, values);
+    }
+
+    #[test]
+    fn migrates_flat_string_map() {
+        let parsed = parse(r#"{ "theme": "dark", "version": "3" }"#).unwrap();
+        assert_eq!(parsed.get("theme"), Some(&json!("dark")));
+        assert_eq!(parsed.get("version"), Some(&json!("3")));
+        assert!(parse(r#"{ "count": 3 }"#).is_err());
+    }
+
+    #[test]
+    fn string_view_serializes_non_strings() {
+        assert_eq!(as_string(&json!("dark")), "dark");
+        assert_eq!(as_string(&json!({ "a": true })), r#"{"a":true}"#);
+    }
+}
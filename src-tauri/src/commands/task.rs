@@ -0,0 +1,53 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::debug_log;
+use crate::pty::PtyHandle;
+use crate::sidecar::{SidecarCommand, SidecarHandle};
+
+/// What `stop_task` found running and stopped
+#[derive(Debug, Serialize)]
+pub struct StopTaskSummary {
+    /// False when the session had no running worker
+    pub session_aborted: bool,
+    pub terminals_closed: Vec<String>,
+    /// Listed terminals that were already closed
+    pub terminals_missing: Vec<String>,
+}
+
+/// One stop button for a task: abort the agent session and close the
+/// terminals that were running alongside it
+#[tauri::command]
+pub async fn stop_task(
+    sidecar: State<'_, SidecarHandle>,
+    pty: State<'_, PtyHandle>,
+    session_id: String,
+    terminal_ids: Vec<String>,
+) -> Result<StopTaskSummary, String> {
+    debug_log::log(
+        "RUST-CMD",
+        &format!("stop_task: sid={session_id}, terminals={terminal_ids:?}"),
+    );
+
+    let session_aborted = {
+        let mut manager = sidecar
+            .lock()
+            .map_err(|e| format!("Failed to lock sidecar: {e}"))?;
+        let running = manager.active_session_ids().contains(&session_id);
+        let _ = manager.send_command(&SidecarCommand::AbortSession {
+            session_id: session_id.clone(),
+        });
+        manager.remove_session(&session_id);
+        running
+    };
+
+    let mut manager = pty.lock().map_err(|e| format!("PTY lock error: {e}"))?;
+    let (terminals_closed, terminals_missing) =
+        terminal_ids.into_iter().partition(|id| manager.close(id));
+
+    Ok(StopTaskSummary {
+        session_aborted,
+        terminals_closed,
+        terminals_missing,
+    })
+}
@@ -1,10 +1,27 @@
+use std::path::Path;
+
 use tauri::ipc::Channel;
-use tauri::State;
+use tauri::{AppHandle, State};
+
+use crate::commands::files::binary::read_project_bytes;
+use crate::commands::projects::load_recent_projects;
+use crate::commands::settings::{load_setting, store_setting};
+use crate::pty::{
+    PtyEvent, PtyHandle, SpawnConfig, DEFAULT_SCROLLBACK_BYTES, MAX_SCROLLBACK_BYTES,
+    MIN_SCROLLBACK_BYTES,
+};
 
-use crate::pty::{PtyEvent, PtyHandle};
+const CLAUDE_BIN_ENV: &str = "CENTRAL_CLAUDE_BIN";
+const CLAUDE_PATH_SETTING: &str = "claude_path";
+/// "project_root" (default), "last_visited", or "home"
+const DEFAULT_CWD_SETTING: &str = "terminal_default_cwd";
+const LAST_CWD_SETTING: &str = "terminal_last_cwd";
+/// Scrollback bytes for new terminals; unset keeps the built-in default
+const SCROLLBACK_SETTING: &str = "terminal_scrollback_bytes";
 
 #[tauri::command]
 pub fn start_terminal(
+    app: AppHandle,
     session_id: String,
     cwd: String,
     rows: u16,
@@ -12,11 +29,97 @@ pub fn start_terminal(
     on_event: Channel<PtyEvent>,
     pty: State<'_, PtyHandle>,
 ) -> Result<(), String> {
-    let mut manager = pty
-        .lock()
-        .map_err(|e| format!("PTY lock error: {e}"))?;
+    let configured = load_setting(&app, CLAUDE_PATH_SETTING).unwrap_or_default();
+    let claude_command = resolve_claude_command(std::env::var(CLAUDE_BIN_ENV).ok(), configured)?;
+    let cwd = resolve_terminal_cwd(&app, cwd)?;
+    let scrollback_bytes = load_setting(&app, SCROLLBACK_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .and_then(|bytes| check_scrollback_size(bytes).ok())
+        .unwrap_or(DEFAULT_SCROLLBACK_BYTES);
+    let config = SpawnConfig {
+        cwd,
+        rows,
+        cols,
+        command: claude_command,
+        scrollback_bytes,
+    };
 
-    manager.start_terminal(session_id, cwd, rows, cols, on_event)
+    pty.lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?
+        .start_terminal(session_id, config, on_event)
+}
+
+/// Pick the claude binary: `CENTRAL_CLAUDE_BIN`, then the `claude_path` setting,
+/// then plain `claude` resolved by the login shell's PATH. An explicitly
+/// configured path that doesn't exist is an error rather than a silent fallback.
+fn resolve_claude_command(
+    env_value: Option<String>,
+    setting_value: Option<String>,
+) -> Result<String, String> {
+    let configured = env_value
+        .into_iter()
+        .chain(setting_value)
+        .map(|v| v.trim().to_string())
+        .find(|v| !v.is_empty());
+
+    match configured {
+        Some(path) if Path::new(&path).is_file() => Ok(shell_quote(&path)),
+        Some(path) => Err(format!("claude CLI not found at {path}")),
+        None => Ok("claude".to_string()),
+    }
+}
+
+/// An explicit cwd is used as-is (and remembered); an empty one is resolved
+/// from the `terminal_default_cwd` policy. Either way it must be a directory.
+fn resolve_terminal_cwd(app: &AppHandle, cwd: String) -> Result<String, String> {
+    if !cwd.trim().is_empty() {
+        let cwd = ensure_dir(cwd)?;
+        let _ = store_setting(app, LAST_CWD_SETTING, cwd.clone());
+        return Ok(cwd);
+    }
+
+    let strategy = load_setting(app, DEFAULT_CWD_SETTING).unwrap_or_default();
+    let recent_project = load_recent_projects(app)
+        .unwrap_or_default()
+        .into_iter()
+        .next();
+    let last_cwd = load_setting(app, LAST_CWD_SETTING).unwrap_or_default();
+    let home = dirs::home_dir().map(|h| h.to_string_lossy().to_string());
+
+    let picked = pick_default_cwd(strategy.as_deref(), recent_project, last_cwd, home.clone())
+        .ok_or_else(|| "Could not determine a terminal working directory".to_string())?;
+    // A stale remembered path shouldn't block opening a terminal
+    ensure_dir(picked).or_else(|e| home.ok_or(e).and_then(ensure_dir))
+}
+
+/// Apply the cwd policy, falling back to `$HOME` when its source is unset
+fn pick_default_cwd(
+    strategy: Option<&str>,
+    recent_project: Option<String>,
+    last_cwd: Option<String>,
+    home: Option<String>,
+) -> Option<String> {
+    let preferred = match strategy.map(str::trim) {
+        Some("home") => None,
+        Some("last_visited") => last_cwd,
+        _ => recent_project,
+    };
+    preferred.or(home)
+}
+
+fn ensure_dir(path: String) -> Result<String, String> {
+    if Path::new(&path).is_dir() {
+        Ok(path)
+    } else {
+        Err(format!("Terminal directory does not exist: {path}"))
+    }
+}
+
+/// Single-quote a path for `sh -c`, escaping embedded single quotes
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
 }
 
 #[tauri::command]
@@ -32,6 +135,25 @@ pub fn write_terminal_input(
     manager.write_input(&session_id, &data)
 }
 
+/// Feed a project file into the terminal's stdin, like `claude < prompt.txt`.
+/// The file is read here (boundary-checked, size-limited) so the frontend
+/// doesn't have to read and base64-chunk it.
+#[tauri::command]
+pub fn write_terminal_file(
+    session_id: String,
+    project_path: String,
+    file_path: String,
+    pty: State<'_, PtyHandle>,
+) -> Result<(), String> {
+    let bytes = read_project_bytes(&project_path, &file_path)?;
+
+    let mut manager = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.write_bytes(&session_id, &bytes)
+}
+
 #[tauri::command]
 pub fn resize_terminal(
     session_id: String,
@@ -58,3 +180,125 @@ pub fn close_terminal(
     manager.close(&session_id);
     Ok(())
 }
+
+/// Close all terminals running inside a project. Returns how many were closed.
+#[tauri::command]
+pub fn close_terminals_for_project(
+    project_path: String,
+    pty: State<'_, PtyHandle>,
+) -> Result<usize, String> {
+    let mut manager = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?;
+
+    Ok(manager.close_for_project(&project_path))
+}
+
+/// Recent terminal output as text (at most `last_bytes`, capped at the
+/// scrollback size), e.g. to hand a failing build's output to an agent.
+/// Escape sequences are left in; restarting the terminal clears it.
+#[tauri::command]
+pub fn get_terminal_output(
+    session_id: String,
+    last_bytes: Option<usize>,
+    pty: State<'_, PtyHandle>,
+) -> Result<String, String> {
+    let manager = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.output(&session_id, last_bytes.unwrap_or(usize::MAX))
+}
+
+/// Resize a running terminal's scrollback (a build log wants a big one, a
+/// REPL less). Shrinking drops the oldest output. New terminals use the
+/// `terminal_scrollback_bytes` setting.
+#[tauri::command]
+pub fn set_terminal_scrollback(
+    session_id: String,
+    bytes: usize,
+    pty: State<'_, PtyHandle>,
+) -> Result<(), String> {
+    let bytes = check_scrollback_size(bytes)?;
+    let mut manager = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.set_scrollback(&session_id, bytes)
+}
+
+fn check_scrollback_size(bytes: usize) -> Result<usize, String> {
+    match (MIN_SCROLLBACK_BYTES..=MAX_SCROLLBACK_BYTES).contains(&bytes) {
+        true => Ok(bytes),
+        false => Err(format!(
+            "Scrollback must be between {MIN_SCROLLBACK_BYTES} and {MAX_SCROLLBACK_BYTES} bytes"
+        )),
+    }
+}
+
+/// Restart a terminal in place, keeping its session id and event channel
+#[tauri::command]
+pub fn restart_terminal(
+    session_id: String,
+    pty: State<'_, PtyHandle>,
+) -> Result<(), String> {
+    let mut manager = pty
+        .lock()
+        .map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.restart(&session_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_defaults_to_plain_claude() {
+        assert_eq!(resolve_claude_command(None, None).unwrap(), "claude");
+        assert_eq!(
+            resolve_claude_command(Some("  ".to_string()), None).unwrap(),
+            "claude"
+        );
+    }
+
+    #[test]
+    fn resolve_prefers_env_over_setting() {
+        let result = resolve_claude_command(
+            Some("/bin/sh".to_string()),
+            Some("/nonexistent/claude".to_string()),
+        );
+        assert_eq!(result.unwrap(), "'/bin/sh'");
+    }
+
+    #[test]
+    fn resolve_errors_for_missing_configured_path() {
+        let result = resolve_claude_command(None, Some("/nonexistent/claude".to_string()));
+        assert!(result.unwrap_err().contains("claude CLI not found"));
+    }
+
+    #[test]
+    fn pick_default_cwd_follows_strategy() {
+        let project = Some("/p".to_string());
+        let last = Some("/last".to_string());
+        let home = Some("/home/u".to_string());
+
+        let pick = |strategy| pick_default_cwd(strategy, project.clone(), last.clone(), home.clone());
+        assert_eq!(pick(None).as_deref(), Some("/p"));
+        assert_eq!(pick(Some("project_root")).as_deref(), Some("/p"));
+        assert_eq!(pick(Some("last_visited")).as_deref(), Some("/last"));
+        assert_eq!(pick(Some("home")).as_deref(), Some("/home/u"));
+    }
+
+    #[test]
+    fn pick_default_cwd_falls_back_to_home() {
+        let result = pick_default_cwd(Some("last_visited"), None, None, Some("/home/u".to_string()));
+        assert_eq!(result.as_deref(), Some("/home/u"));
+        assert!(ensure_dir("/nonexistent/dir".to_string()).is_err());
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("/a b/it's"), "'/a b/it'\\''s'");
+    }
+}
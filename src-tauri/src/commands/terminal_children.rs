@@ -0,0 +1,28 @@
+use tauri::State;
+
+use crate::pty::children::ChildProcess;
+use crate::pty::PtyHandle;
+
+/// Processes still running under a terminal (dev servers, watchers,
+/// background jobs), so the UI can show what's holding a port
+#[tauri::command]
+pub fn list_terminal_children(
+    session_id: String,
+    pty: State<'_, PtyHandle>,
+) -> Result<Vec<ChildProcess>, String> {
+    let manager = pty.lock().map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.children(&session_id)
+}
+
+/// SIGTERM everything a terminal has started without closing the terminal.
+/// Returns the processes that were signalled.
+#[tauri::command]
+pub fn kill_terminal_children(
+    session_id: String,
+    pty: State<'_, PtyHandle>,
+) -> Result<Vec<ChildProcess>, String> {
+    let manager = pty.lock().map_err(|e| format!("PTY lock error: {e}"))?;
+
+    manager.kill_children(&session_id)
+}
@@ -1,19 +1,142 @@
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-static LOG_FILE: OnceLock<Mutex<std::fs::File>> = OnceLock::new();
+mod level;
+mod rotate;
 
-const LOG_PATH: &str = "/tmp/central-debug.log";
+pub use level::{log_level, set_threshold, LogLevel};
 
-/// Initialize the log file with a Mutex for thread-safe writes
-pub fn init_log_path() {
-    let file = std::fs::File::create(LOG_PATH).expect("cannot create debug log");
-    let mutex = Mutex::new(file);
-    let _ = LOG_FILE.set(mutex);
+static LOG_FILE: OnceLock<Mutex<LogWriter>> = OnceLock::new();
+/// Where the log actually ended up, set once by `init_log_path`
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+const LOG_FILE_NAME: &str = "central-debug.log";
+
+/// How long Info/Debug lines may sit in the buffer; override with CENTRAL_LOG_FLUSH_MS
+const DEFAULT_FLUSH_MS: u64 = 200;
+
+/// Buffered log file plus the time of the last flush
+struct LogWriter {
+    out: BufWriter<std::fs::File>,
+    last_flush: Instant,
+}
+
+impl LogWriter {
+    fn new(file: std::fs::File) -> Self {
+        Self {
+            out: BufWriter::new(file),
+            last_flush: Instant::now(),
+        }
+    }
+
+    fn flush(&mut self) {
+        let _ = self.out.flush();
+        self.last_flush = Instant::now();
+    }
+}
+
+/// Flushes the log when dropped — hold one for the lifetime of the app so
+/// buffered lines survive an unwinding exit
+pub struct FlushGuard;
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        flush();
+    }
+}
+
+/// Batching interval from CENTRAL_LOG_FLUSH_MS (0 = flush every line)
+fn flush_interval() -> Duration {
+    static INTERVAL: OnceLock<Duration> = OnceLock::new();
+    *INTERVAL.get_or_init(|| {
+        let ms = std::env::var("CENTRAL_LOG_FLUSH_MS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_FLUSH_MS);
+        Duration::from_millis(ms)
+    })
+}
+
+/// Errors and warnings are flushed at once so a tail shows them immediately
+fn is_urgent(message: &str) -> bool {
+    let upper = message.to_ascii_uppercase();
+    ["ERROR", "WARN", "FAILED", "PANIC"].iter().any(|m| upper.contains(m))
+}
+
+fn should_flush(message: &str, since_flush: Duration, interval: Duration) -> bool {
+    is_urgent(message) || since_flush >= interval
+}
+
+/// Initialize the log file with a Mutex for thread-safe writes. `dir` is
+/// normally the app data dir; the platform temp dir is used if it's None or
+/// the file can't be created there. Logging stays off if neither works.
+/// The starting level comes from CENTRAL_LOG_LEVEL (default info).
+pub fn init_log_path(dir: Option<PathBuf>) {
+    if let Some(level) = std::env::var("CENTRAL_LOG_LEVEL").ok().and_then(|v| v.parse().ok()) {
+        set_threshold(level);
+    }
+    let candidates = dir.into_iter().chain([std::env::temp_dir()]);
+    let Some((path, file)) = candidates.map(|d| d.join(LOG_FILE_NAME)).find_map(|path| {
+        create_log_file(&path).ok().map(|file| (path, file))
+    }) else {
+        return;
+    };
+
+    if LOG_PATH.set(path).is_ok() && LOG_FILE.set(Mutex::new(LogWriter::new(file))).is_ok() {
+        spawn_flusher();
+        flush_on_panic();
+    }
     log("RUST", "=== Central Debug Log Started ===");
 }
 
+fn create_log_file(path: &Path) -> std::io::Result<std::fs::File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::File::create(path)
+}
+
+/// The log file in use, or None before `init_log_path` (or if it failed)
+pub fn log_path() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+/// Background timer so batched lines never wait longer than the interval
+fn spawn_flusher() {
+    let interval = flush_interval();
+    if interval.is_zero() {
+        return;
+    }
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+        flush();
+    });
+}
+
+/// A panic may abort before any guard runs, so flush from the hook as well.
+/// `try_lock` avoids deadlocking if the panicking thread holds the log.
+fn flush_on_panic() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(Ok(mut writer)) = LOG_FILE.get().map(Mutex::try_lock) {
+            let _ = writeln!(writer.out, "[{}] [RUST] PANIC: {info}", timestamp());
+            writer.flush();
+        }
+        previous(info);
+    }));
+}
+
+/// Write out anything still buffered
+pub fn flush() {
+    if let Some(Ok(mut writer)) = LOG_FILE.get().map(Mutex::lock) {
+        writer.flush();
+    }
+}
+
 fn timestamp() -> String {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -26,8 +149,19 @@ fn timestamp() -> String {
     format!("{hours:02}:{mins:02}:{secs:02}.{millis:03}")
 }
 
-/// Append a log line — Mutex ensures no interleaving from concurrent threads
+/// Append an Info line
 pub fn log(source: &str, message: &str) {
+    log_at(LogLevel::Info, source, message);
+}
+
+/// Append a log line if `level` passes the threshold — Mutex ensures no
+/// interleaving from concurrent threads. The file handle stays open;
+/// Info/Debug lines are batched, errors and warnings are not. Past the size
+/// cap the file is rotated to `central-debug.log.1`.
+pub fn log_at(level: LogLevel, source: &str, message: &str) {
+    if level > log_level() {
+        return;
+    }
     let mutex = match LOG_FILE.get() {
         Some(m) => m,
         None => return,
@@ -36,28 +170,62 @@ pub fn log(source: &str, message: &str) {
     let ts = timestamp();
     let line = format!("[{ts}] [{source}] {message}\n");
 
-    if let Ok(mut guard) = mutex.lock() {
-        // Re-open in append mode each time so we never hold the fd across calls.
-        // The Mutex serialises access; the open+append is atomic on POSIX.
-        if let Ok(mut f) = OpenOptions::new().append(true).open(LOG_PATH) {
-            let _ = f.write_all(line.as_bytes());
-        } else {
-            // Fallback: try writing to the original fd
-            let _ = guard.write_all(line.as_bytes());
+    if let Ok(mut writer) = mutex.lock() {
+        let cap = rotate::max_bytes();
+        rotate::write_rotating(&mut writer, log_path(), &rotate::WRITTEN, line.as_bytes(), cap);
+        let urgent = level <= LogLevel::Warn;
+        if urgent || should_flush(message, writer.last_flush.elapsed(), flush_interval()) {
+            writer.flush();
         }
     }
 }
 
+/// Whether the log file can currently be opened for appending
+pub fn is_writable() -> bool {
+    LOG_FILE.get().is_some()
+        && log_path().is_some_and(|path| OpenOptions::new().append(true).open(path).is_ok())
+}
+
 /// Truncate and reinitialize the log file
 pub fn truncate_log() {
-    if let Ok(f) = std::fs::File::create(LOG_PATH) {
-        if let Some(mutex) = LOG_FILE.get() {
-            if let Ok(mut guard) = mutex.lock() {
-                *guard = f;
-            }
+    truncate_with_marker("=== Central Debug Log Truncated ===");
+}
+
+/// The marker is written while the lock is still held, so no other thread's
+/// line can land ahead of it in the fresh file
+fn truncate_with_marker(marker: &str) {
+    if let Some(Ok(mut guard)) = LOG_FILE.get().map(Mutex::lock) {
+        // Flush first so stale buffered lines don't land past the truncation
+        guard.flush();
+        // If the file can't be recreated, keep writing through the held handle
+        if let Some(Ok(f)) = log_path().map(std::fs::File::create) {
+            *guard = LogWriter::new(f);
+            rotate::WRITTEN.store(0, Ordering::Relaxed);
         }
+        let _ = writeln!(guard.out, "[{}] [RUST] {marker}", timestamp());
+        guard.flush();
     }
-    log("RUST", "=== Central Debug Log Truncated ===");
+}
+
+/// Empty the log without restarting, to isolate a fresh reproduction
+#[tauri::command]
+pub fn clear_debug_log() {
+    truncate_with_marker("=== Central Debug Log Cleared ===");
+}
+
+/// Where the debug log lives, so users can find it for a bug report
+#[tauri::command]
+pub fn debug_log_path() -> Option<String> {
+    log_path().map(|path| path.to_string_lossy().to_string())
+}
+
+/// Change verbosity without a restart: "error", "warn", "info" or "debug"
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), String> {
+    let level: LogLevel = level.parse()?;
+    set_threshold(level);
+    log("RUST", &format!("Log level set to {level:?}"));
+    Ok(())
 }
 
 /// Tauri command so the React frontend can write to the same log file
@@ -69,3 +237,30 @@ pub fn debug_log(source: String, message: String) {
     }
     log(&source, &message);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_log_file_makes_missing_directories() {
+        let dir = std::env::temp_dir().join(format!("central_log_{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nested").join(LOG_FILE_NAME);
+
+        assert!(create_log_file(&path).is_ok());
+        assert!(path.is_file());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn errors_flush_immediately_and_info_waits_for_interval() {
+        let interval = Duration::from_millis(200);
+
+        assert!(should_flush("Failed to lock sidecar", Duration::ZERO, interval));
+        assert!(should_flush("WARN: not a git repo", Duration::ZERO, interval));
+        assert!(!should_flush("worker spawned", Duration::from_millis(50), interval));
+        assert!(should_flush("worker spawned", Duration::from_millis(250), interval));
+        assert!(should_flush("worker spawned", Duration::ZERO, Duration::ZERO));
+    }
+}
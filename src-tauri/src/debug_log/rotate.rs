@@ -0,0 +1,103 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use super::LogWriter;
+
+/// Size cap for the live log; override with CENTRAL_LOG_MAX_BYTES
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// How many rotated files (`.1` newest … `.3` oldest) are kept
+pub(super) const KEEP_ROTATED: usize = 3;
+
+/// Bytes written to the live file, so the cap check never touches `metadata()`
+pub(super) static WRITTEN: AtomicU64 = AtomicU64::new(0);
+
+/// Rotation threshold from CENTRAL_LOG_MAX_BYTES (0 disables rotation)
+pub(super) fn max_bytes() -> u64 {
+    static MAX: OnceLock<u64> = OnceLock::new();
+    *MAX.get_or_init(|| {
+        std::env::var("CENTRAL_LOG_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_MAX_BYTES)
+    })
+}
+
+/// Append `line`, first rotating the file if the line would take it past
+/// `cap`. Called with the log lock held. If rotation fails the line still
+/// goes to the current file.
+pub(super) fn write_rotating(
+    writer: &mut LogWriter,
+    path: Option<&Path>,
+    written: &AtomicU64,
+    line: &[u8],
+    cap: u64,
+) {
+    let len = line.len() as u64;
+    let current = written.load(Ordering::Relaxed);
+    if cap > 0 && current > 0 && current + len > cap {
+        if let Some(path) = path {
+            writer.flush();
+            if let Ok(file) = rotate_files(path, KEEP_ROTATED) {
+                *writer = LogWriter::new(file);
+                written.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+    if writer.out.write_all(line).is_ok() {
+        written.fetch_add(len, Ordering::Relaxed);
+    }
+}
+
+/// Shift `log.1`→`log.2`… (dropping the oldest), move the live file to
+/// `log.1` and return a fresh one in its place
+fn rotate_files(path: &Path, keep: usize) -> std::io::Result<std::fs::File> {
+    let _ = std::fs::remove_file(rotated_path(path, keep));
+    for n in (1..keep).rev() {
+        let from = rotated_path(path, n);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(path, n + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated_path(path, 1))?;
+    std::fs::File::create(path)
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{n}"));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_past_cap_and_keeps_at_most_three_files() {
+        let dir = std::env::temp_dir().join(format!("central_rotate_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("central-debug.log");
+        let mut writer = LogWriter::new(std::fs::File::create(&path).unwrap());
+        let written = AtomicU64::new(0);
+
+        let line = [b'x'; 99].iter().chain(b"\n").copied().collect::<Vec<u8>>();
+        for _ in 0..50 {
+            write_rotating(&mut writer, Some(&path), &written, &line, 1000);
+        }
+        writer.flush();
+
+        assert!(rotated_path(&path, 1).is_file());
+        assert!(rotated_path(&path, 3).is_file());
+        assert!(!rotated_path(&path, 4).exists());
+        assert_eq!(
+            std::fs::metadata(rotated_path(&path, 1)).unwrap().len(),
+            1000
+        );
+        assert!(std::fs::metadata(&path).unwrap().len() <= 1000);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
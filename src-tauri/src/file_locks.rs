@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::clock::now_ms;
+use crate::commands::files::fs_helpers::resolve_inside_project;
+use crate::project_path::{path_string, resolve_project_path};
+
+/// An advisory lock held by one of the app's writers (editor, tool approvals)
+#[derive(Debug, Clone, Serialize)]
+pub struct FileLock {
+    pub project_path: String,
+    pub file_path: String,
+    pub owner: String,
+    pub acquired_at_ms: u64,
+}
+
+/// Tracks advisory file locks so the app's own writers don't clobber each other.
+/// This does not prevent OS-level races — external processes ignore it.
+pub struct FileLockRegistry {
+    locks: HashMap<(String, String), FileLock>,
+}
+
+/// Thread-safe handle to the lock registry
+pub type FileLockHandle = Arc<Mutex<FileLockRegistry>>;
+
+/// Create a new lock registry handle for Tauri state
+pub fn create_file_lock_handle() -> FileLockHandle {
+    Arc::new(Mutex::new(FileLockRegistry::new()))
+}
+
+/// Locks are keyed by the resolved project and the canonical path inside it,
+/// so `src/a.rs`, `./src//a.rs`, `src/../src/a.rs` and the absolute path all
+/// name the same lock. Paths outside the project are rejected.
+fn lock_key(project_path: &str, file_path: &str) -> Result<(String, String), String> {
+    let project = resolve_project_path(project_path)?;
+    let file = resolve_inside_project(&project, file_path)?;
+    let relative = file.strip_prefix(&project).unwrap_or(&file);
+    Ok((path_string(&project), path_string(relative)))
+}
+
+impl FileLockRegistry {
+    pub fn new() -> Self {
+        Self {
+            locks: HashMap::new(),
+        }
+    }
+
+    /// Acquire a lock. Re-acquiring by the same owner is a no-op.
+    pub fn acquire(
+        &mut self,
+        project_path: &str,
+        file_path: &str,
+        owner: &str,
+    ) -> Result<FileLock, String> {
+        let key = lock_key(project_path, file_path)?;
+
+        if let Some(existing) = self.locks.get(&key) {
+            if existing.owner != owner {
+                return Err(format!("File {file_path} is locked by {}", existing.owner));
+            }
+            return Ok(existing.clone());
+        }
+
+        let lock = FileLock {
+            project_path: key.0.clone(),
+            file_path: key.1.clone(),
+            owner: owner.to_string(),
+            acquired_at_ms: now_ms(),
+        };
+        self.locks.insert(key, lock.clone());
+        Ok(lock)
+    }
+
+    /// Release a lock. Only the owner may release it; releasing an unlocked file is a no-op.
+    pub fn release(
+        &mut self,
+        project_path: &str,
+        file_path: &str,
+        owner: &str,
+    ) -> Result<(), String> {
+        let key = lock_key(project_path, file_path)?;
+
+        match self.locks.get(&key) {
+            Some(existing) if existing.owner != owner => {
+                Err(format!("File {file_path} is locked by {}", existing.owner))
+            }
+            Some(_) => {
+                self.locks.remove(&key);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Reject a write when the file is locked by someone other than `owner`
+    pub fn check_write(
+        &self,
+        project_path: &str,
+        file_path: &str,
+        owner: Option<&str>,
+    ) -> Result<(), String> {
+        let key = lock_key(project_path, file_path)?;
+
+        match self.locks.get(&key) {
+            Some(existing) if Some(existing.owner.as_str()) != owner => {
+                Err(format!("File {file_path} is locked by {}", existing.owner))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// List current locks, optionally restricted to one project
+    pub fn list(&self, project_path: Option<&str>) -> Vec<FileLock> {
+        let project = project_path.map(|p| match resolve_project_path(p) {
+            Ok(resolved) => path_string(&resolved),
+            Err(_) => p.trim_end_matches('/').to_string(),
+        });
+        let mut locks: Vec<FileLock> = self
+            .locks
+            .values()
+            .filter(|l| project.as_ref().map_or(true, |p| &l.project_path == p))
+            .cloned()
+            .collect();
+        locks.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        locks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A temp project directory, removed on drop
+    struct Project(std::path::PathBuf);
+
+    impl Project {
+        fn new() -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("central_file_locks_{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(dir.join("src")).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self) -> String {
+            path_string(&self.0)
+        }
+    }
+
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn acquire_then_conflicting_acquire_fails() {
+        let p = Project::new();
+        let mut registry = FileLockRegistry::new();
+        registry.acquire(&p.path(), "a.rs", "editor").unwrap();
+
+        let result = registry.acquire(&p.path(), "a.rs", "agent");
+        assert!(result.unwrap_err().contains("locked by editor"));
+    }
+
+    #[test]
+    fn reacquire_by_same_owner_is_ok() {
+        let p = Project::new();
+        let mut registry = FileLockRegistry::new();
+        registry.acquire(&p.path(), "a.rs", "editor").unwrap();
+        assert!(registry.acquire(&p.path(), "./a.rs", "editor").is_ok());
+        assert_eq!(registry.list(None).len(), 1);
+    }
+
+    #[test]
+    fn spellings_of_one_file_share_a_lock() {
+        let p = Project::new();
+        let mut registry = FileLockRegistry::new();
+        let lock = registry.acquire(&p.path(), "src/a.rs", "editor").unwrap();
+        assert_eq!(lock.file_path, "src/a.rs");
+
+        let absolute = format!("{}/src/a.rs", p.path());
+        for path in [
+            "src/../src/a.rs",
+            "src//a.rs",
+            "./src/a.rs",
+            absolute.as_str(),
+        ] {
+            let err = registry
+                .check_write(&p.path(), path, Some("agent"))
+                .unwrap_err();
+            assert!(err.contains("locked by editor"), "{path}: {err}");
+        }
+        let project_with_slash = format!("{}/", p.path());
+        assert!(registry
+            .check_write(&project_with_slash, "src/a.rs", None)
+            .is_err());
+        assert!(registry
+            .acquire(&p.path(), "../outside.rs", "editor")
+            .is_err());
+    }
+
+    #[test]
+    fn release_requires_owner() {
+        let p = Project::new();
+        let mut registry = FileLockRegistry::new();
+        registry.acquire(&p.path(), "a.rs", "editor").unwrap();
+
+        assert!(registry.release(&p.path(), "a.rs", "agent").is_err());
+        assert!(registry.release(&p.path(), "a.rs", "editor").is_ok());
+        assert!(registry.list(None).is_empty());
+    }
+
+    #[test]
+    fn check_write_rejects_other_owners() {
+        let p = Project::new();
+        let mut registry = FileLockRegistry::new();
+        registry.acquire(&p.path(), "a.rs", "editor").unwrap();
+
+        assert!(registry
+            .check_write(&p.path(), "a.rs", Some("editor"))
+            .is_ok());
+        assert!(registry
+            .check_write(&p.path(), "a.rs", Some("agent"))
+            .is_err());
+        assert!(registry.check_write(&p.path(), "a.rs", None).is_err());
+        assert!(registry.check_write(&p.path(), "b.rs", None).is_ok());
+    }
+
+    #[test]
+    fn list_filters_by_project() {
+        let (p1, p2) = (Project::new(), Project::new());
+        let mut registry = FileLockRegistry::new();
+        registry.acquire(&p1.path(), "a.rs", "editor").unwrap();
+        registry.acquire(&p2.path(), "b.rs", "editor").unwrap();
+
+        let locks = registry.list(Some(&format!("{}/", p1.path())));
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].file_path, "a.rs");
+    }
+}
@@ -1,11 +1,23 @@
 use tauri::Manager;
 use tauri_plugin_sql::{Builder as SqlBuilder, Migration, MigrationKind};
 
+mod clock;
 mod commands;
+mod content_hash;
 mod debug_log;
+mod file_locks;
 mod notifications;
+mod operations;
+mod project_index;
+mod project_path;
+mod projects;
 mod pty;
+mod shell_env;
 mod sidecar;
+mod watcher;
+
+/// How long sessions get to finish when the app quits
+const EXIT_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
 
 fn create_migrations() -> Vec<Migration> {
     vec![
@@ -26,8 +38,9 @@ fn create_migrations() -> Vec<Migration> {
 
 /// Clean up all child processes to prevent orphans on app quit
 fn cleanup_on_exit(app_handle: &tauri::AppHandle) {
-    // Shut down the sidecar (kills the Node.js process + all agent sessions)
+    // Give sessions a moment to finish writing, then kill whatever is left
     if let Some(sidecar) = app_handle.try_state::<sidecar::SidecarHandle>() {
+        sidecar::drain_all(&sidecar, EXIT_DRAIN_TIMEOUT);
         if let Ok(mut manager) = sidecar.lock() {
             manager.shutdown();
         }
@@ -39,10 +52,20 @@ fn cleanup_on_exit(app_handle: &tauri::AppHandle) {
             manager.shutdown();
         }
     }
+
+    // Stop all project watcher threads
+    if let Some(watcher_handle) = app_handle.try_state::<watcher::WatcherHandle>() {
+        if let Ok(mut manager) = watcher_handle.lock() {
+            manager.shutdown();
+        }
+    }
+
+    debug_log::flush();
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let _log_guard = debug_log::FlushGuard;
     let migrations = create_migrations();
 
     tauri::Builder::default()
@@ -54,16 +77,25 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .setup(|app| {
-            debug_log::init_log_path();
+            debug_log::init_log_path(app.path().app_data_dir().ok());
             debug_log::log("RUST", "Tauri app starting up");
+            shell_env::resolve_spawn_path_in_background();
 
             let handle = app.handle().clone();
             let sidecar_handle = sidecar::create_sidecar_handle(handle);
+            sidecar::keep_alive::spawn_idle_reaper(sidecar_handle.clone());
             app.manage(sidecar_handle);
 
             let pty_handle = pty::create_pty_handle();
             app.manage(pty_handle);
 
+            app.manage(sidecar::models::create_model_cache_handle());
+            app.manage(file_locks::create_file_lock_handle());
+            app.manage(operations::create_operation_handle());
+            app.manage(projects::create_projects_handle());
+            app.manage(project_index::create_project_index_handle());
+            app.manage(watcher::create_watcher_handle(app.handle().clone()));
+
             debug_log::log("RUST", "Sidecar + PTY handles created and managed");
 
             if let Err(e) = notifications::init() {
@@ -80,26 +112,113 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::greet,
-            commands::agents::start_agent_session,
+            commands::health::backend_health,
+            commands::health::inspect_worker_env,
+            commands::health::sidecar_stats,
+            commands::health::verify_worker,
+            commands::health::detect_shell,
+            commands::about::about_info,
+            commands::agents::start::start_agent_session,
             commands::agents::send_agent_message,
             commands::agents::abort_agent_session,
             commands::agents::end_agent_session,
+            commands::agents::cancel_agent_tool,
+            commands::agents::reprompt_agent_session,
+            commands::agents::control::prewarm_session,
+            commands::agents::control::restart_agent_session,
+            commands::agents::control::forget_agent_session,
+            commands::agents::control::set_agent_model,
+            commands::agents::control::set_session_permission_mode,
+            commands::agents::control::interrupt_agent_session,
+            commands::agents::control::drain_all_sessions,
+            commands::task::stop_task,
+            commands::agents::diagnostics::export_session_diagnostics,
             commands::agents::respond_tool_approval,
             commands::agents::list_agent_sessions,
+            commands::agents::get_session_metadata,
+            commands::agents::session_store::get_session_conversation,
+            commands::agents::get_cost_by_model,
+            commands::agents::get_session_usage,
+            commands::agents::get_session_budget,
+            commands::agents::get_session_tool_history,
+            commands::agents::get_session_tool_counts,
+            commands::agents::changes::get_session_diff,
+            commands::agents::snapshot::revert_session,
+            commands::agents::replay_agent_events,
+            commands::agents::list_available_models,
             commands::files::tree::get_file_tree,
+            commands::files::list::list_project_files,
+            commands::files::language::detect_project_language,
+            commands::files::tail::tail_file,
+            commands::files::watch::watch_file,
+            commands::files::watch::unwatch_file,
+            commands::files::hash::hash_file,
             commands::files::status::get_git_status,
+            commands::files::status::get_multi_git_status,
             commands::files::status::get_file_content,
+            commands::files::content::get_files_content,
+            commands::files::format::detect_file_format,
             commands::files::status::write_file,
+            commands::files::transaction::write_files,
+            commands::files::binary::write_file_bytes,
+            commands::files::binary::get_file_bytes,
+            commands::files::locks::acquire_file_lock,
+            commands::files::locks::release_file_lock,
+            commands::files::locks::list_file_locks,
             commands::files::diff::get_diff,
+            commands::files::staged::get_staged_diff,
+            commands::files::env_vars::list_env_requirements,
+            commands::files::diff_stats::get_diff_summary,
+            commands::files::diff_stats::git_compare_branches,
+            commands::files::history::git_file_history,
+            commands::files::renames::get_renames,
+            commands::files::diff_stats::git_diff_against_branch,
+            commands::files::git_info::git_head_info,
+            commands::files::git_info::git_default_branch,
+            commands::files::git_info::is_git_repo,
+            commands::files::dirty::git_is_clean,
+            commands::files::repo_state::git_operation_state,
+            commands::files::repo_path::repo_relative_path,
+            commands::files::git_identity::get_git_identity,
+            commands::files::git_identity::set_git_identity,
+            commands::files::index::build_project_index,
+            commands::files::index::query_project_index,
+            commands::files::clean::clean_project_artifacts,
+            commands::files::size::get_dir_size,
+            commands::files::disk::disk_space,
             commands::files::discover::list_project_directories,
+            commands::projects::open_project,
+            commands::projects::close_project,
+            commands::projects::list_open_projects,
+            commands::projects::get_recent_projects,
+            commands::projects::validate_project_path,
+            commands::sessions::list_all_sessions,
             commands::settings::get_setting,
             commands::settings::set_setting,
+            commands::settings::get_settings,
+            commands::settings::set_settings,
+            commands::settings::delete_setting,
+            commands::settings::list_settings,
+            commands::settings::get_setting_json,
+            commands::settings::set_setting_json,
+            commands::settings::reload_settings,
             commands::notifications::send_native_notification,
             commands::terminal::start_terminal,
             commands::terminal::write_terminal_input,
+            commands::terminal::write_terminal_file,
             commands::terminal::resize_terminal,
             commands::terminal::close_terminal,
+            commands::terminal::restart_terminal,
+            commands::terminal::get_terminal_output,
+            commands::terminal::set_terminal_scrollback,
+            commands::terminal::close_terminals_for_project,
+            commands::terminal_children::list_terminal_children,
+            commands::terminal_children::kill_terminal_children,
             debug_log::debug_log,
+            operations::cancel_operation,
+            debug_log::clear_debug_log,
+            debug_log::debug_log_path,
+            debug_log::set_log_level,
         ])
         .run(tauri::generate_context!())
         .expect("error while running Central");
@@ -1,23 +1,239 @@
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+mod throttle;
+
+use throttle::Admission;
+
+pub use throttle::DEFAULT_WINDOW;
+
+/// A native notification as a process to spawn: osascript on macOS,
+/// notify-send on Linux, a PowerShell toast on Windows
+struct NotifyCommand {
+    backend: &'static str,
+    program: &'static str,
+    args: Vec<String>,
+    /// Passed via the environment so text never has to survive shell quoting
+    env: Vec<(&'static str, String)>,
+}
+
 pub fn init() -> Result<(), String> {
-    crate::debug_log::log("NOTIFY", "Notification system initialized (osascript)");
+    throttle::init();
+    let backend = notify_command("", "").map(|c| c.backend)?;
+    crate::debug_log::log(
+        "NOTIFY",
+        &format!("Notification system initialized ({backend})"),
+    );
     Ok(())
 }
 
-pub fn send(title: &str, body: &str, _session_id: &str) -> Result<(), String> {
-    let title = title.replace('\\', "\\\\").replace('"', "\\\"");
-    let body = body.replace('\\', "\\\\").replace('"', "\\\"");
+/// Show a notification. An exact repeat from `session_id` within `window`
+/// is dropped, and further notifications in that window are folded into one
+/// shown when it ends.
+pub fn send(title: &str, body: &str, session_id: &str, window: Duration) -> Result<(), String> {
+    match throttle::admission(session_id, title, body, window) {
+        Admission::Show => dispatch(title, body),
+        Admission::Drop => {
+            crate::debug_log::log(
+                "NOTIFY",
+                &format!("Suppressed repeat notification for {session_id}: {title}"),
+            );
+            Ok(())
+        }
+        Admission::Hold { flush_in } => {
+            crate::debug_log::log(
+                "NOTIFY",
+                &format!("Holding notification for {session_id} to coalesce: {title}"),
+            );
+            if let Some(delay) = flush_in {
+                schedule_flush(session_id.to_string(), delay);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Show the folded notification for a session's burst once its window ends
+fn schedule_flush(session_id: String, delay: Duration) {
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        if let Some((title, body)) = throttle::flush(&session_id) {
+            if let Err(e) = dispatch(&title, &body) {
+                crate::debug_log::log("NOTIFY", &format!("WARN: {e}"));
+            }
+        }
+    });
+}
+
+fn dispatch(title: &str, body: &str) -> Result<(), String> {
+    let notify = notify_command(title, body)?;
+
+    let mut command = Command::new(notify.program);
+    command
+        .args(&notify.args)
+        .envs(notify.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    hide_console_window(&mut command);
+
+    command
+        .spawn()
+        .map_err(|e| format!("{} failed: {e}", notify.backend))?;
+
+    crate::debug_log::log(
+        "NOTIFY",
+        &format!("Sent notification via {}", notify.backend),
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn notify_command(title: &str, body: &str) -> Result<NotifyCommand, String> {
+    Ok(osascript_command(title, body))
+}
+
+#[cfg(target_os = "windows")]
+fn notify_command(title: &str, body: &str) -> Result<NotifyCommand, String> {
+    Ok(toast_command(title, body))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn notify_command(title: &str, body: &str) -> Result<NotifyCommand, String> {
+    Ok(notify_send_command(title, body))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn notify_command(_title: &str, _body: &str) -> Result<NotifyCommand, String> {
+    Err("Native notifications are not supported on this platform".to_string())
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn osascript_command(title: &str, body: &str) -> NotifyCommand {
     let script = format!(
         "display notification \"{}\" with title \"{}\"",
-        body, title,
+        applescript_escape(body),
+        applescript_escape(title),
     );
+    NotifyCommand {
+        backend: "osascript",
+        program: "/usr/bin/osascript",
+        args: vec!["-e".to_string(), script],
+        env: Vec::new(),
+    }
+}
 
-    std::process::Command::new("/usr/bin/osascript")
-        .args(["-e", &script])
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn()
-        .map_err(|e| format!("osascript failed: {e}"))?;
+#[cfg(any(target_os = "macos", test))]
+fn applescript_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
 
-    Ok(())
+/// notify-send gets its arguments directly (no shell), but notification
+/// daemons render the body as markup
+#[cfg(any(all(unix, not(target_os = "macos")), test))]
+fn notify_send_command(title: &str, body: &str) -> NotifyCommand {
+    NotifyCommand {
+        backend: "notify-send",
+        program: "notify-send",
+        args: vec![
+            "--app-name=Central".to_string(),
+            "--".to_string(),
+            title.to_string(),
+            markup_escape(body),
+        ],
+        env: Vec::new(),
+    }
+}
+
+/// Builds the toast XML from already-escaped env values, so title and body
+/// are never parsed as PowerShell or XML
+#[cfg(any(target_os = "windows", test))]
+const TOAST_SCRIPT: &str = r#"
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null
+[Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] > $null
+$xml = New-Object Windows.Data.Xml.Dom.XmlDocument
+$xml.LoadXml("<toast><visual><binding template='ToastGeneric'><text>$env:CENTRAL_NOTIFY_TITLE</text><text>$env:CENTRAL_NOTIFY_BODY</text></binding></visual></toast>")
+$toast = [Windows.UI.Notifications.ToastNotification]::new($xml)
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier($env:CENTRAL_NOTIFY_APP_ID).Show($toast)
+"#;
+
+/// Toasts need a registered AppUserModelID; PowerShell's always is
+#[cfg(any(target_os = "windows", test))]
+const POWERSHELL_APP_ID: &str =
+    r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+
+#[cfg(any(target_os = "windows", test))]
+fn toast_command(title: &str, body: &str) -> NotifyCommand {
+    NotifyCommand {
+        backend: "windows-toast",
+        program: "powershell.exe",
+        args: ["-NoProfile", "-NonInteractive", "-Command", TOAST_SCRIPT]
+            .map(String::from)
+            .to_vec(),
+        env: vec![
+            ("CENTRAL_NOTIFY_TITLE", markup_escape(title)),
+            ("CENTRAL_NOTIFY_BODY", markup_escape(body)),
+            ("CENTRAL_NOTIFY_APP_ID", POWERSHELL_APP_ID.to_string()),
+        ],
+    }
+}
+
+/// XML/Pango escaping shared by the toast XML and notify-send bodies
+#[cfg(any(not(target_os = "macos"), test))]
+fn markup_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Don't flash a console window for each toast
+#[cfg(windows)]
+fn hide_console_window(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    command.creation_flags(CREATE_NO_WINDOW);
+}
+
+#[cfg(not(windows))]
+fn hide_console_window(_command: &mut Command) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osascript_escapes_quotes_and_backslashes() {
+        let cmd = osascript_command("Say \"hi\"", r"C:\path");
+        assert_eq!(
+            cmd.args[1],
+            r#"display notification "C:\\path" with title "Say \"hi\"""#
+        );
+    }
+
+    #[test]
+    fn notify_send_escapes_body_markup_and_ends_options() {
+        let cmd = notify_send_command("-title", "a < b & <b>c</b>");
+        assert_eq!(cmd.program, "notify-send");
+        assert_eq!(
+            cmd.args[1..],
+            ["--", "-title", "a &lt; b &amp; &lt;b&gt;c&lt;/b&gt;"]
+        );
+    }
+
+    #[test]
+    fn toast_passes_escaped_text_through_env() {
+        let cmd = toast_command("it's <done>", "$(rm -rf /)");
+        assert!(!cmd
+            .args
+            .iter()
+            .any(|a| a.contains("done") || a.contains("rm -rf")));
+        assert!(cmd
+            .env
+            .contains(&("CENTRAL_NOTIFY_TITLE", "it&apos;s &lt;done&gt;".to_string())));
+        assert!(cmd
+            .env
+            .contains(&("CENTRAL_NOTIFY_BODY", "$(rm -rf /)".to_string())));
+    }
 }
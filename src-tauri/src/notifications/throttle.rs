@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default suppression window; override with the `notification_throttle_ms` setting
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(5);
+
+/// A notification as shown to the user: title and body
+type Note = (String, String);
+
+/// What one session has notified since its first notification in the window
+struct Burst {
+    started: Instant,
+    shown: Note,
+    /// Distinct notifications waiting to be folded into one at window end
+    held: Vec<Note>,
+}
+
+/// What `send` should do with a notification
+#[derive(Debug, PartialEq)]
+pub(super) enum Admission {
+    Show,
+    /// Exact repeat of one already shown or held in this window
+    Drop,
+    /// Part of a burst; `flush_in` is set for the first held notification,
+    /// after which `flush` returns the folded one
+    Hold {
+        flush_in: Option<Duration>,
+    },
+}
+
+/// Open bursts per session, set up by `init`
+static BURSTS: OnceLock<Mutex<HashMap<String, Burst>>> = OnceLock::new();
+
+pub(super) fn init() {
+    let _ = BURSTS.set(Mutex::new(HashMap::new()));
+}
+
+/// How to handle this notification now. Always `Show` before `init`
+/// (nothing to throttle against).
+pub(super) fn admission(session_id: &str, title: &str, body: &str, window: Duration) -> Admission {
+    let note = (title.to_string(), body.to_string());
+    match BURSTS.get().map(Mutex::lock) {
+        Some(Ok(mut bursts)) => admit(&mut bursts, session_id, note, Instant::now(), window),
+        _ => Admission::Show,
+    }
+}
+
+/// The notification standing in for a session's held burst, if any
+pub(super) fn flush(session_id: &str) -> Option<Note> {
+    let mut bursts = BURSTS.get()?.lock().ok()?;
+    take_held(&mut bursts, session_id)
+}
+
+/// The first notification from a session is shown; the rest of its burst
+/// (until `window` after the first) is held and folded into one, so a
+/// "completed" followed by "failed" still reaches the user, just once the
+/// window ends. Exact repeats within the window are dropped. Finished
+/// bursts are pruned so ended sessions don't accumulate.
+fn admit(
+    bursts: &mut HashMap<String, Burst>,
+    session_id: &str,
+    note: Note,
+    now: Instant,
+    window: Duration,
+) -> Admission {
+    bursts.retain(|_, b| now.duration_since(b.started) < window || !b.held.is_empty());
+    let Some(burst) = bursts.get_mut(session_id) else {
+        let burst = Burst {
+            started: now,
+            shown: note,
+            held: Vec::new(),
+        };
+        bursts.insert(session_id.to_string(), burst);
+        return Admission::Show;
+    };
+
+    if burst.shown == note || burst.held.contains(&note) {
+        return Admission::Drop;
+    }
+    burst.held.push(note);
+    let flush_in = (burst.held.len() == 1).then(|| window.saturating_sub(now - burst.started));
+    Admission::Hold { flush_in }
+}
+
+/// A single held notification is shown as is; more become "N updates"
+fn take_held(bursts: &mut HashMap<String, Burst>, session_id: &str) -> Option<Note> {
+    let burst = bursts.remove(session_id)?;
+    let count = burst.held.len();
+    let (title, body) = burst.held.into_iter().last()?;
+    Some(match count {
+        1 => (title, body),
+        _ => (title, format!("{count} updates, latest: {body}")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(title: &str, body: &str) -> Note {
+        (title.to_string(), body.to_string())
+    }
+
+    #[test]
+    fn suppresses_repeats_within_window() {
+        let mut bursts = HashMap::new();
+        let start = Instant::now();
+        let window = Duration::from_secs(5);
+        let done = || note("Done", "body");
+
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(
+            admit(&mut bursts, "s1", done(), at(0), window),
+            Admission::Show
+        );
+        assert_eq!(
+            admit(&mut bursts, "s1", done(), at(1), window),
+            Admission::Drop
+        );
+        assert_eq!(
+            admit(&mut bursts, "s2", done(), at(1), window),
+            Admission::Show
+        );
+        assert_eq!(
+            admit(&mut bursts, "s1", done(), at(6), window),
+            Admission::Show
+        );
+        for _ in 0..2 {
+            let admitted = admit(&mut bursts, "s3", done(), start, Duration::ZERO);
+            assert_eq!(admitted, Admission::Show);
+        }
+    }
+
+    #[test]
+    fn a_burst_of_distinct_notifications_folds_into_one() {
+        let mut bursts = HashMap::new();
+        let start = Instant::now();
+        let window = Duration::from_secs(5);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let first = admit(
+            &mut bursts,
+            "s1",
+            note("Agent", "Edited a.rs"),
+            at(0),
+            window,
+        );
+        assert_eq!(first, Admission::Show);
+        assert_eq!(
+            admit(
+                &mut bursts,
+                "s1",
+                note("Agent", "Edited b.rs"),
+                at(1),
+                window
+            ),
+            Admission::Hold {
+                flush_in: Some(Duration::from_secs(4))
+            }
+        );
+        assert_eq!(
+            admit(
+                &mut bursts,
+                "s1",
+                note("Agent", "Edited b.rs"),
+                at(2),
+                window
+            ),
+            Admission::Drop
+        );
+        for (secs, body) in [(2, "Edited c.rs"), (3, "Ran tests")] {
+            let held = admit(&mut bursts, "s1", note("Agent", body), at(secs), window);
+            assert_eq!(held, Admission::Hold { flush_in: None });
+        }
+        // Held bursts outlive the window until they are flushed
+        let other = admit(&mut bursts, "s2", note("Agent", "x"), at(9), window);
+        assert_eq!(other, Admission::Show);
+
+        assert_eq!(
+            take_held(&mut bursts, "s1"),
+            Some(note("Agent", "3 updates, latest: Ran tests"))
+        );
+        assert_eq!(take_held(&mut bursts, "s1"), None);
+    }
+
+    #[test]
+    fn a_single_held_notification_is_shown_unchanged() {
+        let mut bursts = HashMap::new();
+        let now = Instant::now();
+        let window = Duration::from_secs(5);
+
+        let completed = admit(&mut bursts, "s1", note("Completed", "ok"), now, window);
+        assert_eq!(completed, Admission::Show);
+        assert!(matches!(
+            admit(&mut bursts, "s1", note("Failed", "ok"), now, window),
+            Admission::Hold { flush_in: Some(_) }
+        ));
+
+        assert_eq!(take_held(&mut bursts, "s1"), Some(note("Failed", "ok")));
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tauri::State;
+
+/// Cancellation flags for long-running commands (tree walks, dir sizes),
+/// keyed by a request id the frontend picks, so one `cancel_operation`
+/// command can stop any of them
+#[derive(Default)]
+pub struct OperationRegistry {
+    flags: HashMap<String, Arc<AtomicBool>>,
+}
+
+/// Thread-safe handle to the operation registry
+pub type OperationHandle = Arc<Mutex<OperationRegistry>>;
+
+/// Create a new operation registry handle for Tauri state
+pub fn create_operation_handle() -> OperationHandle {
+    Arc::new(Mutex::new(OperationRegistry::default()))
+}
+
+impl OperationRegistry {
+    /// Signal the operation to stop. Returns false if it isn't running.
+    pub fn cancel(&self, request_id: &str) -> bool {
+        match self.flags.get(request_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Stop a tree walk or dir-size started with this `request_id`; it fails
+/// with "Cancelled". Returns false if nothing with that id is running.
+/// Async so it never queues behind work on the main thread.
+#[tauri::command]
+pub async fn cancel_operation(
+    operations: State<'_, OperationHandle>,
+    request_id: String,
+) -> Result<bool, String> {
+    let cancelled = operations
+        .lock()
+        .map_err(|e| format!("Operation registry error: {e}"))?
+        .cancel(&request_id);
+    crate::debug_log::log(
+        "RUST-CMD",
+        &format!("cancel_operation: {request_id} (running={cancelled})"),
+    );
+    Ok(cancelled)
+}
+
+/// A running operation's cancel flag; unregisters itself when dropped
+pub struct Operation {
+    handle: OperationHandle,
+    request_id: Option<String>,
+    flag: Arc<AtomicBool>,
+}
+
+impl Operation {
+    /// Register `request_id` for the length of an operation. Without an id the
+    /// operation simply can't be cancelled. Reusing a live id replaces it.
+    pub fn begin(handle: &OperationHandle, request_id: Option<String>) -> Result<Self, String> {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Some(id) = &request_id {
+            handle
+                .lock()
+                .map_err(|e| format!("Operation registry error: {e}"))?
+                .flags
+                .insert(id.clone(), flag.clone());
+        }
+        Ok(Self {
+            handle: handle.clone(),
+            request_id,
+            flag,
+        })
+    }
+
+    pub fn cancel_flag(&self) -> &AtomicBool {
+        &self.flag
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        let (Some(id), Ok(mut registry)) = (&self.request_id, self.handle.lock()) else {
+            return;
+        };
+        // Only remove our own entry, not a newer operation that reused the id
+        if registry
+            .flags
+            .get(id)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &self.flag))
+        {
+            registry.flags.remove(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_reaches_the_running_operation_until_it_ends() {
+        let handle = create_operation_handle();
+        let op = Operation::begin(&handle, Some("tree-1".to_string())).unwrap();
+
+        assert!(handle.lock().unwrap().cancel("tree-1"));
+        assert!(op.cancel_flag().load(Ordering::Relaxed));
+        assert!(!handle.lock().unwrap().cancel("other"));
+
+        drop(op);
+        assert!(!handle.lock().unwrap().cancel("tree-1"));
+    }
+
+    #[test]
+    fn finished_operation_leaves_a_reused_id_alone() {
+        let handle = create_operation_handle();
+        let first = Operation::begin(&handle, Some("r".to_string())).unwrap();
+        let second = Operation::begin(&handle, Some("r".to_string())).unwrap();
+
+        drop(first);
+        assert!(handle.lock().unwrap().cancel("r"));
+        assert!(second.cancel_flag().load(Ordering::Relaxed));
+    }
+}
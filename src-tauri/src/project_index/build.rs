@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use super::{IndexedSymbol, ProjectIndex};
+use crate::clock::now_ms;
+use crate::commands::files::list::walk_files;
+
+/// Files bigger than this are indexed by path only
+const MAX_SYMBOL_SCAN_BYTES: u64 = 512 * 1024;
+
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "mjs", "py", "go", "rb", "java", "kt", "swift",
+];
+
+/// Line prefixes that introduce a top-level definition, and the kind they mean.
+/// Longer prefixes come first so `pub fn` wins over `fn`.
+const SYMBOL_PREFIXES: &[(&str, &str)] = &[
+    ("pub(crate) fn ", "function"),
+    ("pub async fn ", "function"),
+    ("pub fn ", "function"),
+    ("async fn ", "function"),
+    ("fn ", "function"),
+    ("pub struct ", "struct"),
+    ("struct ", "struct"),
+    ("pub enum ", "enum"),
+    ("enum ", "enum"),
+    ("pub trait ", "trait"),
+    ("trait ", "trait"),
+    ("export default function ", "function"),
+    ("export async function ", "function"),
+    ("export function ", "function"),
+    ("async function ", "function"),
+    ("function ", "function"),
+    ("export class ", "class"),
+    ("class ", "class"),
+    ("export interface ", "interface"),
+    ("interface ", "interface"),
+    ("export type ", "type"),
+    ("type ", "type"),
+    ("export const ", "const"),
+    ("def ", "function"),
+    ("func ", "function"),
+];
+
+/// Walk the project once, skipping the usual build dirs and anything git ignores
+pub fn build_index(project_path: &str) -> Result<ProjectIndex, String> {
+    let root = Path::new(project_path);
+    if !root.is_dir() {
+        return Err(format!("Path does not exist: {project_path}"));
+    }
+
+    let mut index = ProjectIndex::default();
+    for (path, rel) in walk_files(root, false) {
+        index.symbols.extend(scan_symbols(&path, &rel));
+        index.files.push(rel);
+    }
+
+    index.files.sort();
+    index.built_at_ms = now_ms();
+    Ok(index)
+}
+
+fn scan_symbols(path: &Path, rel: &str) -> Vec<IndexedSymbol> {
+    let is_source = path
+        .extension()
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()));
+    let small = std::fs::metadata(path).is_ok_and(|m| m.len() <= MAX_SYMBOL_SCAN_BYTES);
+    if !is_source || !small {
+        return vec![];
+    }
+
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return vec![];
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            parse_symbol(line).map(|(name, kind)| IndexedSymbol {
+                name,
+                kind: kind.to_string(),
+                path: rel.to_string(),
+                line: i + 1,
+            })
+        })
+        .collect()
+}
+
+/// Only unindented lines count, which keeps methods and locals out
+fn parse_symbol(line: &str) -> Option<(String, &'static str)> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (prefix, kind) = SYMBOL_PREFIXES.iter().find(|(p, _)| line.starts_with(p))?;
+    let name: String = line[prefix.len()..]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    (!name.is_empty()).then_some((name, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_symbol_reads_top_level_definitions() {
+        assert_eq!(
+            parse_symbol("pub fn build_index(x: u8) {"),
+            Some(("build_index".to_string(), "function"))
+        );
+        assert_eq!(
+            parse_symbol("export class Store {"),
+            Some(("Store".to_string(), "class"))
+        );
+        assert_eq!(
+            parse_symbol("struct Point;"),
+            Some(("Point".to_string(), "struct"))
+        );
+        assert_eq!(parse_symbol("    fn method(&self) {}"), None);
+        assert_eq!(parse_symbol("let x = 1;"), None);
+    }
+
+    #[test]
+    fn build_index_collects_files_and_symbols() {
+        let temp =
+            std::env::temp_dir().join(format!("central_index_build_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        std::fs::create_dir_all(temp.join("node_modules")).unwrap();
+        std::fs::write(temp.join("src").join("lib.rs"), "pub fn hello() {}\n").unwrap();
+        std::fs::write(temp.join("README.md"), "fn not_code() {}\n").unwrap();
+        std::fs::write(temp.join("node_modules").join("x.js"), "").unwrap();
+
+        let index = build_index(&temp.to_string_lossy()).unwrap();
+        assert_eq!(index.files, vec!["README.md", "src/lib.rs"]);
+        assert_eq!(index.symbols.len(), 1);
+        assert_eq!(index.symbols[0].name, "hello");
+        assert_eq!(index.symbols[0].line, 1);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use super::{IndexMatch, ProjectIndex};
+
+/// Characters after which a match counts as the start of a word
+const BOUNDARIES: &[char] = &['/', '_', '-', '.', ' '];
+
+/// Fuzzy-match `query` against file paths and symbol names, best first
+pub fn query_index(index: &ProjectIndex, query: &str, limit: usize) -> Vec<IndexMatch> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let files = index.files.iter().filter_map(|path| {
+        score(&query, path).map(|score| IndexMatch {
+            kind: "file".to_string(),
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            path: path.clone(),
+            line: None,
+            score,
+        })
+    });
+    let symbols = index.symbols.iter().filter_map(|sym| {
+        score(&query, &sym.name).map(|score| IndexMatch {
+            kind: sym.kind.clone(),
+            name: sym.name.clone(),
+            path: sym.path.clone(),
+            line: Some(sym.line),
+            score,
+        })
+    });
+
+    let mut matches: Vec<IndexMatch> = files.chain(symbols).collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.path.len().cmp(&b.path.len()))
+            .then(a.path.cmp(&b.path))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Subsequence match score: rewards consecutive runs, word starts, and hits in
+/// the file name; penalises long candidates. None when `query` isn't a subsequence.
+fn score(query: &str, candidate: &str) -> Option<i64> {
+    let lower = candidate.to_lowercase();
+    let name_start = lower.rfind('/').map_or(0, |i| i + 1);
+    let mut total: i64 = 0;
+    let mut prev_match: Option<usize> = None;
+    let mut chars = lower.char_indices().peekable();
+
+    for q in query.chars() {
+        let (idx, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        total += 1;
+        if prev_match.is_some_and(|p| idx == p + 1) {
+            total += 5;
+        }
+        let at_boundary = idx == 0
+            || lower[..idx].ends_with(BOUNDARIES)
+            || candidate
+                .get(idx..)
+                .is_some_and(|s| s.starts_with(char::is_uppercase));
+        if at_boundary {
+            total += 3;
+        }
+        if idx >= name_start {
+            total += 2;
+        }
+        prev_match = Some(idx);
+    }
+
+    Some(total * 10 - candidate.len() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::IndexedSymbol;
+    use super::*;
+
+    fn index() -> ProjectIndex {
+        ProjectIndex {
+            files: vec![
+                "src/components/FileTree.tsx".to_string(),
+                "src/features/files/api.ts".to_string(),
+                "README.md".to_string(),
+            ],
+            symbols: vec![IndexedSymbol {
+                name: "build_tree_recursive".to_string(),
+                kind: "function".to_string(),
+                path: "src-tauri/src/commands/files/tree.rs".to_string(),
+                line: 53,
+            }],
+            built_at_ms: 0,
+        }
+    }
+
+    #[test]
+    fn score_requires_subsequence() {
+        assert!(score("ftr", "FileTree.tsx").is_some());
+        assert!(score("xyz", "FileTree.tsx").is_none());
+    }
+
+    #[test]
+    fn query_ranks_file_name_matches_first() {
+        let results = query_index(&index(), "filetree", 10);
+        assert_eq!(results[0].path, "src/components/FileTree.tsx");
+        assert_eq!(results[0].kind, "file");
+    }
+
+    #[test]
+    fn query_matches_symbols_and_respects_limit() {
+        let results = query_index(&index(), "btr", 10);
+        assert!(results
+            .iter()
+            .any(|m| m.name == "build_tree_recursive" && m.line == Some(53)));
+        assert_eq!(query_index(&index(), "s", 1).len(), 1);
+        assert!(query_index(&index(), "  ", 10).is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+mod build;
+mod fuzzy;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+pub use build::build_index;
+pub use fuzzy::query_index;
+
+/// A top-level definition found by the keyword scan
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexedSymbol {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub line: usize,
+}
+
+/// Every non-ignored file path in a project plus its top-level symbols
+#[derive(Debug, Clone, Default)]
+pub struct ProjectIndex {
+    pub files: Vec<String>,
+    pub symbols: Vec<IndexedSymbol>,
+    pub built_at_ms: u64,
+}
+
+/// One fuzzy-match hit — a file, or a symbol with its line
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexMatch {
+    pub kind: String,
+    pub name: String,
+    pub path: String,
+    pub line: Option<usize>,
+    pub score: i64,
+}
+
+/// Indexes by project path. Entries are dropped when the watcher sees changes.
+pub type ProjectIndexHandle = Arc<Mutex<HashMap<String, ProjectIndex>>>;
+
+/// Create an empty index cache for Tauri state
+pub fn create_project_index_handle() -> ProjectIndexHandle {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+/// Forget a project's index so the next query rebuilds it
+pub fn invalidate(handle: &ProjectIndexHandle, project_path: &str) {
+    if let Ok(mut indexes) = handle.lock() {
+        indexes.remove(project_path);
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// Why a project path was rejected, serialized as `{ code, message }`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum ProjectPathError {
+    Empty(String),
+    /// Relative paths would resolve against the app's own working directory
+    NotAbsolute(String),
+    NotFound(String),
+    NotADirectory(String),
+    /// Exists but can't be resolved (permissions, broken symlink, ...)
+    Inaccessible(String),
+}
+
+impl ProjectPathError {
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Empty(m)
+            | Self::NotAbsolute(m)
+            | Self::NotFound(m)
+            | Self::NotADirectory(m)
+            | Self::Inaccessible(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for ProjectPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// Callers that only report a message keep using `Result<_, String>`
+impl From<ProjectPathError> for String {
+    fn from(err: ProjectPathError) -> Self {
+        err.message().to_string()
+    }
+}
+
+/// The one place a `project_path` from the frontend is turned into a real
+/// directory: expands a leading `~`, requires an absolute path, resolves
+/// symlinks and `..`, and checks that it's an existing directory
+pub fn resolve_project_path(project_path: &str) -> Result<PathBuf, ProjectPathError> {
+    let trimmed = project_path.trim();
+    if trimmed.is_empty() {
+        return Err(ProjectPathError::Empty("Project path is empty".to_string()));
+    }
+
+    let expanded = expand_home(trimmed, dirs::home_dir());
+    if !expanded.is_absolute() {
+        return Err(ProjectPathError::NotAbsolute(format!(
+            "Project path must be absolute or start with ~: {project_path}"
+        )));
+    }
+
+    let canonical = expanded.canonicalize().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            ProjectPathError::NotFound(format!("Project path does not exist: {project_path}"))
+        }
+        _ => ProjectPathError::Inaccessible(format!("Invalid project path {project_path}: {e}")),
+    })?;
+    if !canonical.is_dir() {
+        return Err(ProjectPathError::NotADirectory(format!(
+            "Not a directory: {project_path}"
+        )));
+    }
+    Ok(canonical)
+}
+
+fn expand_home(path: &str, home: Option<PathBuf>) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return PathBuf::from(path),
+    };
+    match home {
+        Some(home) => home.join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Lossy string form of a resolved path, as the commands pass paths around
+pub fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_home_only_for_a_bare_tilde_prefix() {
+        let home = Some(PathBuf::from("/home/u"));
+        assert_eq!(expand_home("~", home.clone()), PathBuf::from("/home/u"));
+        assert_eq!(
+            expand_home("~/code/app", home.clone()),
+            PathBuf::from("/home/u/code/app")
+        );
+        assert_eq!(
+            expand_home("~other/app", home.clone()),
+            PathBuf::from("~other/app")
+        );
+        assert_eq!(expand_home("/abs", home), PathBuf::from("/abs"));
+    }
+
+    #[test]
+    fn resolves_directories_and_classifies_failures() {
+        let temp =
+            std::env::temp_dir().join(format!("central_project_path_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("app")).unwrap();
+        std::fs::write(temp.join("file.txt"), "").unwrap();
+        let path = |rel: &str| path_string(&temp.join(rel));
+
+        let resolved = resolve_project_path(&format!("  {}/app/../app ", temp.display())).unwrap();
+        assert_eq!(resolved, temp.join("app").canonicalize().unwrap());
+
+        assert!(matches!(
+            resolve_project_path(" "),
+            Err(ProjectPathError::Empty(_))
+        ));
+        assert!(matches!(
+            resolve_project_path("app"),
+            Err(ProjectPathError::NotAbsolute(_))
+        ));
+        assert!(matches!(
+            resolve_project_path(&path("gone")),
+            Err(ProjectPathError::NotFound(_))
+        ));
+        assert!(matches!(
+            resolve_project_path(&path("file.txt")),
+            Err(ProjectPathError::NotADirectory(_))
+        ));
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::commands::files::types::GitStatusInfo;
+
+/// A project the user currently has open, with its warmed git status
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenProject {
+    pub project_path: String,
+    pub opened_at_ms: u64,
+    pub git_status: Option<GitStatusInfo>,
+}
+
+/// Tracks which projects are open so lifecycle teardown has one source of truth
+pub struct ProjectRegistry {
+    open: HashMap<String, OpenProject>,
+}
+
+/// Thread-safe handle to the project registry
+pub type ProjectsHandle = Arc<Mutex<ProjectRegistry>>;
+
+/// Create a new project registry handle for Tauri state
+pub fn create_projects_handle() -> ProjectsHandle {
+    Arc::new(Mutex::new(ProjectRegistry::new()))
+}
+
+impl ProjectRegistry {
+    pub fn new() -> Self {
+        Self {
+            open: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, project: OpenProject) {
+        self.open.insert(project.project_path.clone(), project);
+    }
+
+    pub fn remove(&mut self, project_path: &str) -> Option<OpenProject> {
+        self.open.remove(project_path)
+    }
+
+    pub fn list(&self) -> Vec<OpenProject> {
+        let mut projects: Vec<OpenProject> = self.open.values().cloned().collect();
+        projects.sort_by(|a, b| a.project_path.cmp(&b.project_path));
+        projects
+    }
+}
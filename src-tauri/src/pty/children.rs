@@ -0,0 +1,123 @@
+//! Processes a terminal has started (dev servers, watchers, background jobs),
+//! found by walking the process table down from the PTY's child
+
+use std::collections::{HashMap, VecDeque};
+use std::process::Command;
+
+use serde::Serialize;
+
+/// A descendant of a terminal's shell
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ChildProcess {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Full command line as `ps` reports it
+    pub command: String,
+}
+
+/// Every live descendant of `root`, parents before their children
+#[cfg(unix)]
+pub fn descendants(root: u32) -> Result<Vec<ChildProcess>, String> {
+    let output = Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,command="])
+        .output()
+        .map_err(|e| format!("Failed to run ps: {e}"))?;
+    if !output.status.success() {
+        return Err(format!("ps exited with {}", output.status));
+    }
+    let table = parse_ps(&String::from_utf8_lossy(&output.stdout));
+    Ok(descendants_in(&table, root))
+}
+
+#[cfg(not(unix))]
+pub fn descendants(_root: u32) -> Result<Vec<ChildProcess>, String> {
+    Err("Listing terminal child processes is not supported on this platform".to_string())
+}
+
+/// Send SIGTERM to each process, giving servers a chance to release ports
+#[cfg(unix)]
+pub fn terminate(processes: &[ChildProcess]) -> Result<(), String> {
+    if processes.is_empty() {
+        return Ok(());
+    }
+    let status = Command::new("kill")
+        .arg("-TERM")
+        .args(processes.iter().map(|p| p.pid.to_string()))
+        .status()
+        .map_err(|e| format!("Failed to run kill: {e}"))?;
+    // A process that exited since it was listed makes kill fail; that's fine
+    if !status.success() {
+        crate::debug_log::log("PTY", &format!("kill -TERM exited with {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn terminate(_processes: &[ChildProcess]) -> Result<(), String> {
+    Err("Killing terminal child processes is not supported on this platform".to_string())
+}
+
+fn parse_ps(output: &str) -> Vec<ChildProcess> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            let command = fields.collect::<Vec<_>>().join(" ");
+            Some(ChildProcess { pid, ppid, command })
+        })
+        .collect()
+}
+
+/// Breadth-first walk from `root`; `root` itself is not included
+fn descendants_in(table: &[ChildProcess], root: u32) -> Vec<ChildProcess> {
+    let mut by_parent: HashMap<u32, Vec<&ChildProcess>> = HashMap::new();
+    for process in table {
+        by_parent.entry(process.ppid).or_default().push(process);
+    }
+
+    let mut found: Vec<ChildProcess> = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    while let Some(pid) = queue.pop_front() {
+        for child in by_parent.get(&pid).into_iter().flatten() {
+            // Guard against pid reuse producing a cycle
+            if child.pid != root && !found.iter().any(|p| p.pid == child.pid) {
+                queue.push_back(child.pid);
+                found.push((*child).clone());
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_only_the_subtree_of_root() {
+        let table = parse_ps(
+            "    1     0 /sbin/init\n  100     1 /bin/zsh -l -c claude\n  \
+             200   100 npm run dev\n  300   200 node server.js --port 3000\n  \
+             400     1 unrelated\n garbage line\n",
+        );
+        assert_eq!(table.len(), 5);
+
+        let found = descendants_in(&table, 100);
+        let pids: Vec<u32> = found.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, [200, 300]);
+        assert_eq!(found[1].command, "node server.js --port 3000");
+        assert!(descendants_in(&table, 400).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finds_a_real_child_process() {
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let found = descendants(std::process::id()).unwrap();
+        assert!(found.iter().any(|p| p.pid == child.id()));
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
@@ -1,29 +1,16 @@
 use std::collections::HashMap;
-use std::io::{Read, Write};
-use std::thread;
+use std::io::Write;
 
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
-use portable_pty::{native_pty_system, CommandBuilder, PtySize, MasterPty, Child};
+use portable_pty::PtySize;
 use tauri::ipc::Channel;
 
-use super::types::PtyEvent;
+use super::children::{descendants, terminate, ChildProcess};
+use super::session::{canonical_or_raw, spawn_session, PtySession, SpawnConfig};
+use super::types::{PtyEvent, TerminalInfo};
 use crate::debug_log;
 
-/// One PTY session
-struct PtySession {
-    master: Box<dyn MasterPty + Send>,
-    child: Box<dyn Child + Send + Sync>,
-    writer: Box<dyn Write + Send>,
-}
-
-impl PtySession {
-    fn kill(&mut self) {
-        let _ = self.child.kill();
-        let _ = self.child.wait();
-    }
-}
-
 /// Manages PTY sessions, one per terminal session
 pub struct PtyManager {
     sessions: HashMap<String, PtySession>,
@@ -36,13 +23,11 @@ impl PtyManager {
         }
     }
 
-    /// Start a new PTY running `claude` CLI via the user's login shell
+    /// Start a new PTY running `config.command` via the user's login shell
     pub fn start_terminal(
         &mut self,
         session_id: String,
-        cwd: String,
-        rows: u16,
-        cols: u16,
+        config: SpawnConfig,
         channel: Channel<PtyEvent>,
     ) -> Result<(), String> {
         // If session already exists, close the old one first (handles StrictMode re-mounts)
@@ -51,105 +36,54 @@ impl PtyManager {
             self.close(&session_id);
         }
 
-        let pty_system = native_pty_system();
-
-        let size = PtySize {
-            rows,
-            cols,
-            pixel_width: 0,
-            pixel_height: 0,
-        };
-
-        let pair = pty_system
-            .openpty(size)
-            .map_err(|e| format!("Failed to open PTY: {e}"))?;
-
-        // Spawn claude through the user's login shell so PATH is properly set up
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
-        debug_log::log("PTY", &format!("Using shell: {shell} for session {session_id}"));
+        let session = spawn_session(&session_id, config, channel)?;
 
-        let mut cmd = CommandBuilder::new(&shell);
-        cmd.args(&["-l", "-c", "claude"]);
-        cmd.cwd(&cwd);
+        debug_log::log(
+            "PTY",
+            &format!("Started terminal: {session_id} in {}", session.config.cwd),
+        );
+        self.sessions.insert(session_id, session);
 
-        let child = pair
-            .slave
-            .spawn_command(cmd)
-            .map_err(|e| format!("Failed to spawn claude via {shell}: {e}"))?;
+        Ok(())
+    }
 
-        // Drop slave — we only need the master side
-        drop(pair.slave);
+    /// Kill the session's process and spawn a fresh one with the same cwd, size,
+    /// command and channel, so the UI tab survives the restart
+    pub fn restart(&mut self, session_id: &str) -> Result<(), String> {
+        let mut old = self
+            .sessions
+            .remove(session_id)
+            .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
+        old.kill();
 
-        let reader = pair
-            .master
-            .try_clone_reader()
-            .map_err(|e| format!("Failed to clone PTY reader: {e}"))?;
-
-        let writer = pair
-            .master
-            .take_writer()
-            .map_err(|e| format!("Failed to take PTY writer: {e}"))?;
-
-        let sid = session_id.clone();
-
-        // Spawn reader thread: reads raw bytes, base64-encodes, sends via Channel
-        thread::spawn(move || {
-            let mut reader = reader;
-            let mut buf = [0u8; 4096];
-            loop {
-                match reader.read(&mut buf) {
-                    Ok(0) => {
-                        debug_log::log("PTY", &format!("EOF on reader for {sid}"));
-                        let _ = channel.send(PtyEvent::Exit { code: 0 });
-                        break;
-                    }
-                    Ok(n) => {
-                        let encoded = BASE64.encode(&buf[..n]);
-                        if channel.send(PtyEvent::Output { data: encoded }).is_err() {
-                            debug_log::log("PTY", &format!("Channel closed for {sid}"));
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        debug_log::log("PTY", &format!("Read error for {sid}: {e}"));
-                        let _ = channel.send(PtyEvent::Error {
-                            message: format!("Read error: {e}"),
-                        });
-                        break;
-                    }
-                }
-            }
-            debug_log::log("PTY", &format!("Reader thread exiting for {sid}"));
-        });
-
-        debug_log::log("PTY", &format!("Started terminal: {session_id} in {cwd}"));
-
-        self.sessions.insert(
-            session_id,
-            PtySession {
-                master: pair.master,
-                child,
-                writer,
-            },
-        );
+        // Announce before spawning so the UI clears its scrollback ahead of new output
+        let _ = old.channel.send(PtyEvent::Started);
+        let session = spawn_session(session_id, old.config.clone(), old.channel.clone())?;
+        self.sessions.insert(session_id.to_string(), session);
 
+        debug_log::log("PTY", &format!("Restarted terminal: {session_id}"));
         Ok(())
     }
 
     /// Write base64-encoded input to a PTY session
     pub fn write_input(&mut self, session_id: &str, data: &str) -> Result<(), String> {
+        let bytes = BASE64
+            .decode(data)
+            .map_err(|e| format!("Base64 decode error: {e}"))?;
+
+        self.write_bytes(session_id, &bytes)
+    }
+
+    /// Write raw bytes to a PTY session's stdin
+    pub fn write_bytes(&mut self, session_id: &str, bytes: &[u8]) -> Result<(), String> {
         let session = self
             .sessions
             .get_mut(session_id)
             .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
 
-        let bytes = BASE64
-            .decode(data)
-            .map_err(|e| format!("Base64 decode error: {e}"))?;
-
         session
             .writer
-            .write_all(&bytes)
+            .write_all(bytes)
             .map_err(|e| format!("Write error: {e}"))?;
 
         session
@@ -164,7 +98,7 @@ impl PtyManager {
     pub fn resize(&mut self, session_id: &str, rows: u16, cols: u16) -> Result<(), String> {
         let session = self
             .sessions
-            .get(session_id)
+            .get_mut(session_id)
             .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
 
         session
@@ -177,17 +111,108 @@ impl PtyManager {
             })
             .map_err(|e| format!("Resize error: {e}"))?;
 
+        // Restarts should come back at the current size, not the original one
+        session.config.rows = rows;
+        session.config.cols = cols;
         Ok(())
     }
 
-    /// Close a PTY session and kill the process
-    pub fn close(&mut self, session_id: &str) {
-        if let Some(mut session) = self.sessions.remove(session_id) {
-            session.kill();
-            debug_log::log("PTY", &format!("Closed terminal: {session_id}"));
+    /// Close a PTY session and kill the process. Returns false if it wasn't open.
+    pub fn close(&mut self, session_id: &str) -> bool {
+        let Some(mut session) = self.sessions.remove(session_id) else {
+            return false;
+        };
+        session.kill();
+        debug_log::log("PTY", &format!("Closed terminal: {session_id}"));
+        true
+    }
+
+    /// Close every terminal whose cwd is inside `project_path`. Returns how many.
+    pub fn close_for_project(&mut self, project_path: &str) -> usize {
+        let root = canonical_or_raw(project_path);
+        let ids: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.cwd_root.starts_with(&root))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in &ids {
+            self.close(id);
+        }
+        ids.len()
+    }
+
+    /// Every live terminal with its cwd, start time and process id
+    pub fn list(&self) -> Vec<TerminalInfo> {
+        self.sessions
+            .iter()
+            .map(|(id, session)| TerminalInfo {
+                session_id: id.clone(),
+                cwd: session.config.cwd.clone(),
+                started_at_ms: session.started_at_ms,
+                pid: session.child.process_id(),
+            })
+            .collect()
+    }
+
+    /// Processes started from a terminal that are still running
+    pub fn children(&self, session_id: &str) -> Result<Vec<ChildProcess>, String> {
+        let session = self
+            .sessions
+            .get(session_id)
+            .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
+        match session.child.process_id() {
+            Some(pid) => descendants(pid),
+            None => Ok(Vec::new()),
         }
     }
 
+    /// SIGTERM every process started from a terminal, leaving the terminal
+    /// itself running. Returns the processes that were signalled.
+    pub fn kill_children(&self, session_id: &str) -> Result<Vec<ChildProcess>, String> {
+        let children = self.children(session_id)?;
+        terminate(&children)?;
+        debug_log::log("PTY", &format!("Sent SIGTERM to {} children of {session_id}", children.len()));
+        Ok(children)
+    }
+
+    /// Up to `last_bytes` of a terminal's recent output as text
+    pub fn output(&self, session_id: &str, last_bytes: usize) -> Result<String, String> {
+        let session = self
+            .sessions
+            .get(session_id)
+            .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
+
+        let scrollback = session
+            .scrollback
+            .lock()
+            .map_err(|e| format!("PTY scrollback lock error: {e}"))?;
+        Ok(scrollback.tail_text(last_bytes))
+    }
+
+    /// Resize a terminal's scrollback, trimming the oldest output if it shrinks.
+    /// The size carries over to restarts.
+    pub fn set_scrollback(&mut self, session_id: &str, bytes: usize) -> Result<(), String> {
+        let session = self
+            .sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("PTY session not found: {session_id}"))?;
+
+        session.config.scrollback_bytes = bytes;
+        session
+            .scrollback
+            .lock()
+            .map_err(|e| format!("PTY scrollback lock error: {e}"))?
+            .set_capacity(bytes);
+        Ok(())
+    }
+
+    /// Number of live terminal sessions
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
     /// Shut down all PTY sessions
     pub fn shutdown(&mut self) {
         let ids: Vec<String> = self.sessions.keys().cloned().collect();
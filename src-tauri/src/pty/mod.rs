@@ -1,9 +1,14 @@
+pub mod children;
 pub mod manager;
+mod scrollback;
+mod session;
 pub mod types;
 
 use std::sync::{Arc, Mutex};
 
 pub use manager::PtyManager;
+pub use scrollback::{DEFAULT_SCROLLBACK_BYTES, MAX_SCROLLBACK_BYTES, MIN_SCROLLBACK_BYTES};
+pub use session::SpawnConfig;
 pub use types::PtyEvent;
 
 /// Thread-safe handle to the PTY manager
@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Raw output kept per terminal for `get_terminal_output`
+pub const DEFAULT_SCROLLBACK_BYTES: usize = 256 * 1024;
+
+/// Bounds for `set_terminal_scrollback` and the `terminal_scrollback_bytes` setting
+pub const MIN_SCROLLBACK_BYTES: usize = 1024;
+pub const MAX_SCROLLBACK_BYTES: usize = 64 * 1024 * 1024;
+
+/// The most recent raw output of one terminal process, oldest byte first
+pub struct Scrollback {
+    bytes: VecDeque<u8>,
+    capacity: usize,
+}
+
+/// Shared between a session and its reader thread
+pub type ScrollbackHandle = Arc<Mutex<Scrollback>>;
+
+impl Scrollback {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bytes: VecDeque::with_capacity(capacity.min(DEFAULT_SCROLLBACK_BYTES)),
+            capacity,
+        }
+    }
+
+    /// Change how much output is kept, dropping the oldest bytes when shrinking
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let overflow = self.bytes.len().saturating_sub(capacity);
+        self.bytes.drain(..overflow);
+        self.bytes.shrink_to(capacity.min(DEFAULT_SCROLLBACK_BYTES));
+    }
+
+    pub fn push(&mut self, data: &[u8]) {
+        let data = &data[data.len().saturating_sub(self.capacity)..];
+        let overflow = (self.bytes.len() + data.len()).saturating_sub(self.capacity);
+        self.bytes.drain(..overflow);
+        self.bytes.extend(data);
+    }
+
+    /// Up to `last_bytes` of the newest output, decoded as UTF-8 text
+    pub fn tail_text(&self, last_bytes: usize) -> String {
+        let start = self.bytes.len().saturating_sub(last_bytes);
+        let tail: Vec<u8> = self.bytes.range(start..).copied().collect();
+        decode_tail(&tail)
+    }
+}
+
+/// Decode a window cut out of a byte stream. A multi-byte character split at
+/// either edge is dropped rather than shown as U+FFFD: leading continuation
+/// bytes are skipped, and an incomplete trailing sequence is carried over
+/// (left out) until the rest arrives.
+fn decode_tail(bytes: &[u8]) -> String {
+    let skip = bytes
+        .iter()
+        .take(3)
+        .take_while(|&&b| b & 0xC0 == 0x80)
+        .count();
+    let body = &bytes[skip..];
+    let end = match std::str::from_utf8(body) {
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        _ => body.len(),
+    };
+    String::from_utf8_lossy(&body[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_newest_bytes_and_drops_split_characters() {
+        let mut scrollback = Scrollback::new(8);
+        scrollback.push(b"hello ");
+        scrollback.push("wörld".as_bytes());
+
+        // Only the last 8 bytes are kept: "o wörld" is 8 bytes
+        assert_eq!(scrollback.tail_text(100), "o wörld");
+        // Cutting inside "ö" skips its continuation byte
+        assert_eq!(scrollback.tail_text(4), "rld");
+
+        scrollback.set_capacity(3);
+        assert_eq!(scrollback.tail_text(100), "rld");
+
+        let mut partial = Scrollback::new(16);
+        partial.push(&"ok é".as_bytes()[..4]);
+        assert_eq!(partial.tail_text(16), "ok ");
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use tauri::ipc::Channel;
+
+use super::scrollback::{Scrollback, ScrollbackHandle};
+use super::types::PtyEvent;
+use crate::clock::now_ms;
+use crate::debug_log;
+use crate::shell_env::spawn_path;
+
+/// Everything needed to (re)spawn a terminal's process
+#[derive(Clone)]
+pub struct SpawnConfig {
+    pub cwd: String,
+    pub rows: u16,
+    pub cols: u16,
+    pub command: String,
+    /// Scrollback size, kept across restarts
+    pub scrollback_bytes: usize,
+}
+
+/// One PTY session
+pub struct PtySession {
+    pub master: Box<dyn MasterPty + Send>,
+    pub child: Box<dyn Child + Send + Sync>,
+    pub writer: Box<dyn Write + Send>,
+    pub config: SpawnConfig,
+    /// Canonical cwd, used to find a project's terminals
+    pub cwd_root: PathBuf,
+    pub channel: Channel<PtyEvent>,
+    /// When the current process was spawned (reset by a restart)
+    pub started_at_ms: u64,
+    /// Recent output of the current process (a restart starts it empty)
+    pub scrollback: ScrollbackHandle,
+    /// Set before an intentional kill so the reader stays quiet about the exit
+    retired: Arc<AtomicBool>,
+}
+
+impl PtySession {
+    pub fn kill(&mut self) {
+        self.retired.store(true, Ordering::SeqCst);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Spawn `config.command` via the user's login shell so PATH is properly set up
+pub fn spawn_session(
+    session_id: &str,
+    config: SpawnConfig,
+    channel: Channel<PtyEvent>,
+) -> Result<PtySession, String> {
+    let pair = native_pty_system()
+        .openpty(PtySize {
+            rows: config.rows,
+            cols: config.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        })
+        .map_err(|e| format!("Failed to open PTY: {e}"))?;
+
+    let (cmd, shell) = shell_command(session_id, &config);
+    let child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| format!("Failed to spawn {} via {shell}: {e}", config.command))?;
+
+    // Drop slave — we only need the master side
+    drop(pair.slave);
+
+    let reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to clone PTY reader: {e}"))?;
+
+    let writer = pair
+        .master
+        .take_writer()
+        .map_err(|e| format!("Failed to take PTY writer: {e}"))?;
+
+    let retired = Arc::new(AtomicBool::new(false));
+    let scrollback = start_reader(session_id, reader, &channel, &retired, &config);
+
+    let cwd_root = canonical_or_raw(&config.cwd);
+    Ok(PtySession {
+        master: pair.master,
+        child,
+        writer,
+        cwd_root,
+        config,
+        channel,
+        started_at_ms: now_ms(),
+        scrollback,
+        retired,
+    })
+}
+
+/// `$SHELL -l -c <command>` in the session's cwd, with the resolved login
+/// PATH. Returns the shell too, for error messages.
+fn shell_command(session_id: &str, config: &SpawnConfig) -> (CommandBuilder, String) {
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
+    debug_log::log(
+        "PTY",
+        &format!("Using shell: {shell} for session {session_id}"),
+    );
+
+    let mut cmd = CommandBuilder::new(&shell);
+    cmd.args(["-l", "-c", &config.command]);
+    cmd.cwd(&config.cwd);
+    cmd.env("PATH", spawn_path());
+    (cmd, shell)
+}
+
+/// Start the reader thread with an empty scrollback of the configured size,
+/// returning the scrollback it fills
+fn start_reader(
+    session_id: &str,
+    reader: Box<dyn Read + Send>,
+    channel: &Channel<PtyEvent>,
+    retired: &Arc<AtomicBool>,
+    config: &SpawnConfig,
+) -> ScrollbackHandle {
+    let scrollback = Arc::new(Mutex::new(Scrollback::new(config.scrollback_bytes)));
+    spawn_reader(
+        session_id.to_string(),
+        reader,
+        channel.clone(),
+        Arc::clone(retired),
+        Arc::clone(&scrollback),
+    );
+    scrollback
+}
+
+/// Canonicalize when possible so symlinked project paths still match
+pub fn canonical_or_raw(path: &str) -> PathBuf {
+    Path::new(path)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(path))
+}
+
+/// Reader thread: reads raw bytes, keeps them in the scrollback,
+/// base64-encodes, sends via Channel
+fn spawn_reader(
+    sid: String,
+    mut reader: Box<dyn Read + Send>,
+    channel: Channel<PtyEvent>,
+    retired: Arc<AtomicBool>,
+    scrollback: ScrollbackHandle,
+) {
+    thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        loop {
+            let result = reader.read(&mut buf);
+            // A restarted session shares the channel; don't leak the old process's exit
+            if retired.load(Ordering::SeqCst) {
+                break;
+            }
+            match result {
+                Ok(0) => {
+                    debug_log::log("PTY", &format!("EOF on reader for {sid}"));
+                    let _ = channel.send(PtyEvent::Exit { code: 0 });
+                    break;
+                }
+                Ok(n) => {
+                    if let Ok(mut kept) = scrollback.lock() {
+                        kept.push(&buf[..n]);
+                    }
+                    let encoded = BASE64.encode(&buf[..n]);
+                    if channel.send(PtyEvent::Output { data: encoded }).is_err() {
+                        debug_log::log("PTY", &format!("Channel closed for {sid}"));
+                        break;
+                    }
+                }
+                Err(e) => {
+                    debug_log::log("PTY", &format!("Read error for {sid}: {e}"));
+                    let _ = channel.send(PtyEvent::Error {
+                        message: format!("Read error: {e}"),
+                    });
+                    break;
+                }
+            }
+        }
+        debug_log::log("PTY", &format!("Reader thread exiting for {sid}"));
+    });
+}
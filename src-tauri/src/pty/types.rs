@@ -1,5 +1,14 @@
 use serde::Serialize;
 
+/// A live terminal, for process listings
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminalInfo {
+    pub session_id: String,
+    pub cwd: String,
+    pub started_at_ms: u64,
+    pub pid: Option<u32>,
+}
+
 /// Events sent from PTY sessions to the frontend via Tauri Channel
 #[derive(Clone, Serialize)]
 #[serde(tag = "type")]
@@ -8,6 +17,8 @@ pub enum PtyEvent {
     Output { data: String },
     /// Process exited with a code
     Exit { code: i32 },
+    /// A fresh process was spawned in place of the old one (restart)
+    Started,
     /// Error occurred
     Error { message: String },
 }
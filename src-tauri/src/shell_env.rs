@@ -0,0 +1,257 @@
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::debug_log;
+
+/// Slow rc files shouldn't hold up startup forever
+const LOGIN_SHELL_TIMEOUT: Duration = Duration::from_secs(5);
+/// Brackets $PATH so banners printed by interactive rc files can be ignored
+const PATH_MARKER: &str = "__CENTRAL_PATH__";
+
+static SPAWN_PATH: OnceLock<OsString> = OnceLock::new();
+
+/// The user's shell and which of its startup files exist, to explain why the
+/// in-app environment differs from a terminal
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ShellInfo {
+    /// None on Windows, where no login shell is consulted
+    pub path: Option<String>,
+    pub name: Option<String>,
+    pub profile_files: Vec<String>,
+}
+
+/// Start resolving `spawn_path` on a background thread so a slow login
+/// shell doesn't hold up the window. Spawns that come first wait for it.
+pub fn resolve_spawn_path_in_background() {
+    std::thread::spawn(|| {
+        spawn_path();
+    });
+}
+
+/// PATH for spawned workers and terminals: the login shell's entries merged
+/// with ours, so they can find `node`/`claude` (GUI apps on macOS start with
+/// a minimal PATH). Resolved once; callers block until the login shell has
+/// answered or timed out. Passed to each child rather than set on this
+/// process, since changing the environment while other threads run is unsound.
+pub fn spawn_path() -> &'static OsString {
+    SPAWN_PATH.get_or_init(|| {
+        let current = std::env::var_os("PATH").unwrap_or_default();
+        let Some(login_path) = capture_login_shell_path() else {
+            return current;
+        };
+        let merged = merge_path_lists(&OsString::from(login_path), &current);
+        debug_log::log(
+            "ENV",
+            &format!("Using login shell PATH: {}", merged.to_string_lossy()),
+        );
+        merged
+    })
+}
+
+fn capture_login_shell_path() -> Option<String> {
+    if cfg!(windows) {
+        return None;
+    }
+    let shell = login_shell();
+    let script = path_script(&shell);
+    let mut child = Command::new(&shell)
+        .args(["-ilc", &script])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| debug_log::log("ENV", &format!("Failed to run {shell}: {e}")))
+        .ok()?;
+
+    let mut stdout = child.stdout.take()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut out = String::new();
+        let _ = stdout.read_to_string(&mut out);
+        let _ = tx.send(out);
+    });
+
+    let output = rx.recv_timeout(LOGIN_SHELL_TIMEOUT).ok();
+    let _ = child.kill();
+    let _ = child.wait();
+    if output.is_none() {
+        debug_log::log("ENV", &format!("{shell} did not report PATH in time"));
+    }
+    output.and_then(|out| extract_marked_path(&out))
+}
+
+/// Print $PATH between markers. fish keeps PATH as a list and would print
+/// it space-separated, so it's joined with `:` there.
+fn path_script(shell: &str) -> String {
+    let path = match Path::new(shell).file_name().and_then(|n| n.to_str()) {
+        Some("fish") => "(string join : $PATH)",
+        _ => "\"$PATH\"",
+    };
+    format!("printf '{PATH_MARKER}%s{PATH_MARKER}' {path}")
+}
+
+/// $SHELL, falling back to zsh (the macOS default) when a GUI launch lacks it
+fn login_shell() -> String {
+    std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string())
+}
+
+/// Describe the shell used for login PATH resolution and its existing profile files
+pub fn shell_info() -> ShellInfo {
+    if cfg!(windows) {
+        return ShellInfo {
+            path: None,
+            name: None,
+            profile_files: Vec::new(),
+        };
+    }
+    let shell = login_shell();
+    let name = Path::new(&shell)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string());
+    let profile_files = match (dirs::home_dir(), name.as_deref()) {
+        (Some(home), Some(name)) => existing_profile_files(&home, name),
+        _ => Vec::new(),
+    };
+    ShellInfo {
+        path: Some(shell),
+        name,
+        profile_files,
+    }
+}
+
+/// Startup files a shell may read, in the order it reads them
+fn profile_candidates(shell_name: &str) -> &'static [&'static str] {
+    match shell_name {
+        "zsh" => &[".zshenv", ".zprofile", ".zshrc", ".zlogin"],
+        "bash" => &[".bash_profile", ".bash_login", ".profile", ".bashrc"],
+        "fish" => &[".config/fish/config.fish"],
+        "ksh" | "mksh" => &[".profile", ".kshrc"],
+        _ => &[".profile"],
+    }
+}
+
+fn existing_profile_files(home: &Path, shell_name: &str) -> Vec<String> {
+    profile_candidates(shell_name)
+        .iter()
+        .map(|rel| home.join(rel))
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+fn extract_marked_path(output: &str) -> Option<String> {
+    let start = output.find(PATH_MARKER)? + PATH_MARKER.len();
+    let len = output[start..].find(PATH_MARKER)?;
+    Some(output[start..start + len].to_string()).filter(|p| !p.is_empty())
+}
+
+/// Login entries first, then any current entries the login PATH lacks
+fn merge_path_lists(login: &std::ffi::OsStr, current: &std::ffi::OsStr) -> OsString {
+    let mut merged: Vec<PathBuf> = Vec::new();
+    for dir in std::env::split_paths(login).chain(std::env::split_paths(current)) {
+        if !dir.as_os_str().is_empty() && !merged.contains(&dir) {
+            merged.push(dir);
+        }
+    }
+    std::env::join_paths(merged).unwrap_or_else(|_| current.to_os_string())
+}
+
+/// Look up an executable on `spawn_path` without spawning anything
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    find_in_path_list(name, spawn_path())
+}
+
+fn find_in_path_list(name: &str, path_list: &std::ffi::OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path_list)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_marked_path_ignores_rc_noise() {
+        let output = format!("Welcome!\n{PATH_MARKER}/opt/homebrew/bin:/usr/bin{PATH_MARKER}");
+        assert_eq!(
+            extract_marked_path(&output).as_deref(),
+            Some("/opt/homebrew/bin:/usr/bin")
+        );
+        assert_eq!(extract_marked_path("no markers"), None);
+    }
+
+    #[test]
+    fn path_script_joins_the_fish_path_list() {
+        assert_eq!(
+            path_script("/usr/local/bin/fish"),
+            format!("printf '{PATH_MARKER}%s{PATH_MARKER}' (string join : $PATH)")
+        );
+        assert_eq!(
+            path_script("/bin/zsh"),
+            format!("printf '{PATH_MARKER}%s{PATH_MARKER}' \"$PATH\"")
+        );
+    }
+
+    #[test]
+    fn existing_profile_files_lists_only_present_files_in_read_order() {
+        let home = std::env::temp_dir().join(format!("central_shell_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(home.join(".zshrc"), "").unwrap();
+        std::fs::write(home.join(".zshenv"), "").unwrap();
+        std::fs::write(home.join(".bashrc"), "").unwrap();
+
+        let found = existing_profile_files(&home, "zsh");
+        let names: Vec<_> = found
+            .iter()
+            .map(|p| p.rsplit('/').next().unwrap())
+            .collect();
+        assert_eq!(names, [".zshenv", ".zshrc"]);
+        assert!(existing_profile_files(&home, "dash").is_empty());
+
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn merge_path_lists_prefers_login_entries_and_dedupes() {
+        let merged = merge_path_lists(
+            std::ffi::OsStr::new("/opt/homebrew/bin:/usr/bin"),
+            std::ffi::OsStr::new("/usr/bin:/bin"),
+        );
+        assert_eq!(merged, OsString::from("/opt/homebrew/bin:/usr/bin:/bin"));
+    }
+
+    #[test]
+    fn find_in_path_list_finds_sh() {
+        let found = find_in_path_list("sh", std::ffi::OsStr::new("/nonexistent:/bin:/usr/bin"));
+        assert!(found.is_some());
+    }
+
+    #[test]
+    fn find_in_path_list_misses_unknown_binary() {
+        let found = find_in_path_list(
+            "central-no-such-binary",
+            std::ffi::OsStr::new("/bin:/usr/bin"),
+        );
+        assert!(found.is_none());
+    }
+}
@@ -0,0 +1,66 @@
+use super::session_meta::SessionMetadata;
+
+/// Flag `meta` the first time its spend passes its budget, returning the
+/// error to fail the session with. The worker reports a running total for
+/// the session, so the latest figure is the spend so far — summing them
+/// would double-count earlier turns.
+pub fn check_budget(meta: &mut SessionMetadata) -> Option<String> {
+    if meta.over_budget {
+        return None;
+    }
+    let (spent, budget) = (meta.total_cost_usd?, meta.max_budget_usd?);
+    if budget <= 0.0 || spent <= budget {
+        return None;
+    }
+    meta.over_budget = true;
+    Some(format!(
+        "Session over budget: spent ${spent:.2} of ${budget:.2}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sidecar::session_meta::metadata_from_command;
+    use crate::sidecar::types::SidecarCommand;
+
+    fn meta_with_budget(budget: Option<f64>) -> SessionMetadata {
+        metadata_from_command(&SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp".to_string(),
+            prompt: "hi".to_string(),
+            model: None,
+            max_budget_usd: budget,
+            resume_session_id: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn fires_once_when_spend_passes_budget() {
+        let mut meta = meta_with_budget(Some(1.0));
+        meta.total_cost_usd = Some(0.4);
+        assert_eq!(check_budget(&mut meta), None);
+
+        meta.total_cost_usd = Some(1.25);
+        assert_eq!(
+            check_budget(&mut meta).as_deref(),
+            Some("Session over budget: spent $1.25 of $1.00")
+        );
+        assert!(meta.over_budget);
+
+        meta.total_cost_usd = Some(1.5);
+        assert_eq!(check_budget(&mut meta), None);
+    }
+
+    #[test]
+    fn no_budget_never_fires() {
+        let mut meta = meta_with_budget(None);
+        meta.total_cost_usd = Some(100.0);
+        assert_eq!(check_budget(&mut meta), None);
+
+        let mut meta = meta_with_budget(Some(0.0));
+        meta.total_cost_usd = Some(100.0);
+        assert_eq!(check_budget(&mut meta), None);
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Why a worker couldn't be started, serialized as `{ code, message }` so the
+/// UI can show setup help per failure instead of a raw string
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum SidecarError {
+    /// `node` isn't on PATH
+    NodeNotFound(String),
+    /// The session-worker script couldn't be located
+    WorkerScriptMissing(String),
+    /// The OS refused to execute node or the worker
+    SpawnPermissionDenied(String),
+    /// The session already has a live worker
+    AlreadyRunning(String),
+    /// `max_concurrent_sessions` workers are already running
+    SessionLimitReached(String),
+    Other(String),
+}
+
+impl SidecarError {
+    /// Classify a failed `Command::spawn` of the node worker
+    pub fn from_spawn(label: &str, err: &std::io::Error) -> Self {
+        let message = format!("Failed to spawn worker for {label}: {err}");
+        match err.kind() {
+            std::io::ErrorKind::NotFound => Self::NodeNotFound(message),
+            std::io::ErrorKind::PermissionDenied => Self::SpawnPermissionDenied(message),
+            _ => Self::Other(message),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NodeNotFound(m)
+            | Self::WorkerScriptMissing(m)
+            | Self::SpawnPermissionDenied(m)
+            | Self::AlreadyRunning(m)
+            | Self::SessionLimitReached(m)
+            | Self::Other(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for SidecarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<String> for SidecarError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+/// Callers that only report a message keep using `Result<_, String>`
+impl From<SidecarError> for String {
+    fn from(err: SidecarError) -> Self {
+        err.message().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_errors_are_classified_and_serialized_with_a_code() {
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+
+        assert!(matches!(
+            SidecarError::from_spawn("s1", &missing),
+            SidecarError::NodeNotFound(_)
+        ));
+        assert!(matches!(
+            SidecarError::from_spawn("s1", &denied),
+            SidecarError::SpawnPermissionDenied(_)
+        ));
+
+        let json = serde_json::to_value(SidecarError::AlreadyRunning("busy".into())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"code": "already_running", "message": "busy"})
+        );
+    }
+}
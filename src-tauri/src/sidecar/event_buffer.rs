@@ -0,0 +1,99 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use super::types::{AgentEventPayload, SidecarEvent};
+
+/// Enough to cover a frontend reload; older events are only in the message store
+const MAX_BUFFERED_EVENTS: usize = 500;
+
+/// Recent events for one session, each tagged with a per-session sequence number
+#[derive(Default)]
+pub struct EventBuffer {
+    next_seq: u64,
+    events: VecDeque<AgentEventPayload>,
+}
+
+/// Shared between the manager and the worker reader threads
+pub type EventBufferStore = Arc<Mutex<HashMap<String, EventBuffer>>>;
+
+impl EventBuffer {
+    /// Assign the next sequence number (starting at 1) and keep a copy
+    pub fn push(&mut self, event: SidecarEvent) -> AgentEventPayload {
+        self.next_seq += 1;
+        let payload = AgentEventPayload {
+            seq: self.next_seq,
+            event,
+        };
+        if self.events.len() >= MAX_BUFFERED_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(payload.clone());
+        payload
+    }
+
+    /// Sequence number of the newest event (0 before any)
+    pub fn last_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// Buffered events with `seq > since_seq`, oldest first
+    pub fn since(&self, since_seq: u64) -> Vec<AgentEventPayload> {
+        self.events
+            .iter()
+            .filter(|p| p.seq > since_seq)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Sequence and buffer an event for a session. If the store is poisoned the
+/// event is still delivered, just without replay support.
+pub fn sequence_event(
+    store: &EventBufferStore,
+    session_id: &str,
+    event: SidecarEvent,
+) -> AgentEventPayload {
+    match store.lock() {
+        Ok(mut buffers) => buffers
+            .entry(session_id.to_string())
+            .or_default()
+            .push(event),
+        Err(_) => AgentEventPayload { seq: 0, event },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(text: &str) -> SidecarEvent {
+        SidecarEvent::ContentDelta {
+            session_id: "s1".to_string(),
+            delta: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn push_assigns_increasing_sequence_numbers() {
+        let mut buffer = EventBuffer::default();
+        assert_eq!(buffer.push(delta("a")).seq, 1);
+        assert_eq!(buffer.push(delta("b")).seq, 2);
+
+        let replay = buffer.since(1);
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].seq, 2);
+        assert_eq!(buffer.since(0).len(), 2);
+    }
+
+    #[test]
+    fn buffer_keeps_only_recent_events() {
+        let mut buffer = EventBuffer::default();
+        for i in 0..MAX_BUFFERED_EVENTS + 10 {
+            buffer.push(delta(&i.to_string()));
+        }
+
+        let replay = buffer.since(0);
+        assert_eq!(replay.len(), MAX_BUFFERED_EVENTS);
+        assert_eq!(replay[0].seq, 11);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use super::manager::{drain_idle, SidecarHandle};
+use crate::debug_log;
+
+/// How long a parked or prewarmed worker may sit idle before it's shut down
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How often parked workers are checked against the idle timeout
+const REAP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long an idle worker gets to finish after `end_session` before it's killed
+const IDLE_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sessions started with `keep_alive`, and when each was last parked.
+/// A parked session's worker stays up with its follow-up queue open, so the
+/// next message skips the respawn and resume. Prewarmed workers are parked
+/// from the start and unparked by their first message.
+#[derive(Debug, Default)]
+pub struct ParkedSessions {
+    keep_alive: HashSet<String>,
+    parked: HashMap<String, Instant>,
+}
+
+impl ParkedSessions {
+    pub fn enable(&mut self, session_id: &str) {
+        self.keep_alive.insert(session_id.to_string());
+    }
+
+    /// Park a keep-alive session; false means the caller should end it normally
+    pub fn park(&mut self, session_id: &str) -> bool {
+        if !self.keep_alive.contains(session_id) {
+            return false;
+        }
+        self.parked.insert(session_id.to_string(), Instant::now());
+        true
+    }
+
+    /// Start the idle clock for a worker that hasn't had a prompt yet
+    pub fn prewarm(&mut self, session_id: &str) {
+        self.parked.insert(session_id.to_string(), Instant::now());
+    }
+
+    /// The session is in use again, so its idle clock stops
+    pub fn unpark(&mut self, session_id: &str) {
+        self.parked.remove(session_id);
+    }
+
+    pub fn is_keep_alive(&self, session_id: &str) -> bool {
+        self.keep_alive.contains(session_id)
+    }
+
+    pub fn forget(&mut self, session_id: &str) {
+        self.keep_alive.remove(session_id);
+        self.parked.remove(session_id);
+    }
+
+    /// Sessions parked for longer than `timeout`
+    pub fn expired(&self, timeout: Duration) -> Vec<String> {
+        self.parked
+            .iter()
+            .filter(|(_, since)| since.elapsed() >= timeout)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+}
+
+/// Periodically end parked workers that have been idle past `IDLE_TIMEOUT`
+/// through the same drain as shutdown, and reap workers that died on their own
+pub fn spawn_idle_reaper(sidecar: SidecarHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(REAP_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let idle = sidecar.clone();
+            let drained = tokio::task::spawn_blocking(move || {
+                drain_idle(&idle, IDLE_TIMEOUT, IDLE_DRAIN_TIMEOUT)
+            })
+            .await;
+            if let Err(e) = drained {
+                debug_log::log("SIDECAR", &format!("Idle reaper: drain failed: {e}"));
+            }
+            match sidecar.lock() {
+                Ok(mut manager) => {
+                    manager.reap_dead_workers();
+                }
+                Err(e) => debug_log::log(
+                    "SIDECAR",
+                    &format!("Idle reaper: failed to lock sidecar: {e}"),
+                ),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_keep_alive_sessions_park_and_expire() {
+        let mut sessions = ParkedSessions::default();
+        sessions.enable("kept");
+
+        assert!(sessions.park("kept"));
+        assert!(!sessions.park("plain"));
+        assert_eq!(sessions.expired(Duration::ZERO), vec!["kept".to_string()]);
+        assert!(sessions.expired(IDLE_TIMEOUT).is_empty());
+
+        sessions.unpark("kept");
+        assert!(sessions.expired(Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn prewarmed_sessions_expire_until_first_use() {
+        let mut sessions = ParkedSessions::default();
+        sessions.prewarm("warm");
+        assert_eq!(sessions.expired(Duration::ZERO), vec!["warm".to_string()]);
+
+        sessions.unpark("warm");
+        assert!(sessions.expired(Duration::ZERO).is_empty());
+        // Not keep-alive, so ending it later shuts the worker down normally
+        assert!(!sessions.park("warm"));
+    }
+}
@@ -1,47 +1,56 @@
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Child, Command, Stdio};
+use std::process::Child;
 use std::sync::{Arc, Mutex};
 
-use tauri::{AppHandle, Emitter};
-
-use super::types::{AgentEventPayload, SidecarCommand, SidecarEvent};
-use crate::debug_log;
-
-/// One worker process per agent session
-struct SessionWorker {
-    child: Child,
-}
-
-impl SessionWorker {
-    /// Send a JSON-line command to this worker's stdin
-    fn send(&mut self, json: &str) -> Result<(), String> {
-        let stdin = self.child.stdin.as_mut().ok_or_else(|| {
-            "Worker stdin not available".to_string()
-        })?;
-
-        stdin
-            .write_all(format!("{json}\n").as_bytes())
-            .map_err(|e| format!("Failed to write to worker stdin: {e}"))?;
-
-        stdin
-            .flush()
-            .map_err(|e| format!("Failed to flush worker stdin: {e}"))?;
-
-        Ok(())
-    }
-
-    /// Kill the worker process
-    fn kill(&mut self) {
-        let _ = self.child.kill();
-        let _ = self.child.wait();
-    }
-}
+use tauri::AppHandle;
+
+use super::error::SidecarError;
+use super::keep_alive::ParkedSessions;
+use super::event_buffer::EventBufferStore;
+use super::process::{forward_stderr, spawn_worker};
+use super::reader::{spawn_stdout_reader, EventRouter};
+use super::stderr_tail::StderrTailStore;
+use super::session_meta::{metadata_from_command, SessionMetaStore, SessionMetadata};
+use super::tool_history::ToolHistoryStore;
+use super::types::SidecarCommand;
+use crate::debug_log::{self, LogLevel};
+
+mod drain;
+mod limit;
+mod parking;
+mod queries;
+mod reaper;
+mod restart;
+mod stats;
+#[cfg(all(test, unix))]
+pub(crate) mod stubs;
+mod worker;
+
+pub use drain::{drain_all, drain_idle, DrainReport};
+pub use stats::SidecarStats;
+
+use worker::{SessionWorker, StartParams};
 
 /// Manages per-session Node.js worker processes
 pub struct SidecarManager {
     workers: HashMap<String, SessionWorker>,
-    app_handle: AppHandle,
+    metadata: SessionMetaStore,
+    tool_history: ToolHistoryStore,
+    event_buffers: EventBufferStore,
+    stderr_tails: StderrTailStore,
+    /// How removed workers ended, kept for diagnostics
+    exits: HashMap<String, String>,
+    /// Start parameters of removed workers, for `restart_session`
+    retired_starts: HashMap<String, StartParams>,
+    parked: ParkedSessions,
+    /// Workers spawned for a session id that had a worker before
+    restarts: u64,
+    /// `start_session` refuses to spawn past this many running workers
+    max_concurrent_sessions: usize,
+    /// `process::spawn_worker`, swapped for a stub process in tests
+    spawn: fn(&str) -> Result<Child, SidecarError>,
+    /// None only in tests, where events have nowhere to go
+    app_handle: Option<AppHandle>,
 }
 
 /// Thread-safe handle to the sidecar manager
@@ -49,103 +58,98 @@ pub type SidecarHandle = Arc<Mutex<SidecarManager>>;
 
 /// Create a new sidecar handle managed by Tauri state
 pub fn create_sidecar_handle(app_handle: AppHandle) -> SidecarHandle {
-    Arc::new(Mutex::new(SidecarManager::new(app_handle)))
+    let max_concurrent_sessions = limit::configured_limit(&app_handle);
+    Arc::new(Mutex::new(SidecarManager::new(
+        Some(app_handle),
+        max_concurrent_sessions,
+    )))
 }
 
 impl SidecarManager {
-    fn new(app_handle: AppHandle) -> Self {
+    fn new(app_handle: Option<AppHandle>, max_concurrent_sessions: usize) -> Self {
         Self {
             workers: HashMap::new(),
+            metadata: SessionMetaStore::default(),
+            tool_history: ToolHistoryStore::default(),
+            event_buffers: EventBufferStore::default(),
+            stderr_tails: StderrTailStore::default(),
+            exits: HashMap::new(),
+            retired_starts: HashMap::new(),
+            parked: ParkedSessions::default(),
+            restarts: 0,
+            max_concurrent_sessions,
+            spawn: spawn_worker,
             app_handle,
         }
     }
 
-    /// Spawn a new worker for this session and send the start_session command
-    pub fn start_session(&mut self, command: &SidecarCommand) -> Result<(), String> {
+    /// Spawn a new worker for this session and send its start_session (or
+    /// prewarm_session) command.
+    /// With a `window_label`, the session's events go only to that window.
+    pub fn start_session(
+        &mut self,
+        command: &SidecarCommand,
+        window_label: Option<String>,
+    ) -> Result<(), SidecarError> {
         let session_id = match command {
-            SidecarCommand::StartSession { session_id, .. } => session_id.clone(),
-            _ => return Err("Expected StartSession command".to_string()),
+            SidecarCommand::StartSession { session_id, .. }
+            | SidecarCommand::PrewarmSession { session_id, .. } => session_id.clone(),
+            _ => return Err(SidecarError::Other("Expected StartSession command".to_string())),
         };
 
         if self.workers.contains_key(&session_id) {
-            return Err(format!("Session {session_id} already has a running worker"));
+            return Err(SidecarError::AlreadyRunning(format!(
+                "Session {session_id} already has a running worker"
+            )));
         }
+        self.check_session_limit()?;
 
-        let worker_path = resolve_worker_path()?;
-        let sidecar_dir = std::path::Path::new(&worker_path)
-            .parent()
-            .and_then(|p| p.parent())
-            .ok_or_else(|| "Cannot resolve sidecar directory".to_string())?;
-
-        debug_log::log("SIDECAR", &format!("Spawning worker for session {session_id}"));
-        debug_log::log("SIDECAR", &format!("Worker path: {worker_path}"));
-
-        let ca_certs = resolve_ca_certs();
-
-        let mut cmd = Command::new("node");
-        cmd.arg("--import")
-            .arg("tsx")
-            .arg(&worker_path)
-            .current_dir(sidecar_dir)
-            // Unset CLAUDECODE to prevent SDK from refusing to start inside
-            // a Claude Code session (common during development)
-            .env_remove("CLAUDECODE");
-
-        // Ensure Node.js can verify TLS certs (macOS system bundle)
-        // See https://github.com/anthropics/claude-code/issues/4053
-        if let Some(ref certs) = ca_certs {
-            cmd.env("NODE_EXTRA_CA_CERTS", certs);
+        let mut child = (self.spawn)(&session_id)?;
+        if self.exits.contains_key(&session_id) {
+            self.restarts += 1;
         }
 
-        let mut child = cmd
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| {
-                let msg = format!("Failed to spawn worker for {session_id}: {e}");
-                debug_log::log("SIDECAR", &msg);
-                msg
-            })?;
-
-        let pid = child.id();
-        debug_log::log("SIDECAR", &format!("Worker spawned for {session_id}, PID: {pid}"));
+        if let (Some(meta), Ok(mut store)) = (metadata_from_command(command), self.metadata.lock()) {
+            store.insert(session_id.clone(), meta);
+        }
 
-        // Start stdout reader thread
+        let mut readers = Vec::new();
         if let Some(stdout) = child.stdout.take() {
-            let app_handle = self.app_handle.clone();
-            let sid = session_id.clone();
-            std::thread::spawn(move || {
-                debug_log::log("SIDECAR", &format!("[{sid}] stdout reader started"));
-                read_worker_output(stdout, &app_handle, &sid);
-                debug_log::log("SIDECAR", &format!("[{sid}] stdout reader ended"));
-            });
+            let router = EventRouter {
+                app_handle: self.app_handle.clone(),
+                metadata: self.metadata.clone(),
+                tool_history: self.tool_history.clone(),
+                event_buffers: self.event_buffers.clone(),
+                session_id: session_id.clone(),
+                window_label: window_label.clone(),
+            };
+            readers.push(spawn_stdout_reader(stdout, router));
         }
-
-        // Start stderr reader thread
         if let Some(stderr) = child.stderr.take() {
-            let sid = session_id.clone();
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines() {
-                    match line {
-                        Ok(l) if !l.trim().is_empty() => {
-                            debug_log::log("SIDECAR-STDERR", &format!("[{sid}] {l}"));
-                        }
-                        Err(_) => break,
-                        _ => {}
-                    }
-                }
-            });
+            readers.push(forward_stderr(
+                stderr,
+                session_id.clone(),
+                Some(self.stderr_tails.clone()),
+            ));
         }
 
-        let mut worker = SessionWorker { child };
+        let mut worker = SessionWorker {
+            child,
+            readers,
+            start: StartParams {
+                command: command.clone(),
+                window_label,
+            },
+        };
 
-        // Send the start_session command
+        // Send the start_session / prewarm_session command
         let json = serde_json::to_string(command)
             .map_err(|e| format!("Failed to serialize command: {e}"))?;
         debug_log::log("SIDECAR-CMD", &format!("[{session_id}] {json}"));
-        worker.send(&json)?;
+        if let Err(e) = worker.send(&json) {
+            worker.stop();
+            return Err(e.into());
+        }
 
         self.workers.insert(session_id, worker);
         Ok(())
@@ -156,13 +160,21 @@ impl SidecarManager {
         let session_id = command_session_id(command)
             .ok_or_else(|| "Command has no session ID".to_string())?;
 
+        self.parked.unpark(&session_id);
         self.send_to_session(&session_id, command)
     }
 
     /// Send a command to a specific session's worker by explicit session ID
+    /// Fails with "worker has exited" (and reaps it) if the process died.
     pub fn send_to_session(&mut self, session_id: &str, command: &SidecarCommand) -> Result<(), String> {
+        if let Some(status) = self.reap_if_exited(session_id) {
+            return Err(format!("Worker for session {session_id} has exited ({status})"));
+        }
         let worker = self.workers.get_mut(session_id).ok_or_else(|| {
-            let msg = format!("No worker found for session {session_id}");
+            let msg = match self.exits.get(session_id) {
+                Some(status) => format!("Worker for session {session_id} has exited ({status})"),
+                None => format!("No worker found for session {session_id}"),
+            };
             debug_log::log("SIDECAR", &msg);
             msg
         })?;
@@ -170,9 +182,9 @@ impl SidecarManager {
         let json = serde_json::to_string(command)
             .map_err(|e| format!("Failed to serialize command: {e}"))?;
 
-        debug_log::log("SIDECAR-CMD", &format!("[{session_id}] {json}"));
+        debug_log::log_at(LogLevel::Debug, "SIDECAR-CMD", &format!("[{session_id}] {json}"));
         worker.send(&json)?;
-        debug_log::log("SIDECAR", &format!("[{session_id}] command sent OK"));
+        debug_log::log_at(LogLevel::Debug, "SIDECAR", &format!("[{session_id}] command sent OK"));
         Ok(())
     }
 
@@ -180,13 +192,56 @@ impl SidecarManager {
     pub fn remove_session(&mut self, session_id: &str) {
         if let Some(mut worker) = self.workers.remove(session_id) {
             debug_log::log("SIDECAR", &format!("Killing worker for session {session_id}"));
-            worker.kill();
+            let exit = worker.kill();
+            self.exits.insert(session_id.to_string(), exit);
+            self.retired_starts.insert(session_id.to_string(), worker.start);
         }
+        self.parked.forget(session_id);
     }
 
-    /// Get list of active session IDs
-    pub fn active_session_ids(&self) -> Vec<String> {
-        self.workers.keys().cloned().collect()
+    /// Drop everything kept about a deleted session, killing its worker if
+    /// it is still running. Returns its metadata for any final cleanup.
+    pub fn forget_session(&mut self, session_id: &str) -> Option<SessionMetadata> {
+        self.remove_session(session_id);
+        self.exits.remove(session_id);
+        self.retired_starts.remove(session_id);
+        if let Ok(mut history) = self.tool_history.lock() {
+            history.remove(session_id);
+        }
+        if let Ok(mut buffers) = self.event_buffers.lock() {
+            buffers.remove(session_id);
+        }
+        if let Ok(mut tails) = self.stderr_tails.lock() {
+            tails.remove(session_id);
+        }
+        self.metadata.lock().ok()?.remove(session_id)
+    }
+
+    /// Hard budget stop: kill the worker outright rather than letting it
+    /// finish the turn and spend more
+    pub fn abort_over_budget(&mut self, session_id: &str, error: &str) {
+        debug_log::log_at(LogLevel::Warn, "SIDECAR", &format!("[{session_id}] {error}, killing worker"));
+        self.remove_session(session_id);
+    }
+
+    /// Ask a session's worker to cancel gracefully: SIGINT where supported,
+    /// otherwise an `abort_session` command. The worker is left running so
+    /// it can report how the session ended.
+    pub fn interrupt_session(&mut self, session_id: &str) -> Result<(), SidecarError> {
+        let worker = self
+            .workers
+            .get(session_id)
+            .ok_or_else(|| SidecarError::Other(format!("No worker found for session {session_id}")))?;
+        match worker.interrupt() {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                debug_log::log("SIDECAR", &format!("[{session_id}] SIGINT failed ({e}), sending abort_session"));
+                self.send_to_session(session_id, &SidecarCommand::AbortSession {
+                    session_id: session_id.to_string(),
+                })?;
+                Ok(())
+            }
+        }
     }
 
     /// Kill all worker processes and clean up
@@ -194,7 +249,7 @@ impl SidecarManager {
         debug_log::log("SIDECAR", &format!("Shutting down {} workers", self.workers.len()));
         for (sid, mut worker) in self.workers.drain() {
             debug_log::log("SIDECAR", &format!("Killing worker for session {sid}"));
-            worker.kill();
+            worker.stop();
         }
     }
 }
@@ -209,113 +264,14 @@ impl Drop for SidecarManager {
 fn command_session_id(command: &SidecarCommand) -> Option<String> {
     match command {
         SidecarCommand::StartSession { session_id, .. } => Some(session_id.clone()),
+        SidecarCommand::PrewarmSession { session_id, .. } => Some(session_id.clone()),
         SidecarCommand::SendMessage { session_id, .. } => Some(session_id.clone()),
         SidecarCommand::AbortSession { session_id, .. } => Some(session_id.clone()),
         SidecarCommand::EndSession { session_id, .. } => Some(session_id.clone()),
-        SidecarCommand::ToolApprovalResponse { .. } => None,
+        SidecarCommand::CancelTool { session_id, .. } => Some(session_id.clone()),
+        SidecarCommand::Reprompt { session_id, .. } => Some(session_id.clone()),
+        SidecarCommand::SetModel { session_id, .. } => Some(session_id.clone()),
+        SidecarCommand::SetPermissionMode { session_id, .. } => Some(session_id.clone()),
+        SidecarCommand::ToolApprovalResponse { .. } | SidecarCommand::ListModels => None,
     }
 }
-
-/// Read JSON-line events from a worker's stdout and emit via Tauri events
-fn read_worker_output(stdout: impl std::io::Read, app_handle: &AppHandle, session_id: &str) {
-    let reader = BufReader::new(stdout);
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(e) => {
-                debug_log::log("SIDECAR", &format!("[{session_id}] stdout read error: {e}"));
-                break;
-            }
-        };
-
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        debug_log::log("SIDECAR-STDOUT", &format!("[{session_id}] {trimmed}"));
-
-        match serde_json::from_str::<SidecarEvent>(trimmed) {
-            Ok(event) => {
-                let payload = AgentEventPayload { event };
-                match app_handle.emit("agent-event", &payload) {
-                    Ok(_) => debug_log::log("SIDECAR", &format!("[{session_id}] event emitted OK")),
-                    Err(e) => debug_log::log("SIDECAR", &format!("[{session_id}] EMIT ERROR: {e}")),
-                }
-            }
-            Err(e) => {
-                debug_log::log("SIDECAR", &format!("[{session_id}] PARSE ERROR: {e} — {trimmed}"));
-            }
-        }
-    }
-}
-
-/// Resolve the CA certificate bundle path for Node.js TLS.
-/// Checks the user's env first, then falls back to well-known system paths.
-fn resolve_ca_certs() -> Option<String> {
-    // Respect user's explicit setting
-    if let Ok(val) = std::env::var("NODE_EXTRA_CA_CERTS") {
-        if !val.is_empty() {
-            return Some(val);
-        }
-    }
-
-    // macOS system bundle, then common Linux paths
-    let candidates = [
-        "/etc/ssl/cert.pem",
-        "/etc/ssl/certs/ca-certificates.crt",
-        "/etc/pki/tls/certs/ca-bundle.crt",
-    ];
-    for path in &candidates {
-        if std::path::Path::new(path).exists() {
-            return Some(path.to_string());
-        }
-    }
-    None
-}
-
-/// Resolve the path to the session-worker entry script
-fn resolve_worker_path() -> Result<String, String> {
-    let worker_rel = std::path::Path::new("sidecar")
-        .join("src")
-        .join("session-worker.ts");
-
-    // Strategy 1: CWD is src-tauri/, parent is project_root (tauri dev)
-    if let Ok(cwd) = std::env::current_dir() {
-        if let Some(parent) = cwd.parent() {
-            let candidate = parent.join(&worker_rel);
-            if candidate.exists() {
-                return path_to_string(&candidate);
-            }
-        }
-    }
-
-    // Strategy 2: Walk up from executable to find the project root.
-    // Handles .app bundles where exe is at:
-    //   src-tauri/target/debug/bundle/macos/App.app/Contents/MacOS/binary
-    if let Ok(exe) = std::env::current_exe() {
-        let mut dir = exe.as_path();
-        // Walk up at most 10 levels looking for the sidecar directory
-        for _ in 0..10 {
-            match dir.parent() {
-                Some(parent) => {
-                    let candidate = parent.join(&worker_rel);
-                    if candidate.exists() {
-                        return path_to_string(&candidate);
-                    }
-                    dir = parent;
-                }
-                None => break,
-            }
-        }
-    }
-
-    Err(format!("Worker not found (looked for {})", worker_rel.display()))
-}
-
-fn path_to_string(p: &std::path::Path) -> Result<String, String> {
-    p.to_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Invalid path encoding".to_string())
-}
@@ -0,0 +1,162 @@
+//! Graceful shutdown: let workers finish their sessions before killing them.
+//! Used for every worker on exit and for idle parked workers.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::super::types::SidecarCommand;
+use super::{SidecarHandle, SidecarManager};
+use crate::debug_log;
+
+const DRAIN_POLL: Duration = Duration::from_millis(50);
+
+/// Which sessions ended on their own and which had to be killed
+#[derive(Debug, Default, Serialize)]
+pub struct DrainReport {
+    pub drained: Vec<String>,
+    pub killed: Vec<String>,
+}
+
+impl SidecarManager {
+    /// Send `end_session` to each of these workers. Returns each session
+    /// with the event seq its completion has to arrive after.
+    fn begin_drain(&mut self, session_ids: Vec<String>) -> Vec<(String, u64)> {
+        let sessions: Vec<(String, u64)> = session_ids
+            .into_iter()
+            .map(|id| {
+                let seq = self.last_event_seq(&id);
+                (id, seq)
+            })
+            .collect();
+        for (session_id, _) in &sessions {
+            self.parked.unpark(session_id);
+            let end = SidecarCommand::EndSession {
+                session_id: session_id.clone(),
+            };
+            if let Err(e) = self.send_to_session(session_id, &end) {
+                debug_log::log(
+                    "SIDECAR",
+                    &format!("[{session_id}] WARN: drain end_session failed: {e}"),
+                );
+            }
+        }
+        sessions
+    }
+
+    /// Whether a draining session has reported completion or its worker exited
+    fn has_drained(&mut self, session_id: &str, since_seq: u64) -> bool {
+        let exited = self
+            .workers
+            .get_mut(session_id)
+            .map_or(true, |worker| worker.exit_status().is_some());
+        exited || self.session_ended_since(session_id, since_seq)
+    }
+}
+
+/// End every session, wait up to `timeout` for them to finish, then kill
+/// whatever is left
+pub fn drain_all(sidecar: &SidecarHandle, timeout: Duration) -> DrainReport {
+    drain(sidecar, timeout, |manager| {
+        manager.workers.keys().cloned().collect()
+    })
+}
+
+/// End parked or prewarmed workers that have sat idle for `idle`, giving
+/// them up to `timeout` to finish before they're killed
+pub fn drain_idle(sidecar: &SidecarHandle, idle: Duration, timeout: Duration) -> DrainReport {
+    drain(sidecar, timeout, |manager| manager.parked.expired(idle))
+}
+
+/// The lock is only held between polls so worker events keep flowing while
+/// we wait
+fn drain(
+    sidecar: &SidecarHandle,
+    timeout: Duration,
+    select: impl FnOnce(&SidecarManager) -> Vec<String>,
+) -> DrainReport {
+    let lock_failed = |e| {
+        debug_log::log(
+            "SIDECAR",
+            &format!("WARN: drain could not lock sidecar: {e}"),
+        )
+    };
+    let Some(mut pending) = sidecar.lock().map_err(lock_failed).ok().map(|mut m| {
+        let session_ids = select(&m);
+        m.begin_drain(session_ids)
+    }) else {
+        return DrainReport::default();
+    };
+    if pending.is_empty() {
+        return DrainReport::default();
+    }
+    debug_log::log("SIDECAR", &format!("Draining {} sessions", pending.len()));
+
+    let deadline = Instant::now() + timeout;
+    let mut report = DrainReport::default();
+    while !pending.is_empty() {
+        let Ok(mut manager) = sidecar.lock() else {
+            break;
+        };
+        let timed_out = Instant::now() >= deadline;
+        pending.retain(|(session_id, since)| {
+            let drained = manager.has_drained(session_id, *since);
+            if drained || timed_out {
+                manager.remove_session(session_id);
+                let bucket = if drained {
+                    &mut report.drained
+                } else {
+                    &mut report.killed
+                };
+                bucket.push(session_id.clone());
+            }
+            !(drained || timed_out)
+        });
+        drop(manager);
+        if !pending.is_empty() {
+            std::thread::sleep(DRAIN_POLL);
+        }
+    }
+    debug_log::log("SIDECAR", &format!("Drain finished: {report:?}"));
+    report
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::super::stubs::{spawn_ender, spawn_sleeper, start, stub_manager};
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn idle_workers_get_to_finish_before_being_removed() {
+        let mut manager = stub_manager(4, spawn_ender);
+        manager.start_session(&start("idle"), None).unwrap();
+        manager.start_session(&start("busy"), None).unwrap();
+        manager.set_keep_alive("idle");
+        assert!(manager.park_session("idle"));
+        let sidecar = Arc::new(Mutex::new(manager));
+
+        let report = drain_idle(&sidecar, Duration::ZERO, Duration::from_secs(5));
+
+        assert_eq!(report.drained, vec!["idle".to_string()]);
+        assert!(report.killed.is_empty());
+        let mut manager = sidecar.lock().unwrap();
+        assert!(!manager.workers.contains_key("idle"));
+        assert_eq!(manager.exits["idle"], "exit status: 0");
+        assert_eq!(manager.active_session_count(), 1);
+    }
+
+    #[test]
+    fn idle_workers_that_ignore_end_session_are_killed() {
+        let mut manager = stub_manager(4, spawn_sleeper);
+        manager.start_session(&start("stuck"), None).unwrap();
+        manager.set_keep_alive("stuck");
+        assert!(manager.park_session("stuck"));
+        let sidecar = Arc::new(Mutex::new(manager));
+
+        let report = drain_idle(&sidecar, Duration::ZERO, Duration::from_millis(100));
+
+        assert_eq!(report.killed, vec!["stuck".to_string()]);
+        assert!(sidecar.lock().unwrap().workers.is_empty());
+    }
+}
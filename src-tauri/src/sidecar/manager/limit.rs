@@ -0,0 +1,93 @@
+//! Cap on simultaneous workers, since each one is a full Node agent session
+
+use tauri::AppHandle;
+
+use super::SidecarManager;
+use crate::commands::settings::load_setting;
+use crate::sidecar::error::SidecarError;
+
+pub const DEFAULT_MAX_CONCURRENT_SESSIONS: usize = 4;
+
+/// Read at startup; changing it takes effect on the next launch
+const MAX_SESSIONS_SETTING: &str = "max_concurrent_sessions";
+
+/// The `max_concurrent_sessions` setting, or the default when unset or not
+/// a positive number
+pub fn configured_limit(app: &AppHandle) -> usize {
+    load_setting(app, MAX_SESSIONS_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_SESSIONS)
+}
+
+impl SidecarManager {
+    /// Workers whose process is still running, parked ones included
+    pub fn active_session_count(&mut self) -> usize {
+        self.workers
+            .values_mut()
+            .map(|worker| worker.exit_status().is_none())
+            .filter(|&running| running)
+            .count()
+    }
+
+    pub(super) fn check_session_limit(&mut self) -> Result<(), SidecarError> {
+        check_capacity(self.active_session_count(), self.max_concurrent_sessions)
+    }
+}
+
+fn check_capacity(active: usize, limit: usize) -> Result<(), SidecarError> {
+    match active < limit {
+        true => Ok(()),
+        false => Err(SidecarError::SessionLimitReached(format!(
+            "{active} agent sessions are already running (limit {limit}); end one first \
+             or raise the {MAX_SESSIONS_SETTING} setting"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::stubs::{spawn_missing, spawn_sleeper, start, stub_manager, SPAWNED};
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn the_session_past_the_limit_is_refused() {
+        let limit = 3;
+        let mut active = 0;
+        for _ in 0..limit {
+            assert!(check_capacity(active, limit).is_ok());
+            active += 1;
+        }
+
+        let err = check_capacity(active, limit).unwrap_err();
+        assert!(matches!(err, SidecarError::SessionLimitReached(_)));
+        assert!(err.message().contains("limit 3"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn start_session_refuses_the_worker_past_the_limit() {
+        let mut manager = stub_manager(2, spawn_missing);
+        assert!(manager.start_session(&start("broken"), None).is_err());
+
+        manager.spawn = spawn_sleeper;
+        manager.start_session(&start("s1"), None).unwrap();
+        manager.start_session(&start("s2"), None).unwrap();
+        assert_eq!(manager.active_session_count(), 2);
+
+        let spawned = SPAWNED.load(Ordering::SeqCst);
+        let err = manager.start_session(&start("s3"), None).unwrap_err();
+        assert!(matches!(err, SidecarError::SessionLimitReached(_)));
+        assert_eq!(SPAWNED.load(Ordering::SeqCst), spawned);
+        assert!(!manager.workers.contains_key("s3"));
+
+        let crashed = manager.workers.get_mut("s1").unwrap();
+        crashed.child.kill().unwrap();
+        crashed.child.wait().unwrap();
+        manager.start_session(&start("s3"), None).unwrap();
+        assert_eq!(manager.active_session_count(), 2);
+    }
+}
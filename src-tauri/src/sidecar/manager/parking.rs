@@ -0,0 +1,41 @@
+//! Idle workers kept around for fast starts: keep-alive sessions between
+//! turns, and prewarmed sessions waiting for their first message
+
+use super::super::error::SidecarError;
+use super::super::types::SidecarCommand;
+use super::{command_session_id, SidecarManager};
+use crate::debug_log;
+
+impl SidecarManager {
+    /// Spawn a worker that sits ready for its first message. Until that
+    /// arrives it counts as parked, so the idle reaper ends unused workers.
+    pub fn prewarm_session(
+        &mut self,
+        command: &SidecarCommand,
+        window_label: Option<String>,
+    ) -> Result<(), SidecarError> {
+        self.start_session(command, window_label)?;
+        if let Some(session_id) = command_session_id(command) {
+            self.parked.prewarm(&session_id);
+        }
+        Ok(())
+    }
+
+    /// Keep this session's worker alive when it's ended, for fast follow-ups
+    pub fn set_keep_alive(&mut self, session_id: &str) {
+        self.parked.enable(session_id);
+    }
+
+    /// Leave a keep-alive session's worker idle instead of ending it.
+    /// Returns false for sessions that weren't started with `keep_alive`.
+    pub fn park_session(&mut self, session_id: &str) -> bool {
+        let parked = self.workers.contains_key(session_id) && self.parked.park(session_id);
+        if parked {
+            debug_log::log(
+                "SIDECAR",
+                &format!("Parked worker for session {session_id}"),
+            );
+        }
+        parked
+    }
+}
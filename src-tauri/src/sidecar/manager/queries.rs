@@ -0,0 +1,139 @@
+//! Lookups into the per-session state the manager keeps alongside its workers
+
+use std::collections::BTreeMap;
+
+use super::super::session_meta::{
+    cost_by_model, ModelCost, SessionBudget, SessionMetadata, SessionUsage,
+};
+use super::super::stderr_tail;
+use super::super::tool_history::ToolHistoryEntry;
+use super::super::types::{AgentEventPayload, SidecarEvent};
+use super::SidecarManager;
+
+impl SidecarManager {
+    /// Get list of active session IDs
+    pub fn active_session_ids(&self) -> Vec<String> {
+        self.workers.keys().cloned().collect()
+    }
+
+    /// OS process id of a session's live worker
+    pub fn worker_pid(&self, session_id: &str) -> Option<u32> {
+        self.workers.get(session_id).map(|worker| worker.child.id())
+    }
+
+    /// Metadata for a session, including completed ones whose worker has exited
+    pub fn session_metadata(&self, session_id: &str) -> Option<SessionMetadata> {
+        self.metadata.lock().ok()?.get(session_id).cloned()
+    }
+
+    /// Attach the pre-session working tree snapshot used by `revert_session`
+    pub fn record_snapshot(&self, session_id: &str, snapshot_id: String) {
+        if let Some(meta) = self
+            .metadata
+            .lock()
+            .ok()
+            .as_mut()
+            .and_then(|s| s.get_mut(session_id))
+        {
+            meta.snapshot_id = Some(snapshot_id);
+        }
+    }
+
+    /// Forget the snapshot once `revert_session` has applied and dropped it
+    pub fn clear_snapshot(&self, session_id: &str) {
+        if let Some(meta) = self
+            .metadata
+            .lock()
+            .ok()
+            .as_mut()
+            .and_then(|s| s.get_mut(session_id))
+        {
+            meta.snapshot_id = None;
+        }
+    }
+
+    /// Latest known spend for a session, updated as the worker reports cost
+    pub fn session_usage(&self, session_id: &str) -> Option<SessionUsage> {
+        self.metadata
+            .lock()
+            .ok()?
+            .get(session_id)
+            .map(SessionUsage::from)
+    }
+
+    /// Spend so far against the budget the session was started with
+    pub fn session_budget(&self, session_id: &str) -> Option<SessionBudget> {
+        self.metadata
+            .lock()
+            .ok()?
+            .get(session_id)
+            .map(SessionBudget::from)
+    }
+
+    /// Completed-session spend grouped by the model that actually ran
+    pub fn cost_by_model(&self) -> Vec<ModelCost> {
+        match self.metadata.lock() {
+            Ok(store) => cost_by_model(store.values()),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Tool uses, results and approval requests for a session, oldest first
+    pub fn tool_history(&self, session_id: &str) -> Vec<ToolHistoryEntry> {
+        self.tool_history
+            .lock()
+            .ok()
+            .and_then(|h| h.get(session_id).map(|log| log.iter().cloned().collect()))
+            .unwrap_or_default()
+    }
+
+    /// Tool calls per tool name, counted as `tool_use` events arrive
+    pub fn tool_counts(&self, session_id: &str) -> BTreeMap<String, u64> {
+        self.session_metadata(session_id)
+            .map(|meta| meta.tool_counts)
+            .unwrap_or_default()
+    }
+
+    /// Recent stderr lines from a session's worker
+    pub fn stderr_tail(&self, session_id: &str) -> Vec<String> {
+        stderr_tail::tail(&self.stderr_tails, session_id)
+    }
+
+    /// How a session's worker exited; None while it's still running
+    /// (or if the session never had one)
+    pub fn worker_exit(&mut self, session_id: &str) -> Option<String> {
+        match self.workers.get_mut(session_id) {
+            Some(worker) => worker.exit_status(),
+            None => self.exits.get(session_id).cloned(),
+        }
+    }
+
+    /// Sequence number of a session's newest event (0 before any)
+    pub fn last_event_seq(&self, session_id: &str) -> u64 {
+        self.event_buffers
+            .lock()
+            .ok()
+            .and_then(|b| b.get(session_id).map(|buffer| buffer.last_seq()))
+            .unwrap_or(0)
+    }
+
+    /// Whether the worker reported `session_completed` or `session_failed`
+    /// after `since_seq`
+    pub fn session_ended_since(&self, session_id: &str, since_seq: u64) -> bool {
+        self.replay_events(session_id, since_seq).iter().any(|p| {
+            matches!(
+                p.event,
+                SidecarEvent::SessionCompleted { .. } | SidecarEvent::SessionFailed { .. }
+            )
+        })
+    }
+
+    /// Buffered events after `since_seq`, so a reloaded UI can catch up
+    pub fn replay_events(&self, session_id: &str, since_seq: u64) -> Vec<AgentEventPayload> {
+        self.event_buffers
+            .lock()
+            .ok()
+            .and_then(|b| b.get(session_id).map(|buffer| buffer.since(since_seq)))
+            .unwrap_or_default()
+    }
+}
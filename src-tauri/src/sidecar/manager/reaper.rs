@@ -0,0 +1,122 @@
+//! Noticing workers that died on their own, so a crashed session fails
+//! visibly instead of lingering as "alive" with a broken stdin
+
+use std::collections::HashMap;
+use std::process::ExitStatus;
+
+use super::super::event_buffer::sequence_event;
+use super::super::reader::emit_payload;
+use super::super::types::SidecarEvent;
+use super::{SessionWorker, SidecarManager};
+use crate::debug_log::{self, LogLevel};
+
+impl SidecarManager {
+    /// Remove every worker whose process has exited. Sessions whose worker
+    /// didn't exit cleanly get a `SessionFailed` with the exit status.
+    /// Returns the reaped session ids.
+    pub fn reap_dead_workers(&mut self) -> Vec<String> {
+        take_exited(&mut self.workers)
+            .into_iter()
+            .map(|(session_id, worker, status)| {
+                self.record_dead_worker(&session_id, worker, status);
+                session_id
+            })
+            .collect()
+    }
+
+    /// Reap one session's worker if it has exited, describing how it ended
+    pub(super) fn reap_if_exited(&mut self, session_id: &str) -> Option<String> {
+        let status = self.workers.get_mut(session_id)?.exit()?;
+        let worker = self.workers.remove(session_id)?;
+        self.record_dead_worker(session_id, worker, status);
+        Some(status.to_string())
+    }
+
+    fn record_dead_worker(&mut self, session_id: &str, worker: SessionWorker, status: ExitStatus) {
+        debug_log::log(
+            "SIDECAR",
+            &format!("Reaping exited worker for session {session_id} ({status})"),
+        );
+        self.exits
+            .insert(session_id.to_string(), status.to_string());
+        self.parked.forget(session_id);
+        if !status.success() {
+            self.fail_session(session_id, worker.start.window_label.as_deref(), status);
+        }
+        self.retired_starts
+            .insert(session_id.to_string(), worker.start);
+    }
+
+    fn fail_session(&self, session_id: &str, window_label: Option<&str>, status: ExitStatus) {
+        let event = SidecarEvent::SessionFailed {
+            session_id: session_id.to_string(),
+            error: format!("Agent worker exited unexpectedly ({status})"),
+        };
+        let payload = sequence_event(&self.event_buffers, session_id, event);
+        if let Err(e) = emit_payload(self.app_handle.as_ref(), window_label, &payload) {
+            debug_log::log_at(
+                LogLevel::Warn,
+                "SIDECAR",
+                &format!("[{session_id}] EMIT ERROR: {e}"),
+            );
+        }
+    }
+}
+
+/// Pull workers whose process has exited out of the table
+fn take_exited(
+    workers: &mut HashMap<String, SessionWorker>,
+) -> Vec<(String, SessionWorker, ExitStatus)> {
+    let exited: Vec<(String, ExitStatus)> = workers
+        .iter_mut()
+        .filter_map(|(id, worker)| worker.exit().map(|status| (id.clone(), status)))
+        .collect();
+    exited
+        .into_iter()
+        .filter_map(|(id, status)| workers.remove(&id).map(|worker| (id, worker, status)))
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::sidecar::manager::StartParams;
+    use crate::sidecar::types::SidecarCommand;
+    use std::process::{Command, Stdio};
+
+    fn stub_worker() -> SessionWorker {
+        let child = Command::new("sleep")
+            .arg("30")
+            .stdin(Stdio::piped())
+            .spawn()
+            .unwrap();
+        SessionWorker {
+            child,
+            readers: Vec::new(),
+            start: StartParams {
+                command: SidecarCommand::ListModels,
+                window_label: None,
+            },
+        }
+    }
+
+    #[test]
+    fn killed_worker_is_taken_and_live_one_kept() {
+        let mut workers = HashMap::new();
+        workers.insert("dead".to_string(), stub_worker());
+        workers.insert("alive".to_string(), stub_worker());
+
+        let dead = workers.get_mut("dead").unwrap();
+        dead.child.kill().unwrap();
+        dead.child.wait().unwrap();
+
+        let reaped = take_exited(&mut workers);
+        assert_eq!(reaped.len(), 1);
+        assert_eq!(reaped[0].0, "dead");
+        assert!(!reaped[0].2.success());
+        assert!(workers.contains_key("alive"));
+        assert!(!workers.contains_key("dead"));
+
+        workers.values_mut().for_each(|worker| worker.stop());
+    }
+}
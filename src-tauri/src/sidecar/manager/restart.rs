@@ -0,0 +1,263 @@
+//! Respawning a session's worker after a crash, resuming its SDK conversation
+
+use super::super::error::SidecarError;
+use super::super::session_meta::SessionMetadata;
+use super::super::types::SidecarCommand;
+use super::{SidecarManager, StartParams};
+use crate::debug_log;
+
+/// Opening prompt for a restarted worker asked to carry on by itself
+const RESUME_PROMPT: &str = "Continue where you left off.";
+
+impl SidecarManager {
+    /// Kill any stale worker for the session and spawn a new one from its
+    /// original start parameters. If the SDK had reported a session id the
+    /// new worker resumes it, then either carries on (`resume_prompt`) or
+    /// waits for the next message; otherwise the original prompt runs again.
+    /// Spend, written files and the snapshot carry over, and a session that
+    /// was stopped for going over budget is refused.
+    pub fn restart_session(
+        &mut self,
+        session_id: &str,
+        resume_prompt: bool,
+    ) -> Result<(), SidecarError> {
+        let start = self.start_params(session_id).ok_or_else(|| {
+            SidecarError::Other(format!(
+                "No start parameters recorded for session {session_id}"
+            ))
+        })?;
+        let previous = self.session_metadata(session_id);
+        if previous.as_ref().is_some_and(|meta| meta.over_budget) {
+            return Err(SidecarError::Other(format!(
+                "Session {session_id} went over its budget and can't be restarted"
+            )));
+        }
+        let keep_alive = self.parked.is_keep_alive(session_id);
+        self.remove_session(session_id);
+
+        let command = restart_command(start.command, previous.as_ref(), resume_prompt);
+        debug_log::log(
+            "SIDECAR",
+            &format!("Restarting worker for session {session_id}"),
+        );
+        match command {
+            SidecarCommand::PrewarmSession { .. } => {
+                self.prewarm_session(&command, start.window_label)?
+            }
+            _ => self.start_session(&command, start.window_label)?,
+        }
+
+        if keep_alive {
+            self.set_keep_alive(session_id);
+        }
+        if let Some(previous) = previous {
+            if let Some(meta) = self
+                .metadata
+                .lock()
+                .ok()
+                .as_mut()
+                .and_then(|s| s.get_mut(session_id))
+            {
+                carry_over(meta, previous);
+            }
+        }
+        Ok(())
+    }
+
+    fn start_params(&self, session_id: &str) -> Option<StartParams> {
+        self.workers
+            .get(session_id)
+            .map(|worker| worker.start.clone())
+            .or_else(|| self.retired_starts.get(session_id).cloned())
+    }
+}
+
+/// What a restarted session keeps from its earlier workers, so the budget
+/// still counts their spend and a revert still covers their files
+fn carry_over(meta: &mut SessionMetadata, previous: SessionMetadata) {
+    meta.earlier_cost_usd = previous.total_cost_usd.unwrap_or(0.0);
+    meta.total_cost_usd = previous.total_cost_usd;
+    meta.max_budget_usd = previous.max_budget_usd;
+    meta.over_budget = previous.over_budget;
+    meta.files_written = previous.files_written;
+    meta.snapshot_id = previous.snapshot_id;
+}
+
+/// The command that brings a session back: resume its SDK session when one
+/// was reported, on the model it last ran with. Only with `resume_prompt`
+/// is the worker told to carry on; otherwise it waits like a prewarmed one.
+fn restart_command(
+    start: SidecarCommand,
+    previous: Option<&SessionMetadata>,
+    resume_prompt: bool,
+) -> SidecarCommand {
+    let Some(resume) = previous.and_then(|meta| meta.sdk_session_id.clone()) else {
+        return start;
+    };
+    let last_model = previous.and_then(|meta| meta.requested_model.clone());
+    let (session_id, project_path, model, max_budget_usd) = match start {
+        SidecarCommand::StartSession {
+            session_id,
+            project_path,
+            model,
+            max_budget_usd,
+            ..
+        } => (session_id, project_path, model, max_budget_usd),
+        SidecarCommand::PrewarmSession {
+            session_id,
+            project_path,
+            model,
+            ..
+        } => (session_id, project_path, model, None),
+        other => return other,
+    };
+    let model = last_model.or(model);
+    if !resume_prompt {
+        return SidecarCommand::PrewarmSession {
+            session_id,
+            project_path,
+            model,
+            resume_session_id: Some(resume),
+        };
+    }
+    SidecarCommand::StartSession {
+        session_id,
+        project_path,
+        prompt: RESUME_PROMPT.to_string(),
+        model,
+        max_budget_usd,
+        resume_session_id: Some(resume),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use super::super::stubs::{spawn_sleeper, stub_manager};
+    use super::*;
+    use crate::sidecar::session_meta::{apply_event, metadata_from_command};
+    use crate::sidecar::types::SidecarEvent;
+
+    fn start() -> SidecarCommand {
+        SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp".to_string(),
+            prompt: "Write tests".to_string(),
+            model: Some("sonnet".to_string()),
+            max_budget_usd: Some(2.0),
+            resume_session_id: None,
+        }
+    }
+
+    #[test]
+    fn resumes_the_last_sdk_session_on_the_last_model() {
+        let mut meta = metadata_from_command(&start()).unwrap();
+        meta.sdk_session_id = Some("sdk-1".to_string());
+        meta.requested_model = Some("opus".to_string());
+
+        match restart_command(start(), Some(&meta), true) {
+            SidecarCommand::StartSession {
+                prompt,
+                model,
+                max_budget_usd,
+                resume_session_id,
+                ..
+            } => {
+                assert_eq!(prompt, RESUME_PROMPT);
+                assert_eq!(model.as_deref(), Some("opus"));
+                assert_eq!(max_budget_usd, Some(2.0));
+                assert_eq!(resume_session_id.as_deref(), Some("sdk-1"));
+            }
+            other => panic!("Expected StartSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reruns_the_original_prompt_without_an_sdk_session() {
+        let meta = metadata_from_command(&start()).unwrap();
+        match restart_command(start(), Some(&meta), true) {
+            SidecarCommand::StartSession {
+                prompt,
+                resume_session_id,
+                ..
+            } => {
+                assert_eq!(prompt, "Write tests");
+                assert_eq!(resume_session_id, None);
+            }
+            other => panic!("Expected StartSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn without_the_resume_prompt_the_worker_waits_for_a_message() {
+        let mut meta = metadata_from_command(&start()).unwrap();
+        meta.sdk_session_id = Some("sdk-1".to_string());
+
+        match restart_command(start(), Some(&meta), false) {
+            SidecarCommand::PrewarmSession {
+                model,
+                resume_session_id,
+                ..
+            } => {
+                assert_eq!(model.as_deref(), Some("sonnet"));
+                assert_eq!(resume_session_id.as_deref(), Some("sdk-1"));
+            }
+            other => panic!("Expected PrewarmSession, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn restarted_session_keeps_spend_budget_and_written_files() {
+        let mut previous = metadata_from_command(&start()).unwrap();
+        previous.total_cost_usd = Some(0.75);
+        previous.files_written.insert("src/a.rs".to_string());
+        previous.snapshot_id = Some("abc".to_string());
+        let prewarm = SidecarCommand::PrewarmSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp".to_string(),
+            model: None,
+            resume_session_id: Some("sdk-1".to_string()),
+        };
+        let mut meta = metadata_from_command(&prewarm).unwrap();
+
+        carry_over(&mut meta, previous);
+        let mut cost = SidecarEvent::CostUpdate {
+            session_id: "s1".to_string(),
+            cost_usd: 0.5,
+        };
+        apply_event(&mut meta, &mut cost);
+
+        assert_eq!(meta.total_cost_usd, Some(1.25));
+        assert_eq!(meta.max_budget_usd, Some(2.0));
+        assert!(meta.files_written.contains("src/a.rs"));
+        assert_eq!(meta.snapshot_id.as_deref(), Some("abc"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn over_budget_sessions_are_not_restarted() {
+        let mut manager = stub_manager(4, spawn_sleeper);
+        manager.start_session(&start(), None).unwrap();
+        manager.abort_over_budget("s1", "Session over budget");
+        if let Some(meta) = manager.metadata.lock().unwrap().get_mut("s1") {
+            meta.over_budget = true;
+        }
+
+        let err = manager.restart_session("s1", false).unwrap_err();
+        assert!(err.message().contains("over its budget"));
+        assert!(manager.active_session_ids().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn forgetting_a_session_drops_its_retired_state() {
+        let mut manager = stub_manager(4, spawn_sleeper);
+        manager.start_session(&start(), None).unwrap();
+        manager.remove_session("s1");
+
+        assert!(manager.forget_session("s1").is_some());
+        assert!(manager.exits.is_empty());
+        assert!(manager.retired_starts.is_empty());
+        assert!(manager.restart_session("s1", false).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Worker accounting for spotting leaks in long-running instances
+
+use serde::Serialize;
+
+use super::SidecarManager;
+
+/// Snapshot of the worker table, taken after reaping
+#[derive(Debug, Serialize)]
+pub struct SidecarStats {
+    /// Workers whose process is still running
+    pub live_workers: usize,
+    /// Workers that had exited but were still registered; reaped by this call
+    pub exited_workers: usize,
+    /// Workers spawned for a session that had already had one
+    pub total_restarts: u64,
+}
+
+impl SidecarManager {
+    /// Count workers, removing any whose process has already exited
+    pub fn stats_and_reap(&mut self) -> SidecarStats {
+        let exited = self.reap_dead_workers();
+
+        SidecarStats {
+            live_workers: self.workers.len(),
+            exited_workers: exited.len(),
+            total_restarts: self.restarts,
+        }
+    }
+}
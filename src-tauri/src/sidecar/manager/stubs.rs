@@ -0,0 +1,55 @@
+//! Stand-ins for the Node worker so manager tests can run real sessions
+
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::super::error::SidecarError;
+use super::super::types::SidecarCommand;
+use super::SidecarManager;
+
+/// Stub workers spawned so far, across all tests
+pub static SPAWNED: AtomicUsize = AtomicUsize::new(0);
+
+/// A manager without an app whose workers are `spawn` processes
+pub fn stub_manager(
+    limit: usize,
+    spawn: fn(&str) -> Result<Child, SidecarError>,
+) -> SidecarManager {
+    let mut manager = SidecarManager::new(None, limit);
+    manager.spawn = spawn;
+    manager
+}
+
+/// Ignores its commands and runs until killed
+pub fn spawn_sleeper(_label: &str) -> Result<Child, SidecarError> {
+    spawn_stub(Command::new("sleep").arg("30"))
+}
+
+/// Reads the start command, then exits cleanly on the next one, like a
+/// worker handed `end_session`
+pub fn spawn_ender(_label: &str) -> Result<Child, SidecarError> {
+    spawn_stub(Command::new("sh").args(["-c", "read start; read end"]))
+}
+
+pub fn spawn_missing(_label: &str) -> Result<Child, SidecarError> {
+    Err(SidecarError::WorkerScriptMissing("no worker".to_string()))
+}
+
+fn spawn_stub(command: &mut Command) -> Result<Child, SidecarError> {
+    SPAWNED.fetch_add(1, Ordering::SeqCst);
+    command
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| SidecarError::Other(e.to_string()))
+}
+
+pub fn start(session_id: &str) -> SidecarCommand {
+    SidecarCommand::StartSession {
+        session_id: session_id.to_string(),
+        project_path: "/tmp".to_string(),
+        prompt: "hi".to_string(),
+        model: None,
+        max_budget_usd: None,
+        resume_session_id: None,
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::Write;
+use std::process::{Child, ExitStatus};
+
+use tauri::async_runtime::JoinHandle;
+
+use super::super::error::SidecarError;
+use super::super::types::SidecarCommand;
+
+/// One worker process per agent session
+pub(super) struct SessionWorker {
+    pub(super) child: Child,
+    /// stdout/stderr reader tasks on the shared async runtime
+    pub(super) readers: Vec<JoinHandle<()>>,
+    pub(super) start: StartParams,
+}
+
+/// What a worker was spawned with, kept so `restart_session` can respawn it
+#[derive(Clone)]
+pub(super) struct StartParams {
+    /// The `StartSession` or `PrewarmSession` command
+    pub(super) command: SidecarCommand,
+    /// Window the session's events go to; None broadcasts
+    pub(super) window_label: Option<String>,
+}
+
+impl SessionWorker {
+    /// Send a JSON-line command to this worker's stdin
+    pub(super) fn send(&mut self, json: &str) -> Result<(), String> {
+        let stdin = self
+            .child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| "Worker stdin not available".to_string())?;
+
+        stdin
+            .write_all(format!("{json}\n").as_bytes())
+            .map_err(|e| format!("Failed to write to worker stdin: {e}"))?;
+
+        stdin
+            .flush()
+            .map_err(|e| format!("Failed to flush worker stdin: {e}"))?;
+
+        Ok(())
+    }
+
+    /// How the worker exited, or None while it's still running
+    pub(super) fn exit(&mut self) -> Option<ExitStatus> {
+        self.child.try_wait().ok().flatten()
+    }
+
+    pub(super) fn exit_status(&mut self) -> Option<String> {
+        self.exit().map(|status| status.to_string())
+    }
+
+    /// Kill the worker process and describe how it ended. Its readers
+    /// finish on their own at EOF.
+    pub(super) fn kill(&mut self) -> String {
+        if let Some(status) = self.exit_status() {
+            return status;
+        }
+        let _ = self.child.kill();
+        match self.child.wait() {
+            Ok(status) => format!("killed by Central ({status})"),
+            Err(e) => format!("killed by Central (wait failed: {e})"),
+        }
+    }
+
+    /// Send SIGINT so the worker can cancel its in-flight request cleanly.
+    /// Goes through `kill` like the terminal's child cleanup (`pty::children`).
+    #[cfg(unix)]
+    pub(super) fn interrupt(&self) -> Result<(), SidecarError> {
+        let pid = self.child.id();
+        let status = std::process::Command::new("kill")
+            .args(["-INT", &pid.to_string()])
+            .status()
+            .map_err(|e| SidecarError::Other(format!("Failed to run kill: {e}")))?;
+        if status.success() {
+            return Ok(());
+        }
+        Err(SidecarError::Other(format!(
+            "Failed to send SIGINT to worker {pid}: kill exited with {status}"
+        )))
+    }
+
+    #[cfg(not(unix))]
+    pub(super) fn interrupt(&self) -> Result<(), SidecarError> {
+        Err(SidecarError::Other(
+            "SIGINT is not supported on this platform".to_string(),
+        ))
+    }
+
+    /// Kill the worker and cancel its readers without waiting for EOF
+    pub(super) fn stop(&mut self) {
+        self.kill();
+        self.readers.drain(..).for_each(|reader| reader.abort());
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use super::super::stubs::{spawn_sleeper, start};
+    use super::*;
+
+    #[test]
+    fn interrupt_delivers_sigint_to_the_worker() {
+        let mut worker = SessionWorker {
+            child: spawn_sleeper("s1").unwrap(),
+            readers: Vec::new(),
+            start: StartParams {
+                command: start("s1"),
+                window_label: None,
+            },
+        };
+
+        worker.interrupt().unwrap();
+
+        let status = worker.child.wait().unwrap();
+        // SIGINT is 2 on every unix
+        assert_eq!(status.signal(), Some(2));
+    }
+}
@@ -1,5 +1,19 @@
+mod budget;
+pub mod error;
+pub mod event_buffer;
+pub mod keep_alive;
 pub mod manager;
+pub mod models;
+mod process;
+mod reader;
+pub mod session_meta;
+mod stderr_tail;
+pub mod tool_history;
 pub mod types;
+mod verify;
 
-pub use manager::{create_sidecar_handle, SidecarHandle};
+pub use error::SidecarError;
+pub use manager::{create_sidecar_handle, drain_all, DrainReport, SidecarHandle, SidecarStats};
+pub use process::{worker_command_line, worker_env_report, WorkerEnvReport};
 pub use types::SidecarCommand;
+pub use verify::{verify_worker_script, WorkerVerification};
@@ -0,0 +1,126 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use super::process::{forward_stderr, spawn_worker};
+use super::types::{ModelInfo, SidecarCommand, SidecarEvent};
+use crate::debug_log;
+
+/// The worker has to boot Node and the SDK before it can answer
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Models reported by the last successful fetch; None until first asked
+pub type ModelCacheHandle = Arc<Mutex<Option<Vec<ModelInfo>>>>;
+
+/// Create an empty model cache for Tauri state
+pub fn create_model_cache_handle() -> ModelCacheHandle {
+    Arc::new(Mutex::new(None))
+}
+
+/// Return cached models, fetching from a worker on first use or when `refresh` is set.
+/// The cache lock is held during the fetch so concurrent callers share one worker.
+pub fn cached_or_fetch(cache: &ModelCacheHandle, refresh: bool) -> Result<Vec<ModelInfo>, String> {
+    let mut cached = cache
+        .lock()
+        .map_err(|e| format!("Model cache lock error: {e}"))?;
+
+    if let (Some(models), false) = (cached.as_ref(), refresh) {
+        return Ok(models.clone());
+    }
+
+    let models = fetch_models()?;
+    *cached = Some(models.clone());
+    Ok(models)
+}
+
+/// Spawn a short-lived worker, ask it for the model list, then kill it
+fn fetch_models() -> Result<Vec<ModelInfo>, String> {
+    let mut child = spawn_worker("model listing")?;
+    if let Some(stderr) = child.stderr.take() {
+        forward_stderr(stderr, "models".to_string(), None);
+    }
+
+    let json = serde_json::to_string(&SidecarCommand::ListModels)
+        .map_err(|e| format!("Failed to serialize command: {e}"))?;
+    let stdout = child.stdout.take();
+    let sent = child
+        .stdin
+        .as_mut()
+        .ok_or_else(|| "Worker stdin not available".to_string())
+        .and_then(|stdin| {
+            writeln!(stdin, "{json}").map_err(|e| format!("Failed to write to worker stdin: {e}"))
+        });
+
+    let result = sent.and_then(|_| {
+        let stdout = stdout.ok_or_else(|| "Worker stdout not available".to_string())?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(read_models_reply(stdout));
+        });
+        rx.recv_timeout(FETCH_TIMEOUT)
+            .map_err(|_| "Timed out waiting for the model list".to_string())?
+    });
+
+    let _ = child.kill();
+    let _ = child.wait();
+
+    match &result {
+        Ok(models) => debug_log::log(
+            "SIDECAR",
+            &format!("Worker reported {} models", models.len()),
+        ),
+        Err(e) => debug_log::log("SIDECAR", &format!("Model listing failed: {e}")),
+    }
+    result
+}
+
+/// Scan worker output for the `models_list` reply, skipping unrelated lines
+fn read_models_reply(stdout: impl Read) -> Result<Vec<ModelInfo>, String> {
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        match serde_json::from_str::<SidecarEvent>(line.trim()) {
+            Ok(SidecarEvent::ModelsList { models }) => return Ok(models),
+            Ok(SidecarEvent::Error { message }) => return Err(message),
+            _ => continue,
+        }
+    }
+    Err("Worker exited without reporting models".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_models_reply_skips_noise() {
+        let output = "not json\n{\"type\":\"models_list\",\"models\":[{\"id\":\"sonnet\",\"displayName\":\"Sonnet\"}]}\n";
+        let models = read_models_reply(output.as_bytes()).unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "sonnet");
+    }
+
+    #[test]
+    fn read_models_reply_surfaces_worker_errors() {
+        let output = "{\"type\":\"error\",\"message\":\"not logged in\"}\n";
+        assert_eq!(
+            read_models_reply(output.as_bytes()).unwrap_err(),
+            "not logged in"
+        );
+        assert!(read_models_reply("".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn cached_models_are_returned_without_fetching() {
+        let cache = create_model_cache_handle();
+        let model = ModelInfo {
+            id: "opus".to_string(),
+            display_name: "Opus".to_string(),
+            description: None,
+        };
+        *cache.lock().unwrap() = Some(vec![model.clone()]);
+
+        assert_eq!(cached_or_fetch(&cache, false).unwrap(), vec![model]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::process::ChildStdout;
+
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use super::budget::check_budget;
+use super::event_buffer::{sequence_event, EventBufferStore};
+use super::manager::SidecarHandle;
+use super::session_meta::{apply_event, SessionMetaStore};
+use super::tool_history::{record_event, ToolHistoryStore};
+use super::types::{AgentEventPayload, SidecarEvent};
+use crate::debug_log::{self, LogLevel};
+
+/// Everything a stdout reader task needs to route one session's events
+pub struct EventRouter {
+    /// None only in manager tests, where events have nowhere to go
+    pub app_handle: Option<AppHandle>,
+    pub metadata: SessionMetaStore,
+    pub tool_history: ToolHistoryStore,
+    pub event_buffers: EventBufferStore,
+    pub session_id: String,
+    /// Target window for this session's events; None broadcasts to all windows
+    pub window_label: Option<String>,
+}
+
+impl EventRouter {
+    fn emit(&self, payload: &AgentEventPayload) -> tauri::Result<()> {
+        emit_payload(
+            self.app_handle.as_ref(),
+            self.window_label.as_deref(),
+            payload,
+        )
+    }
+}
+
+/// Send an `agent-event` to the session's window, or to all windows
+pub fn emit_payload(
+    app_handle: Option<&AppHandle>,
+    window_label: Option<&str>,
+    payload: &AgentEventPayload,
+) -> tauri::Result<()> {
+    match (app_handle, window_label) {
+        (None, _) => Ok(()),
+        (Some(app), Some(label)) => app.emit_to(label, "agent-event", payload),
+        (Some(app), None) => app.emit("agent-event", payload),
+    }
+}
+
+/// Read a worker's stdout as a task on the shared async runtime rather than
+/// a dedicated OS thread, so many sessions don't mean many reader threads
+pub fn spawn_stdout_reader(stdout: ChildStdout, router: EventRouter) -> JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let sid = router.session_id.clone();
+        debug_log::log("SIDECAR", &format!("[{sid}] stdout reader started"));
+        match tokio::process::ChildStdout::from_std(stdout) {
+            Ok(stdout) => read_worker_output(stdout, &router).await,
+            Err(e) => debug_log::log("SIDECAR", &format!("[{sid}] stdout reader error: {e}")),
+        }
+        debug_log::log("SIDECAR", &format!("[{sid}] stdout reader ended"));
+    })
+}
+
+/// Read JSON-line events from a worker's stdout and emit via Tauri events
+async fn read_worker_output(stdout: tokio::process::ChildStdout, router: &EventRouter) {
+    let mut lines = BufReader::new(stdout).lines();
+    let session_id = router.session_id.as_str();
+
+    loop {
+        match lines.next_line().await {
+            Ok(Some(line)) => route_line(line.trim(), router),
+            Ok(None) => break,
+            Err(e) => {
+                debug_log::log("SIDECAR", &format!("[{session_id}] stdout read error: {e}"));
+                break;
+            }
+        }
+    }
+}
+
+/// Fold one event line into session state and forward it to the frontend
+fn route_line(trimmed: &str, router: &EventRouter) {
+    let session_id = router.session_id.as_str();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    debug_log::log_at(
+        LogLevel::Debug,
+        "SIDECAR-STDOUT",
+        &format!("[{session_id}] {trimmed}"),
+    );
+
+    match serde_json::from_str::<SidecarEvent>(trimmed) {
+        Ok(mut event) => {
+            let over_budget = match router.metadata.lock() {
+                Ok(mut store) => store.get_mut(session_id).and_then(|meta| {
+                    apply_event(meta, &mut event);
+                    check_budget(meta)
+                }),
+                Err(_) => None,
+            };
+            forward_event(router, event);
+            if let Some(error) = over_budget {
+                stop_over_budget(router, error);
+            }
+        }
+        Err(e) => {
+            debug_log::log(
+                "SIDECAR",
+                &format!("[{session_id}] PARSE ERROR: {e} — {trimmed}"),
+            );
+        }
+    }
+}
+
+fn forward_event(router: &EventRouter, event: SidecarEvent) {
+    let session_id = router.session_id.as_str();
+    record_event(&router.tool_history, session_id, &event);
+    let payload = sequence_event(&router.event_buffers, session_id, event);
+    match router.emit(&payload) {
+        Ok(_) => debug_log::log_at(
+            LogLevel::Debug,
+            "SIDECAR",
+            &format!("[{session_id}] event emitted OK"),
+        ),
+        Err(e) => debug_log::log("SIDECAR", &format!("[{session_id}] EMIT ERROR: {e}")),
+    }
+}
+
+/// Kill a session that went over budget and tell the frontend it failed.
+/// The metadata lock must already be released: the manager takes it too.
+fn stop_over_budget(router: &EventRouter, error: String) {
+    let session_id = router.session_id.clone();
+    match router
+        .app_handle
+        .as_ref()
+        .and_then(|app| app.try_state::<SidecarHandle>())
+        .map(|s| s.inner().clone())
+    {
+        Some(sidecar) => match sidecar.lock() {
+            Ok(mut manager) => manager.abort_over_budget(&session_id, &error),
+            Err(e) => debug_log::log("SIDECAR", &format!("Failed to lock sidecar: {e}")),
+        },
+        None => debug_log::log(
+            "SIDECAR",
+            &format!("[{session_id}] WARN: no sidecar to stop over-budget session"),
+        ),
+    }
+    forward_event(router, SidecarEvent::SessionFailed { session_id, error });
+}
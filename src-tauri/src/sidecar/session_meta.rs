@@ -0,0 +1,112 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use super::types::SidecarCommand;
+use crate::clock::now_ms;
+
+mod apply;
+mod usage;
+
+pub use apply::apply_event;
+pub use usage::{cost_by_model, ModelCost, SessionBudget, SessionUsage};
+
+/// What we know about a session beyond its worker process.
+/// Outlives the worker so completed sessions can still be reported on.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMetadata {
+    pub session_id: String,
+    pub project_path: String,
+    pub requested_model: Option<String>,
+    /// Model the worker actually ran with (differs from requested on fallback)
+    pub effective_model: Option<String>,
+    pub sdk_session_id: Option<String>,
+    /// Latest known spend — updated by `CostUpdate`, final on completion
+    pub total_cost_usd: Option<f64>,
+    /// Spend of the workers this session had before a restart; the current
+    /// worker only reports its own running total, which is added to this
+    #[serde(skip)]
+    pub earlier_cost_usd: f64,
+    pub max_budget_usd: Option<f64>,
+    pub duration_ms: Option<f64>,
+    pub started_at_ms: u64,
+    /// Paths passed to file-writing tools, as the agent gave them
+    pub files_written: BTreeSet<String>,
+    /// Tool calls so far by tool name, e.g. {"Read": 14, "Edit": 3}
+    pub tool_counts: BTreeMap<String, u64>,
+    /// Working tree snapshot taken before the agent ran, for `revert_session`
+    pub snapshot_id: Option<String>,
+    /// Last mode the worker confirmed; None means the default "ask"
+    pub permission_mode: Option<String>,
+    /// Spend passed `max_budget_usd` and Central stopped the worker
+    pub over_budget: bool,
+}
+
+/// Shared between the manager and the worker reader threads
+pub type SessionMetaStore = Arc<Mutex<HashMap<String, SessionMetadata>>>;
+
+/// Build the initial metadata from a StartSession or PrewarmSession command
+pub fn metadata_from_command(command: &SidecarCommand) -> Option<SessionMetadata> {
+    let (session_id, project_path, model, max_budget_usd) = match command {
+        SidecarCommand::StartSession {
+            session_id,
+            project_path,
+            model,
+            max_budget_usd,
+            ..
+        } => (session_id, project_path, model, *max_budget_usd),
+        SidecarCommand::PrewarmSession {
+            session_id,
+            project_path,
+            model,
+            ..
+        } => (session_id, project_path, model, None),
+        _ => return None,
+    };
+    Some(SessionMetadata {
+        session_id: session_id.clone(),
+        project_path: project_path.clone(),
+        requested_model: model.clone(),
+        effective_model: None,
+        sdk_session_id: None,
+        total_cost_usd: None,
+        earlier_cost_usd: 0.0,
+        max_budget_usd,
+        duration_ms: None,
+        started_at_ms: now_ms(),
+        files_written: BTreeSet::new(),
+        tool_counts: BTreeMap::new(),
+        snapshot_id: None,
+        permission_mode: None,
+        over_budget: false,
+    })
+}
+
+/// Commands and events shared by the metadata tests
+#[cfg(test)]
+mod fixtures {
+    use super::super::types::{SidecarCommand, SidecarEvent};
+
+    pub(super) fn start_command(model: Option<&str>) -> SidecarCommand {
+        SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp/p".to_string(),
+            prompt: "hi".to_string(),
+            model: model.map(String::from),
+            max_budget_usd: None,
+            resume_session_id: None,
+        }
+    }
+
+    pub(super) fn completed(model: Option<&str>, cost: f64) -> SidecarEvent {
+        SidecarEvent::SessionCompleted {
+            session_id: "s1".to_string(),
+            sdk_session_id: "sdk-1".to_string(),
+            total_cost_usd: Some(cost),
+            duration_ms: Some(10.0),
+            model: model.map(String::from),
+            requested_model: None,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Folding worker events into a session's metadata
+
+use super::super::types::SidecarEvent;
+use super::SessionMetadata;
+
+/// Tools that modify files, and the input field naming the file
+const WRITE_TOOLS: &[(&str, &str)] = &[
+    ("Write", "file_path"),
+    ("Edit", "file_path"),
+    ("MultiEdit", "file_path"),
+    ("NotebookEdit", "notebook_path"),
+];
+
+/// The file a tool call writes to, if it is a file-writing tool
+fn written_file(tool_name: &str, input: &serde_json::Value) -> Option<String> {
+    let (_, field) = WRITE_TOOLS.iter().find(|(name, _)| *name == tool_name)?;
+    input.get(field)?.as_str().map(String::from)
+}
+
+/// Fold a worker event into the session's metadata. Completion events are
+/// enriched with the requested model so listeners can attribute spend.
+/// Reported costs are raised to include workers from before a restart.
+pub fn apply_event(meta: &mut SessionMetadata, event: &mut SidecarEvent) {
+    match event {
+        SidecarEvent::SessionStarted { sdk_session_id, .. } => {
+            meta.sdk_session_id = Some(sdk_session_id.clone());
+        }
+        SidecarEvent::ToolUse {
+            tool_name, input, ..
+        } => {
+            *meta.tool_counts.entry(tool_name.clone()).or_default() += 1;
+            if let Some(path) = written_file(tool_name, input) {
+                meta.files_written.insert(path);
+            }
+        }
+        SidecarEvent::ModelChanged { model, .. } => {
+            meta.requested_model = Some(model.clone());
+        }
+        SidecarEvent::PermissionModeChanged { mode, .. } => {
+            meta.permission_mode = Some(mode.clone());
+        }
+        SidecarEvent::CostUpdate { cost_usd, .. } => {
+            *cost_usd += meta.earlier_cost_usd;
+            meta.total_cost_usd = Some(*cost_usd);
+        }
+        SidecarEvent::SessionCompleted {
+            sdk_session_id,
+            total_cost_usd,
+            duration_ms,
+            model,
+            requested_model,
+            ..
+        } => {
+            meta.sdk_session_id = Some(sdk_session_id.clone());
+            if let Some(cost) = total_cost_usd {
+                *cost += meta.earlier_cost_usd;
+                meta.total_cost_usd = Some(*cost);
+            }
+            meta.duration_ms = *duration_ms;
+            if model.is_some() {
+                meta.effective_model = model.clone();
+            }
+            *requested_model = meta.requested_model.clone();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{completed, start_command};
+    use super::super::metadata_from_command;
+    use super::*;
+
+    #[test]
+    fn completion_records_cost_and_echoes_requested_model() {
+        let mut meta = metadata_from_command(&start_command(Some("opus"))).unwrap();
+        let mut event = completed(Some("sonnet"), 0.5);
+
+        apply_event(&mut meta, &mut event);
+
+        assert_eq!(meta.total_cost_usd, Some(0.5));
+        assert_eq!(meta.effective_model.as_deref(), Some("sonnet"));
+        match event {
+            SidecarEvent::SessionCompleted {
+                requested_model, ..
+            } => {
+                assert_eq!(requested_model.as_deref(), Some("opus"));
+            }
+            _ => panic!("Expected SessionCompleted event"),
+        }
+    }
+
+    #[test]
+    fn write_tool_uses_are_recorded_as_written_files() {
+        let mut meta = metadata_from_command(&start_command(None)).unwrap();
+        let tool = |name: &str, input: serde_json::Value| SidecarEvent::ToolUse {
+            session_id: "s1".to_string(),
+            tool_name: name.to_string(),
+            input,
+        };
+
+        apply_event(
+            &mut meta,
+            &mut tool("Write", serde_json::json!({"file_path": "/tmp/p/a.rs"})),
+        );
+        apply_event(
+            &mut meta,
+            &mut tool("Edit", serde_json::json!({"file_path": "/tmp/p/a.rs"})),
+        );
+        apply_event(
+            &mut meta,
+            &mut tool("Read", serde_json::json!({"file_path": "/tmp/p/b.rs"})),
+        );
+
+        assert_eq!(
+            meta.files_written.iter().collect::<Vec<_>>(),
+            vec!["/tmp/p/a.rs"]
+        );
+        assert_eq!(meta.tool_counts.get("Edit"), Some(&1));
+        assert_eq!(meta.tool_counts.values().sum::<u64>(), 3);
+    }
+}
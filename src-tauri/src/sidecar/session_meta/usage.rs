@@ -0,0 +1,141 @@
+//! Spend views derived from session metadata
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::SessionMetadata;
+
+/// Live spend for a session, for a cost meter and budget warnings
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionUsage {
+    pub session_id: String,
+    pub cost_usd: Option<f64>,
+    pub max_budget_usd: Option<f64>,
+    /// cost / budget, when both are known
+    pub budget_used: Option<f64>,
+}
+
+impl From<&SessionMetadata> for SessionUsage {
+    fn from(meta: &SessionMetadata) -> Self {
+        let budget_used = match (meta.total_cost_usd, meta.max_budget_usd) {
+            (Some(cost), Some(budget)) if budget > 0.0 => Some(cost / budget),
+            _ => None,
+        };
+        Self {
+            session_id: meta.session_id.clone(),
+            cost_usd: meta.total_cost_usd,
+            max_budget_usd: meta.max_budget_usd,
+            budget_used,
+        }
+    }
+}
+
+/// "$0.42 of $1.00 used" — spend against the session's configured budget
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionBudget {
+    pub spent_usd: f64,
+    /// None when the session was started without a budget
+    pub max_budget_usd: Option<f64>,
+    pub remaining_usd: Option<f64>,
+}
+
+impl From<&SessionMetadata> for SessionBudget {
+    fn from(meta: &SessionMetadata) -> Self {
+        let spent_usd = meta.total_cost_usd.unwrap_or(0.0);
+        Self {
+            spent_usd,
+            max_budget_usd: meta.max_budget_usd,
+            remaining_usd: meta
+                .max_budget_usd
+                .map(|budget| (budget - spent_usd).max(0.0)),
+        }
+    }
+}
+
+/// Per-model cost rollup across completed sessions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelCost {
+    pub model: String,
+    pub sessions: usize,
+    pub total_cost_usd: f64,
+}
+
+/// Sum completed-session cost by the model that actually ran
+pub fn cost_by_model<'a>(sessions: impl Iterator<Item = &'a SessionMetadata>) -> Vec<ModelCost> {
+    let mut totals: HashMap<String, ModelCost> = HashMap::new();
+
+    for meta in sessions {
+        let Some(cost) = meta.total_cost_usd else {
+            continue;
+        };
+        let model = meta
+            .effective_model
+            .clone()
+            .or_else(|| meta.requested_model.clone())
+            .unwrap_or_else(|| "default".to_string());
+
+        let entry = totals.entry(model.clone()).or_insert_with(|| ModelCost {
+            model,
+            ..Default::default()
+        });
+        entry.sessions += 1;
+        entry.total_cost_usd += cost;
+    }
+
+    let mut result: Vec<ModelCost> = totals.into_values().collect();
+    result.sort_by(|a, b| a.model.cmp(&b.model));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::fixtures::{completed, start_command};
+    use super::super::{apply_event, metadata_from_command};
+    use super::*;
+    use crate::sidecar::types::{SidecarCommand, SidecarEvent};
+
+    #[test]
+    fn cost_updates_feed_session_usage() {
+        let mut meta = metadata_from_command(&SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp/p".to_string(),
+            prompt: "hi".to_string(),
+            model: None,
+            max_budget_usd: Some(2.0),
+            resume_session_id: None,
+        })
+        .unwrap();
+
+        apply_event(
+            &mut meta,
+            &mut SidecarEvent::CostUpdate {
+                session_id: "s1".to_string(),
+                cost_usd: 0.5,
+            },
+        );
+
+        let usage = SessionUsage::from(&meta);
+        assert_eq!(usage.cost_usd, Some(0.5));
+        assert_eq!(usage.budget_used, Some(0.25));
+
+        let budget = SessionBudget::from(&meta);
+        assert_eq!(budget.spent_usd, 0.5);
+        assert_eq!(budget.remaining_usd, Some(1.5));
+    }
+
+    #[test]
+    fn cost_by_model_prefers_effective_model() {
+        let mut a = metadata_from_command(&start_command(Some("opus"))).unwrap();
+        apply_event(&mut a, &mut completed(Some("sonnet"), 0.25));
+        let mut b = metadata_from_command(&start_command(Some("sonnet"))).unwrap();
+        apply_event(&mut b, &mut completed(None, 0.75));
+        let c = metadata_from_command(&start_command(None)).unwrap();
+
+        let costs = cost_by_model([a, b, c].iter());
+        assert_eq!(costs.len(), 1);
+        assert_eq!(costs[0].model, "sonnet");
+        assert_eq!(costs[0].sessions, 2);
+        assert!((costs[0].total_cost_usd - 1.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Lines of worker stderr kept per session for diagnostics
+const MAX_LINES_PER_SESSION: usize = 200;
+
+/// Recent stderr per session, shared with the stderr reader tasks
+pub type StderrTailStore = Arc<Mutex<HashMap<String, VecDeque<String>>>>;
+
+/// Remember a stderr line, dropping the oldest past the cap
+pub fn record_line(store: &StderrTailStore, session_id: &str, line: &str) {
+    let Ok(mut sessions) = store.lock() else {
+        return;
+    };
+    let tail = sessions.entry(session_id.to_string()).or_default();
+    if tail.len() == MAX_LINES_PER_SESSION {
+        tail.pop_front();
+    }
+    tail.push_back(line.to_string());
+}
+
+/// A session's captured stderr, oldest first
+pub fn tail(store: &StderrTailStore, session_id: &str) -> Vec<String> {
+    store
+        .lock()
+        .ok()
+        .and_then(|s| s.get(session_id).map(|t| t.iter().cloned().collect()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_keeps_only_the_latest_lines() {
+        let store = StderrTailStore::default();
+        for i in 0..MAX_LINES_PER_SESSION + 5 {
+            record_line(&store, "s1", &format!("line {i}"));
+        }
+
+        let lines = tail(&store, "s1");
+        assert_eq!(lines.len(), MAX_LINES_PER_SESSION);
+        assert_eq!(lines[0], "line 5");
+        assert!(tail(&store, "other").is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use super::types::SidecarEvent;
+use crate::clock::now_ms;
+
+/// Oldest entries are dropped past this so long sessions stay bounded
+const MAX_ENTRIES_PER_SESSION: usize = 2000;
+
+/// One tool-related event, kept so a reloaded UI can rebuild its activity timeline
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ToolHistoryEntry {
+    ToolUse {
+        tool_name: String,
+        input: serde_json::Value,
+        at_ms: u64,
+    },
+    ToolResult {
+        tool_name: String,
+        output: String,
+        cancelled: bool,
+        at_ms: u64,
+    },
+    ApprovalRequest {
+        request_id: String,
+        tool_name: String,
+        input: serde_json::Value,
+        at_ms: u64,
+    },
+}
+
+/// Per-session tool logs, shared between the manager and the worker reader threads
+pub type ToolHistoryStore = Arc<Mutex<HashMap<String, VecDeque<ToolHistoryEntry>>>>;
+
+/// The history entry for a tool event, or None for everything else
+pub fn entry_from_event(event: &SidecarEvent) -> Option<ToolHistoryEntry> {
+    let at_ms = now_ms();
+    match event {
+        SidecarEvent::ToolUse {
+            tool_name, input, ..
+        } => Some(ToolHistoryEntry::ToolUse {
+            tool_name: tool_name.clone(),
+            input: input.clone(),
+            at_ms,
+        }),
+        SidecarEvent::ToolResult {
+            tool_name,
+            output,
+            cancelled,
+            ..
+        } => Some(ToolHistoryEntry::ToolResult {
+            tool_name: tool_name.clone(),
+            output: output.clone(),
+            cancelled: cancelled.unwrap_or(false),
+            at_ms,
+        }),
+        SidecarEvent::ToolApprovalRequest {
+            request_id,
+            tool_name,
+            input,
+            ..
+        } => Some(ToolHistoryEntry::ApprovalRequest {
+            request_id: request_id.clone(),
+            tool_name: tool_name.clone(),
+            input: input.clone(),
+            at_ms,
+        }),
+        _ => None,
+    }
+}
+
+/// Append a session's tool event to its log, if it is one
+pub fn record_event(store: &ToolHistoryStore, session_id: &str, event: &SidecarEvent) {
+    let Some(entry) = entry_from_event(event) else {
+        return;
+    };
+    let Ok(mut sessions) = store.lock() else {
+        return;
+    };
+
+    let log = sessions.entry(session_id.to_string()).or_default();
+    if log.len() >= MAX_ENTRIES_PER_SESSION {
+        log.pop_front();
+    }
+    log.push_back(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_use(name: &str) -> SidecarEvent {
+        SidecarEvent::ToolUse {
+            session_id: "s1".to_string(),
+            tool_name: name.to_string(),
+            input: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn records_tool_events_in_order_and_ignores_others() {
+        let store = ToolHistoryStore::default();
+        record_event(&store, "s1", &tool_use("Read"));
+        record_event(
+            &store,
+            "s1",
+            &SidecarEvent::ContentDelta {
+                session_id: "s1".to_string(),
+                delta: "hi".to_string(),
+            },
+        );
+        record_event(
+            &store,
+            "s1",
+            &SidecarEvent::ToolResult {
+                session_id: "s1".to_string(),
+                tool_name: "Read".to_string(),
+                output: "ok".to_string(),
+                cancelled: None,
+            },
+        );
+
+        let sessions = store.lock().unwrap();
+        let log = &sessions["s1"];
+        assert_eq!(log.len(), 2);
+        assert!(matches!(log[0], ToolHistoryEntry::ToolUse { .. }));
+        assert!(matches!(
+            log[1],
+            ToolHistoryEntry::ToolResult {
+                cancelled: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn drops_oldest_entries_past_the_cap() {
+        let store = ToolHistoryStore::default();
+        record_event(&store, "s1", &tool_use("first"));
+        for _ in 0..MAX_ENTRIES_PER_SESSION {
+            record_event(&store, "s1", &tool_use("Bash"));
+        }
+
+        let sessions = store.lock().unwrap();
+        let log = &sessions["s1"];
+        assert_eq!(log.len(), MAX_ENTRIES_PER_SESSION);
+        assert!(
+            matches!(&log[0], ToolHistoryEntry::ToolUse { tool_name, .. } if tool_name == "Bash")
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Commands Rust writes to a worker's stdin
+
+use serde::Serialize;
+
+/// Commands sent from Rust to the per-session worker via stdin JSON-lines
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+pub enum SidecarCommand {
+    StartSession {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "projectPath")]
+        project_path: String,
+        prompt: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(rename = "maxBudgetUsd", skip_serializing_if = "Option::is_none")]
+        max_budget_usd: Option<f64>,
+        #[serde(rename = "resumeSessionId", skip_serializing_if = "Option::is_none")]
+        resume_session_id: Option<String>,
+    },
+    /// Spawn the worker and initialize the SDK without a prompt; the first
+    /// `send_message` becomes the opening prompt
+    PrewarmSession {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "projectPath")]
+        project_path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// Continue this SDK session instead of opening a new conversation
+        #[serde(rename = "resumeSessionId", skip_serializing_if = "Option::is_none")]
+        resume_session_id: Option<String>,
+    },
+    SendMessage {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        message: String,
+    },
+    AbortSession {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+    },
+    EndSession {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+    },
+    CancelTool {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+    },
+    ToolApprovalResponse {
+        #[serde(rename = "requestId")]
+        request_id: String,
+        allowed: bool,
+        #[serde(rename = "updatedPermissions", skip_serializing_if = "Option::is_none")]
+        updated_permissions: Option<serde_json::Value>,
+    },
+    /// Interrupt the current turn, then send `message` as the next prompt
+    Reprompt {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        message: String,
+    },
+    /// Switch a running session to another model, keeping its conversation
+    SetModel {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        model: String,
+    },
+    /// Change how tool calls are approved for the rest of the session:
+    /// "ask", "auto_edit", or "plan"
+    SetPermissionMode {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        mode: String,
+    },
+    /// Ask a short-lived worker which models the SDK/account can use
+    ListModels,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_start_session_command() {
+        let cmd = SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp/project".to_string(),
+            prompt: "Write tests".to_string(),
+            model: Some("claude-opus-4".to_string()),
+            max_budget_usd: Some(1.0),
+            resume_session_id: Some("sdk-abc-123".to_string()),
+        };
+
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"start_session\""));
+        assert!(json.contains("\"sessionId\":\"s1\""));
+        assert!(json.contains("\"projectPath\":\"/tmp/project\""));
+        assert!(json.contains("\"prompt\":\"Write tests\""));
+        assert!(json.contains("\"model\":\"claude-opus-4\""));
+        assert!(json.contains("\"maxBudgetUsd\":1.0"));
+        assert!(json.contains("\"resumeSessionId\":\"sdk-abc-123\""));
+    }
+
+    #[test]
+    fn serialize_prewarm_session_command() {
+        let cmd = SidecarCommand::PrewarmSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp/project".to_string(),
+            model: None,
+            resume_session_id: None,
+        };
+
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"prewarm_session\""));
+        assert!(json.contains("\"projectPath\":\"/tmp/project\""));
+        assert!(!json.contains("\"prompt\""));
+        assert!(!json.contains("\"model\""));
+        assert!(!json.contains("\"resumeSessionId\""));
+    }
+
+    #[test]
+    fn serialize_send_message_command() {
+        let cmd = SidecarCommand::SendMessage {
+            session_id: "s1".to_string(),
+            message: "Follow up".to_string(),
+        };
+
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"send_message\""));
+        assert!(json.contains("\"sessionId\":\"s1\""));
+        assert!(json.contains("\"message\":\"Follow up\""));
+    }
+
+    #[test]
+    fn serialize_abort_session_command() {
+        let cmd = SidecarCommand::AbortSession {
+            session_id: "s2".to_string(),
+        };
+
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"abort_session\""));
+        assert!(json.contains("\"sessionId\":\"s2\""));
+    }
+
+    #[test]
+    fn serialize_end_session_command() {
+        let cmd = SidecarCommand::EndSession {
+            session_id: "s1".to_string(),
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"end_session\""));
+        assert!(json.contains("\"sessionId\":\"s1\""));
+    }
+
+    #[test]
+    fn serialize_reprompt_command() {
+        let cmd = SidecarCommand::Reprompt {
+            session_id: "s1".to_string(),
+            message: "Do this instead".to_string(),
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"reprompt\""));
+        assert!(json.contains("\"message\":\"Do this instead\""));
+    }
+
+    #[test]
+    fn serialize_set_permission_mode_command() {
+        let cmd = SidecarCommand::SetPermissionMode {
+            session_id: "s1".to_string(),
+            mode: "auto_edit".to_string(),
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"set_permission_mode","sessionId":"s1","mode":"auto_edit"}"#
+        );
+    }
+
+    #[test]
+    fn serialize_cancel_tool_command() {
+        let cmd = SidecarCommand::CancelTool {
+            session_id: "s1".to_string(),
+            tool_name: "Bash".to_string(),
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"cancel_tool\""));
+        assert!(json.contains("\"sessionId\":\"s1\""));
+        assert!(json.contains("\"toolName\":\"Bash\""));
+    }
+
+    #[test]
+    fn serialize_tool_approval_response() {
+        let cmd = SidecarCommand::ToolApprovalResponse {
+            request_id: "apr_1".to_string(),
+            allowed: true,
+            updated_permissions: None,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"tool_approval_response\""));
+        assert!(json.contains("\"requestId\":\"apr_1\""));
+        assert!(json.contains("\"allowed\":true"));
+    }
+
+    #[test]
+    fn serialize_list_models_command() {
+        let json = serde_json::to_string(&SidecarCommand::ListModels).unwrap();
+        assert_eq!(json, r#"{"type":"list_models"}"#);
+    }
+
+    #[test]
+    fn serialize_start_session_without_model() {
+        let cmd = SidecarCommand::StartSession {
+            session_id: "s1".to_string(),
+            project_path: "/tmp".to_string(),
+            prompt: "test".to_string(),
+            model: None,
+            max_budget_usd: None,
+            resume_session_id: None,
+        };
+
+        let json = serde_json::to_string(&cmd).unwrap();
+        // Optional fields should be skipped when None
+        assert!(!json.contains("\"model\""));
+        assert!(!json.contains("\"maxBudgetUsd\""));
+        assert!(!json.contains("\"resumeSessionId\""));
+    }
+}
@@ -0,0 +1,168 @@
+//! Events a worker writes to stdout, and how they reach the frontend
+
+use serde::{Deserialize, Serialize};
+
+use super::ModelInfo;
+
+/// Events received from the per-session worker via stdout JSON-lines
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SidecarEvent {
+    SessionStarted {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "sdkSessionId")]
+        sdk_session_id: String,
+    },
+    Message {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        role: String,
+        content: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        thinking: Option<String>,
+        #[serde(rename = "toolCalls", skip_serializing_if = "Option::is_none")]
+        tool_calls: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        usage: Option<serde_json::Value>,
+    },
+    ToolUse {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+        input: serde_json::Value,
+    },
+    ToolResult {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+        output: String,
+        /// Set when the tool was stopped by a `CancelTool` command
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cancelled: Option<bool>,
+    },
+    ToolApprovalRequest {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "requestId")]
+        request_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+        input: serde_json::Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        suggestions: Option<serde_json::Value>,
+    },
+    ContentDelta {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        delta: String,
+    },
+    ThinkingDelta {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        delta: String,
+    },
+    /// End of a streamed thinking block (`done: true`, empty `text`). The
+    /// chunks themselves arrive as `ThinkingDelta`.
+    Thinking {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        text: String,
+        done: bool,
+    },
+    ToolProgress {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+        #[serde(rename = "elapsedSeconds")]
+        elapsed_seconds: f64,
+    },
+    /// A `CancelTool` hit a tool that was already running. The SDK can't stop
+    /// one tool, so the whole turn was interrupted.
+    TurnInterrupted {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "toolName")]
+        tool_name: String,
+    },
+    SessionCompleted {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "sdkSessionId")]
+        sdk_session_id: String,
+        #[serde(rename = "totalCostUsd", skip_serializing_if = "Option::is_none")]
+        total_cost_usd: Option<f64>,
+        #[serde(rename = "durationMs", skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<f64>,
+        /// Model the worker actually ran with, as reported by the SDK
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// Model requested at session start — filled in by Rust, not the worker
+        #[serde(
+            rename = "requestedModel",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        requested_model: Option<String>,
+    },
+    /// The worker accepted a `SetModel`
+    ModelChanged {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        model: String,
+    },
+    /// The worker applied a `SetPermissionMode`
+    PermissionModeChanged {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        mode: String,
+    },
+    /// Progress of a `Reprompt`: "interrupting", "interrupted", then "sent"
+    RepromptStatus {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        stage: String,
+    },
+    /// Running spend for a session, reported as turns finish
+    CostUpdate {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        #[serde(rename = "costUsd")]
+        cost_usd: f64,
+    },
+    SessionFailed {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        error: String,
+    },
+    Error {
+        message: String,
+    },
+    /// Reply to `ListModels`
+    ModelsList {
+        models: Vec<ModelInfo>,
+    },
+    RateLimitStatus {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        status: String,
+        #[serde(rename = "resetsAt")]
+        resets_at: f64,
+        #[serde(rename = "rateLimitType")]
+        rate_limit_type: String,
+    },
+}
+
+/// Payload emitted to the frontend via Tauri events
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentEventPayload {
+    /// Per-session sequence number, for `replay_agent_events` after a reload
+    pub seq: u64,
+    pub event: SidecarEvent,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,287 @@
+use super::*;
+
+#[test]
+fn deserialize_message_event_full() {
+    let json = r#"{
+            "type":"message",
+            "sessionId":"s1",
+            "role":"assistant",
+            "content":"Hello",
+            "thinking":"Let me think...",
+            "toolCalls":[{"name":"write"}],
+            "usage":{"input_tokens":100}
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::Message {
+            session_id,
+            role,
+            content,
+            thinking,
+            tool_calls,
+            usage,
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(role, "assistant");
+            assert_eq!(content, "Hello");
+            assert_eq!(thinking.unwrap(), "Let me think...");
+            assert!(tool_calls.is_some());
+            assert!(usage.is_some());
+        }
+        _ => panic!("Expected Message event"),
+    }
+}
+
+#[test]
+fn deserialize_message_event_minimal() {
+    let json = r#"{
+            "type":"message",
+            "sessionId":"s1",
+            "role":"assistant",
+            "content":"Hi"
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::Message {
+            thinking,
+            tool_calls,
+            usage,
+            ..
+        } => {
+            assert!(thinking.is_none());
+            assert!(tool_calls.is_none());
+            assert!(usage.is_none());
+        }
+        _ => panic!("Expected Message event"),
+    }
+}
+
+#[test]
+fn deserialize_tool_use_event() {
+    let json = r#"{
+            "type":"tool_use",
+            "sessionId":"s1",
+            "toolName":"write_file",
+            "input":{"path":"test.rs","content":"fn main() {}"}
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ToolUse {
+            session_id,
+            tool_name,
+            input,
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(tool_name, "write_file");
+            assert_eq!(input["path"], "test.rs");
+        }
+        _ => panic!("Expected ToolUse event"),
+    }
+}
+
+#[test]
+fn deserialize_tool_result_event() {
+    let json = r#"{
+            "type":"tool_result",
+            "sessionId":"s1",
+            "toolName":"write_file",
+            "output":"File written successfully"
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ToolResult {
+            tool_name, output, ..
+        } => {
+            assert_eq!(tool_name, "write_file");
+            assert_eq!(output, "File written successfully");
+        }
+        _ => panic!("Expected ToolResult event"),
+    }
+}
+
+#[test]
+fn deserialize_cancelled_tool_result_event() {
+    let json = r#"{"type":"tool_result","sessionId":"s1","toolName":"Bash","output":"Cancelled by user","cancelled":true}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ToolResult { cancelled, .. } => {
+            assert_eq!(cancelled, Some(true));
+        }
+        _ => panic!("Expected ToolResult event"),
+    }
+}
+
+#[test]
+fn deserialize_turn_interrupted_event() {
+    let json = r#"{"type":"turn_interrupted","sessionId":"s1","toolName":"Bash"}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::TurnInterrupted { tool_name, .. } => assert_eq!(tool_name, "Bash"),
+        _ => panic!("Expected TurnInterrupted event"),
+    }
+}
+
+#[test]
+fn deserialize_session_failed_event() {
+    let json = r#"{"type":"session_failed","sessionId":"s1","error":"timeout"}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::SessionFailed { session_id, error } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(error, "timeout");
+        }
+        _ => panic!("Expected SessionFailed event"),
+    }
+}
+
+#[test]
+fn deserialize_error_event() {
+    let json = r#"{"type":"error","message":"SDK unavailable"}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::Error { message } => {
+            assert_eq!(message, "SDK unavailable");
+        }
+        _ => panic!("Expected Error event"),
+    }
+}
+
+#[test]
+fn serialize_agent_event_payload() {
+    let payload = AgentEventPayload {
+        seq: 3,
+        event: SidecarEvent::SessionStarted {
+            session_id: "s1".to_string(),
+            sdk_session_id: "sdk-1".to_string(),
+        },
+    };
+    let json = serde_json::to_string(&payload).unwrap();
+    assert!(json.contains("\"event\""));
+    assert!(json.contains("\"seq\":3"));
+    assert!(json.contains("\"type\":\"session_started\""));
+}
+
+#[test]
+fn deserialize_tool_approval_request_event() {
+    let json = r#"{
+            "type":"tool_approval_request",
+            "sessionId":"s1",
+            "requestId":"apr_1",
+            "toolName":"write_file",
+            "input":{"path":"test.rs"}
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ToolApprovalRequest {
+            session_id,
+            request_id,
+            tool_name,
+            ..
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(request_id, "apr_1");
+            assert_eq!(tool_name, "write_file");
+        }
+        _ => panic!("Expected ToolApprovalRequest event"),
+    }
+}
+
+#[test]
+fn deserialize_content_delta_event() {
+    let json = r#"{"type":"content_delta","sessionId":"s1","delta":"Hello "}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ContentDelta { session_id, delta } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(delta, "Hello ");
+        }
+        _ => panic!("Expected ContentDelta event"),
+    }
+}
+
+#[test]
+fn deserialize_thinking_delta_event() {
+    let json = r#"{"type":"thinking_delta","sessionId":"s1","delta":"Let me "}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ThinkingDelta { session_id, delta } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(delta, "Let me ");
+        }
+        _ => panic!("Expected ThinkingDelta event"),
+    }
+}
+
+#[test]
+fn thinking_event_round_trips() {
+    let json = r#"{"type":"thinking","sessionId":"s1","text":"Hmm","done":false}"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match &event {
+        SidecarEvent::Thinking {
+            session_id,
+            text,
+            done,
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(text, "Hmm");
+            assert!(!done);
+        }
+        _ => panic!("Expected Thinking event"),
+    }
+
+    let out = serde_json::to_string(&event).unwrap();
+    assert!(out.contains("\"type\":\"thinking\""));
+    assert!(out.contains("\"done\":false"));
+}
+
+#[test]
+fn deserialize_tool_progress_event() {
+    let json = r#"{
+            "type":"tool_progress",
+            "sessionId":"s1",
+            "toolName":"bash",
+            "elapsedSeconds":5.2
+        }"#;
+    let event: SidecarEvent = serde_json::from_str(json).unwrap();
+    match event {
+        SidecarEvent::ToolProgress {
+            session_id,
+            tool_name,
+            elapsed_seconds,
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(tool_name, "bash");
+            assert!((elapsed_seconds - 5.2).abs() < 0.01);
+        }
+        _ => panic!("Expected ToolProgress event"),
+    }
+}
+
+#[test]
+fn roundtrip_message_event_serialization() {
+    let event = SidecarEvent::Message {
+        session_id: "s1".to_string(),
+        role: "assistant".to_string(),
+        content: "Hello world".to_string(),
+        thinking: Some("Thinking...".to_string()),
+        tool_calls: None,
+        usage: None,
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+    let deserialized: SidecarEvent = serde_json::from_str(&json).unwrap();
+
+    match deserialized {
+        SidecarEvent::Message {
+            session_id,
+            content,
+            thinking,
+            ..
+        } => {
+            assert_eq!(session_id, "s1");
+            assert_eq!(content, "Hello world");
+            assert_eq!(thinking.unwrap(), "Thinking...");
+        }
+        _ => panic!("Roundtrip failed"),
+    }
+}
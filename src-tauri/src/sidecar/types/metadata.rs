@@ -0,0 +1,126 @@
+//! Models and session details the worker reports
+
+use serde::{Deserialize, Serialize};
+
+/// A model the worker's SDK reports as available
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ModelInfo {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SidecarEvent;
+
+    #[test]
+    fn deserialize_models_list_event() {
+        let json = r#"{"type":"models_list","models":[{"id":"opus","displayName":"Opus","description":"Most capable"}]}"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::ModelsList { models } => {
+                assert_eq!(models.len(), 1);
+                assert_eq!(models[0].id, "opus");
+                assert_eq!(models[0].display_name, "Opus");
+            }
+            _ => panic!("Expected ModelsList event"),
+        }
+    }
+
+    #[test]
+    fn deserialize_cost_update_event() {
+        let json = r#"{"type":"cost_update","sessionId":"s1","costUsd":0.12}"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::CostUpdate {
+                session_id,
+                cost_usd,
+            } => {
+                assert_eq!(session_id, "s1");
+                assert!((cost_usd - 0.12).abs() < f64::EPSILON);
+            }
+            _ => panic!("Expected CostUpdate event"),
+        }
+    }
+
+    #[test]
+    fn deserialize_rate_limit_status_event() {
+        let json = r#"{
+            "type":"rate_limit_status",
+            "sessionId":"s1",
+            "status":"allowed",
+            "resetsAt":1771822800,
+            "rateLimitType":"five_hour"
+        }"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::RateLimitStatus {
+                session_id,
+                status,
+                resets_at,
+                rate_limit_type,
+            } => {
+                assert_eq!(session_id, "s1");
+                assert_eq!(status, "allowed");
+                assert!((resets_at - 1771822800.0).abs() < 0.01);
+                assert_eq!(rate_limit_type, "five_hour");
+            }
+            _ => panic!("Expected RateLimitStatus event"),
+        }
+    }
+
+    #[test]
+    fn deserialize_session_completed_with_model() {
+        let json = r#"{"type":"session_completed","sessionId":"s1","sdkSessionId":"sdk-abc","model":"claude-sonnet-4"}"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::SessionCompleted { model, .. } => {
+                assert_eq!(model.as_deref(), Some("claude-sonnet-4"));
+            }
+            _ => panic!("Expected SessionCompleted event"),
+        }
+    }
+
+    #[test]
+    fn deserialize_session_started_event() {
+        let json = r#"{"type":"session_started","sessionId":"s1","sdkSessionId":"sdk-abc-123"}"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::SessionStarted {
+                session_id,
+                sdk_session_id,
+            } => {
+                assert_eq!(session_id, "s1");
+                assert_eq!(sdk_session_id, "sdk-abc-123");
+            }
+            _ => panic!("Expected SessionStarted event"),
+        }
+    }
+
+    #[test]
+    fn deserialize_session_completed_event() {
+        let json = r#"{"type":"session_completed","sessionId":"s1","sdkSessionId":"sdk-abc","totalCostUsd":0.01,"durationMs":1500}"#;
+        let event: SidecarEvent = serde_json::from_str(json).unwrap();
+        match event {
+            SidecarEvent::SessionCompleted {
+                session_id,
+                sdk_session_id,
+                total_cost_usd,
+                duration_ms,
+                model,
+                requested_model,
+            } => {
+                assert_eq!(session_id, "s1");
+                assert_eq!(sdk_session_id, "sdk-abc");
+                assert_eq!(total_cost_usd, Some(0.01));
+                assert_eq!(duration_ms, Some(1500.0));
+                assert!(model.is_none());
+                assert!(requested_model.is_none());
+            }
+            _ => panic!("Expected SessionCompleted event"),
+        }
+    }
+}
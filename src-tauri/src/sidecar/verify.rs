@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::process::resolve_worker_path;
+use crate::content_hash::content_hash;
+
+/// Worker script hash recorded by build.rs, when the script was available
+const EXPECTED_WORKER_HASH: Option<&str> = option_env!("CENTRAL_WORKER_HASH");
+
+/// Whether the worker script can be used, for install-corruption diagnostics
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WorkerVerification {
+    pub path: Option<String>,
+    /// "ok", "missing", "unreadable", "empty" or "hash_mismatch"
+    pub status: String,
+    pub size: Option<u64>,
+    pub hash: Option<String>,
+    /// None when the build didn't record a hash, so only readability is checked
+    pub expected_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Resolve the worker script the way `spawn_worker` does and check it
+pub fn verify_worker_script() -> WorkerVerification {
+    match resolve_worker_path() {
+        Ok(path) => check_worker_file(&path, EXPECTED_WORKER_HASH),
+        Err(e) => WorkerVerification {
+            path: None,
+            status: "missing".to_string(),
+            size: None,
+            hash: None,
+            expected_hash: EXPECTED_WORKER_HASH.map(String::from),
+            error: Some(e),
+        },
+    }
+}
+
+fn check_worker_file(path: &str, expected: Option<&str>) -> WorkerVerification {
+    let mut report = WorkerVerification {
+        path: Some(path.to_string()),
+        status: "ok".to_string(),
+        size: None,
+        hash: None,
+        expected_hash: expected.map(String::from),
+        error: None,
+    };
+    let bytes = match std::fs::read(Path::new(path)) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            report.status = "unreadable".to_string();
+            report.error = Some(format!("Cannot read worker script: {e}"));
+            return report;
+        }
+    };
+
+    let hash = content_hash(&bytes);
+    report.size = Some(bytes.len() as u64);
+    if bytes.is_empty() {
+        report.status = "empty".to_string();
+        report.error = Some("Worker script is empty".to_string());
+    } else if expected.is_some_and(|e| e != hash) {
+        report.status = "hash_mismatch".to_string();
+        report.error =
+            Some("Worker script differs from the one this build shipped with".to_string());
+    }
+    report.hash = Some(hash);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_reports_mismatch_and_unreadable() {
+        let temp = std::env::temp_dir().join(format!("central_verify_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let script = temp.join("session-worker.ts");
+        std::fs::write(&script, "console.log(1)").unwrap();
+        let path = script.to_string_lossy().to_string();
+        let hash = content_hash(b"console.log(1)");
+
+        assert_eq!(check_worker_file(&path, Some(&hash)).status, "ok");
+        assert_eq!(check_worker_file(&path, None).status, "ok");
+        assert_eq!(
+            check_worker_file(&path, Some("0000")).status,
+            "hash_mismatch"
+        );
+        let missing = temp.join("nope.ts").to_string_lossy().to_string();
+        assert_eq!(check_worker_file(&missing, None).status, "unreadable");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
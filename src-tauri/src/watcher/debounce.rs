@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use crate::commands::files::skip::should_skip;
+
+/// A burst that never goes quiet is still flushed after this many windows
+pub(super) const MAX_DELAY_WINDOWS: u32 = 10;
+
+/// Coalesces changed paths until the tree has been quiet for `window`
+pub struct Debouncer {
+    window: Duration,
+    pending: BTreeSet<String>,
+    first_change: Option<Instant>,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: BTreeSet::new(),
+            first_change: None,
+            last_change: None,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Queue changed paths, dropping anything under the skip list
+    pub fn add(&mut self, paths: Vec<String>, now: Instant) {
+        let mut relevant = paths.into_iter().filter(|p| !is_skipped_path(p)).peekable();
+        if relevant.peek().is_none() {
+            return;
+        }
+        self.pending.extend(relevant);
+        self.first_change.get_or_insert(now);
+        self.last_change = Some(now);
+    }
+
+    /// The deduplicated, sorted batch once quiet (or overdue); None otherwise
+    pub fn take_ready(&mut self, now: Instant) -> Option<Vec<String>> {
+        let (first, last) = (self.first_change?, self.last_change?);
+        let quiet = now.duration_since(last) >= self.window;
+        let overdue = now.duration_since(first) >= self.window * MAX_DELAY_WINDOWS;
+        if !quiet && !overdue {
+            return None;
+        }
+
+        self.first_change = None;
+        self.last_change = None;
+        Some(std::mem::take(&mut self.pending).into_iter().collect())
+    }
+}
+
+/// True when any component of a relative path is on the skip list
+fn is_skipped_path(path: &str) -> bool {
+    path.split(['/', '\\']).any(should_skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn waits_for_quiet_window_and_dedupes() {
+        let t0 = Instant::now();
+        let mut debouncer = Debouncer::new(WINDOW);
+        debouncer.add(vec!["b.rs".to_string(), "a.rs".to_string()], t0);
+        debouncer.add(vec!["a.rs".to_string()], t0 + Duration::from_millis(50));
+
+        assert!(debouncer
+            .take_ready(t0 + Duration::from_millis(100))
+            .is_none());
+        assert_eq!(
+            debouncer.take_ready(t0 + Duration::from_millis(150)),
+            Some(vec!["a.rs".to_string(), "b.rs".to_string()])
+        );
+        assert!(!debouncer.has_pending());
+    }
+
+    #[test]
+    fn flushes_a_never_ending_burst() {
+        let t0 = Instant::now();
+        let mut debouncer = Debouncer::new(WINDOW);
+        for i in 0..=MAX_DELAY_WINDOWS * 2 {
+            let now = t0 + Duration::from_millis(50) * i;
+            debouncer.add(vec![format!("f{i}.rs")], now);
+            if debouncer.take_ready(now).is_some() {
+                return;
+            }
+        }
+        panic!("burst was never flushed");
+    }
+
+    #[test]
+    fn drops_paths_under_skipped_directories() {
+        let mut debouncer = Debouncer::new(WINDOW);
+        debouncer.add(
+            vec![
+                ".git/index".to_string(),
+                "web/node_modules/x.js".to_string(),
+            ],
+            Instant::now(),
+        );
+        assert!(!debouncer.has_pending());
+    }
+}
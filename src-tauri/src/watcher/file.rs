@@ -0,0 +1,144 @@
+//! Single-file polling for editor tabs that only care about one path
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::debounce::MAX_DELAY_WINDOWS;
+use crate::debug_log;
+
+/// One stat per tick, so this can poll much faster than a project scan
+const FILE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Payload of the `file-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChangedPayload {
+    pub project_path: String,
+    pub file_path: String,
+    /// False when the change was the file being deleted
+    pub exists: bool,
+}
+
+/// Modification time + size, or None while the file doesn't exist
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &PathBuf) -> Stamp {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((
+        meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        meta.len(),
+    ))
+}
+
+/// Tracks the last seen stamp and when the current burst of changes
+/// started and last changed
+struct FileState {
+    previous: Stamp,
+    first_change: Option<Instant>,
+    changed_at: Option<Instant>,
+}
+
+impl FileState {
+    fn new(previous: Stamp) -> Self {
+        Self {
+            previous,
+            first_change: None,
+            changed_at: None,
+        }
+    }
+
+    /// Record a poll; true once a change has been quiet for `window`, or
+    /// once a file that keeps changing has waited `MAX_DELAY_WINDOWS` windows
+    fn observe(&mut self, current: Stamp, now: Instant, window: Duration) -> bool {
+        if current != self.previous {
+            self.previous = current;
+            self.first_change.get_or_insert(now);
+            self.changed_at = Some(now);
+        }
+        let (Some(first), Some(last)) = (self.first_change, self.changed_at) else {
+            return false;
+        };
+        let quiet = now.duration_since(last) >= window;
+        let overdue = now.duration_since(first) >= window * MAX_DELAY_WINDOWS;
+        if !quiet && !overdue {
+            return false;
+        }
+
+        self.first_change = None;
+        self.changed_at = None;
+        true
+    }
+}
+
+/// Poll `full_path` until `stop` is set, emitting `file-changed` after each
+/// burst of writes settles
+pub fn spawn_file_poller(
+    full_path: PathBuf,
+    payload: FileChangedPayload,
+    stop: Arc<AtomicBool>,
+    app_handle: AppHandle,
+    window: Duration,
+) {
+    thread::spawn(move || {
+        let mut state = FileState::new(stamp(&full_path));
+        while !stop.load(Ordering::Relaxed) {
+            thread::sleep(FILE_POLL_INTERVAL.min(window.max(Duration::from_millis(1))));
+            let current = stamp(&full_path);
+            let exists = current.is_some();
+            if !state.observe(current, Instant::now(), window) || stop.load(Ordering::Relaxed) {
+                continue;
+            }
+            let event = FileChangedPayload {
+                exists,
+                ..payload.clone()
+            };
+            if let Err(e) = app_handle.emit("file-changed", &event) {
+                debug_log::log(
+                    "WATCHER",
+                    &format!("EMIT ERROR for {}: {e}", payload.file_path),
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_once_after_changes_settle() {
+        let window = Duration::from_millis(100);
+        let t0 = Instant::now();
+        let v1 = Some((SystemTime::UNIX_EPOCH, 1));
+        let v2 = Some((SystemTime::UNIX_EPOCH, 2));
+        let mut state = FileState::new(v1);
+
+        assert!(!state.observe(v1, t0, window));
+        assert!(!state.observe(v2, t0, window));
+        assert!(!state.observe(None, t0 + Duration::from_millis(60), window));
+        assert!(state.observe(None, t0 + Duration::from_millis(160), window));
+        assert!(!state.observe(None, t0 + Duration::from_millis(400), window));
+    }
+
+    #[test]
+    fn a_file_that_never_settles_is_still_reported() {
+        let window = Duration::from_millis(100);
+        let t0 = Instant::now();
+        let mut state = FileState::new(None);
+
+        for i in 0..=MAX_DELAY_WINDOWS * 2 {
+            let now = t0 + Duration::from_millis(50) * i;
+            if state.observe(Some((SystemTime::UNIX_EPOCH, u64::from(i))), now, window) {
+                assert_eq!(now.duration_since(t0), window * MAX_DELAY_WINDOWS);
+                return;
+            }
+        }
+        panic!("a constantly changing file was never reported");
+    }
+}
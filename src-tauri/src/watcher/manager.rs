@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::debounce::Debouncer;
+use super::file::{spawn_file_poller, FileChangedPayload};
+use crate::commands::files::skip::should_skip;
+use crate::commands::settings::load_setting;
+use crate::debug_log;
+use crate::project_index::{self, ProjectIndexHandle};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+/// Quiet period before a burst of changes is reported; the
+/// `watcher_debounce_ms` setting overrides it
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+const DEBOUNCE_SETTING: &str = "watcher_debounce_ms";
+
+/// Payload of the `project-files-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct FilesChangedPayload {
+    pub project_path: String,
+    pub paths: Vec<String>,
+}
+
+/// Modification time + size per project-relative file path
+type Snapshot = HashMap<String, (SystemTime, u64)>;
+
+/// Polls watched project trees and emits `project-files-changed` events.
+/// Polling (rather than OS notifications) keeps us dependency-free; the skip
+/// list keeps each scan cheap.
+pub struct WatcherManager {
+    watchers: HashMap<String, Arc<AtomicBool>>,
+    /// Single-file watchers keyed by (project path, file path)
+    file_watchers: HashMap<(String, String), Arc<AtomicBool>>,
+    app_handle: AppHandle,
+}
+
+impl WatcherManager {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            watchers: HashMap::new(),
+            file_watchers: HashMap::new(),
+            app_handle,
+        }
+    }
+
+    /// Start watching a project. Watching an already-watched project is a no-op.
+    pub fn watch(&mut self, project_path: &str) -> Result<(), String> {
+        if self.watchers.contains_key(project_path) {
+            return Ok(());
+        }
+
+        let root = PathBuf::from(project_path);
+        if !root.is_dir() {
+            return Err(format!("Cannot watch missing directory: {project_path}"));
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let app_handle = self.app_handle.clone();
+        let path = project_path.to_string();
+        let debouncer = Debouncer::new(self.debounce_window());
+
+        thread::spawn(move || poll_loop(&root, &path, &thread_stop, &app_handle, debouncer));
+
+        debug_log::log("WATCHER", &format!("Watching {project_path}"));
+        self.watchers.insert(project_path.to_string(), stop);
+        Ok(())
+    }
+
+    fn debounce_window(&self) -> Duration {
+        load_setting(&self.app_handle, DEBOUNCE_SETTING)
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map_or(DEFAULT_DEBOUNCE, Duration::from_millis)
+    }
+
+    /// Stop watching a project. Returns false if it wasn't watched.
+    pub fn unwatch(&mut self, project_path: &str) -> bool {
+        match self.watchers.remove(project_path) {
+            Some(stop) => {
+                stop.store(true, Ordering::Relaxed);
+                debug_log::log("WATCHER", &format!("Stopped watching {project_path}"));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Watch one file (already resolved to `full_path`) and emit
+    /// `file-changed` for it. Watching it again is a no-op.
+    pub fn watch_file(&mut self, project_path: &str, file_path: &str, full_path: PathBuf) {
+        let key = (project_path.to_string(), file_path.to_string());
+        if self.file_watchers.contains_key(&key) {
+            return;
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let payload = FileChangedPayload {
+            project_path: project_path.to_string(),
+            file_path: file_path.to_string(),
+            exists: true,
+        };
+        let window = self.debounce_window();
+        spawn_file_poller(
+            full_path,
+            payload,
+            stop.clone(),
+            self.app_handle.clone(),
+            window,
+        );
+        debug_log::log(
+            "WATCHER",
+            &format!("Watching file {file_path} in {project_path}"),
+        );
+        self.file_watchers.insert(key, stop);
+    }
+
+    /// Stop watching one file. Returns false if it wasn't watched.
+    pub fn unwatch_file(&mut self, project_path: &str, file_path: &str) -> bool {
+        let key = (project_path.to_string(), file_path.to_string());
+        let Some(stop) = self.file_watchers.remove(&key) else {
+            return false;
+        };
+        stop.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Stop every watcher thread
+    pub fn shutdown(&mut self) {
+        let file_watchers = self.file_watchers.drain().map(|(_, stop)| stop);
+        for stop in self
+            .watchers
+            .drain()
+            .map(|(_, stop)| stop)
+            .chain(file_watchers)
+        {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for WatcherManager {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn poll_loop(
+    root: &Path,
+    project_path: &str,
+    stop: &AtomicBool,
+    app_handle: &AppHandle,
+    mut debouncer: Debouncer,
+) {
+    let mut previous = snapshot(root);
+
+    while !stop.load(Ordering::Relaxed) {
+        // Poll faster while a burst is pending so short windows are honoured
+        let interval = match debouncer.has_pending() {
+            true => POLL_INTERVAL.min(debouncer.window()),
+            false => POLL_INTERVAL,
+        };
+        thread::sleep(interval);
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let current = snapshot(root);
+        debouncer.add(changed_paths(&previous, &current), Instant::now());
+        previous = current;
+
+        let Some(paths) = debouncer.take_ready(Instant::now()) else {
+            continue;
+        };
+        emit_changes(app_handle, project_path, paths);
+    }
+}
+
+/// Drop the stale project index and tell the UI which files changed
+fn emit_changes(app_handle: &AppHandle, project_path: &str, paths: Vec<String>) {
+    if let Some(index) = app_handle.try_state::<ProjectIndexHandle>() {
+        project_index::invalidate(&index, project_path);
+    }
+
+    let payload = FilesChangedPayload {
+        project_path: project_path.to_string(),
+        paths,
+    };
+    if let Err(e) = app_handle.emit("project-files-changed", &payload) {
+        debug_log::log("WATCHER", &format!("EMIT ERROR for {project_path}: {e}"));
+    }
+}
+
+fn snapshot(root: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(read) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for item in read.flatten() {
+            if should_skip(&item.file_name().to_string_lossy()) {
+                continue;
+            }
+            let Ok(meta) = item.metadata() else {
+                continue;
+            };
+            let path = item.path();
+            if meta.is_dir() {
+                stack.push(path);
+            } else if let Ok(rel) = path.strip_prefix(root) {
+                let mtime = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.insert(rel.to_string_lossy().to_string(), (mtime, meta.len()));
+            }
+        }
+    }
+
+    files
+}
+
+/// Paths added, removed, or modified between two snapshots, sorted
+fn changed_paths(previous: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut changed: Vec<String> = current
+        .iter()
+        .filter(|(path, stamp)| previous.get(*path) != Some(*stamp))
+        .map(|(path, _)| path.clone())
+        .chain(
+            previous
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        )
+        .collect();
+    changed.sort();
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_paths_reports_adds_removes_and_edits() {
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+
+        let previous: Snapshot = [
+            ("same.rs".to_string(), (t0, 1)),
+            ("edited.rs".to_string(), (t0, 1)),
+            ("removed.rs".to_string(), (t0, 1)),
+        ]
+        .into_iter()
+        .collect();
+        let current: Snapshot = [
+            ("same.rs".to_string(), (t0, 1)),
+            ("edited.rs".to_string(), (t1, 2)),
+            ("added.rs".to_string(), (t1, 1)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            changed_paths(&previous, &current),
+            vec!["added.rs", "edited.rs", "removed.rs"]
+        );
+    }
+
+    #[test]
+    fn snapshot_skips_ignored_directories() {
+        let temp =
+            std::env::temp_dir().join(format!("central_watch_snapshot_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp.join("src")).unwrap();
+        std::fs::create_dir_all(temp.join("node_modules")).unwrap();
+        std::fs::write(temp.join("src").join("main.rs"), "").unwrap();
+        std::fs::write(temp.join("node_modules").join("x.js"), "").unwrap();
+
+        let snap = snapshot(&temp);
+        assert!(snap.contains_key("src/main.rs"));
+        assert_eq!(snap.len(), 1);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
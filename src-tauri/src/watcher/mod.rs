@@ -0,0 +1,17 @@
+mod debounce;
+pub mod file;
+pub mod manager;
+
+use std::sync::{Arc, Mutex};
+
+use tauri::AppHandle;
+
+pub use manager::WatcherManager;
+
+/// Thread-safe handle to the watcher manager
+pub type WatcherHandle = Arc<Mutex<WatcherManager>>;
+
+/// Create a new watcher handle for Tauri state
+pub fn create_watcher_handle(app_handle: AppHandle) -> WatcherHandle {
+    Arc::new(Mutex::new(WatcherManager::new(app_handle)))
+}
@@ -2,6 +2,7 @@ import { describe, it, expect, beforeEach, vi } from "vitest";
 import {
   createSession,
   updateSessionStatus,
+  updateSessionSdkId,
   listSessions,
   addMessage,
   getMessages,
@@ -48,6 +49,18 @@ describe("Agent API — neverthrow Result patterns", () => {
     });
   });
 
+  describe("updateSessionSdkId", () => {
+    it("stores the SDK session id on the session row", async () => {
+      const result = await updateSessionSdkId("s1", "sdk-1");
+
+      expect(result.isOk()).toBe(true);
+      expect(mockExecute).toHaveBeenCalledWith(
+        expect.stringContaining("UPDATE agent_sessions SET sdk_session_id"),
+        ["sdk-1", "s1"],
+      );
+    });
+  });
+
   describe("updateSessionStatus", () => {
     it("returns Ok on successful update", async () => {
       mockExecute.mockResolvedValueOnce({
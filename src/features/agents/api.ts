@@ -174,6 +174,23 @@ async function updateSessionPrompt(
   }
 }
 
+/** Remember the SDK's session id so the conversation can be resumed after a relaunch */
+async function updateSessionSdkId(
+  sessionId: string,
+  sdkSessionId: string,
+): Promise<Result<void, string>> {
+  try {
+    const db = getDb();
+    await db.execute(
+      `UPDATE agent_sessions SET sdk_session_id = $1 WHERE id = $2`,
+      [sdkSessionId, sessionId],
+    );
+    return ok(undefined);
+  } catch (e) {
+    return err(`Failed to save SDK session id: ${String(e)}`);
+  }
+}
+
 async function updateSessionStatus(
   sessionId: string,
   status: AgentStatus,
@@ -339,6 +356,7 @@ export {
   createIdleSession,
   createTerminalSession,
   updateSessionPrompt,
+  updateSessionSdkId,
   updateSessionStatus,
   listSessions,
   addMessage,
@@ -0,0 +1,83 @@
+import { describe, it, expect, beforeEach, vi } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { getSessionConversation } from "./conversation";
+import { mockSelect } from "@/test-setup";
+
+function row(id: string, role: string, content: string | null, thinking: string | null = null) {
+  return {
+    id,
+    session_id: "s1",
+    role,
+    content,
+    thinking,
+    tool_calls: null,
+    usage: null,
+    timestamp: `2026-01-01T00:00:0${id}Z`,
+  };
+}
+
+describe("getSessionConversation", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("passes the stored messages to the backend and returns its conversation", async () => {
+    mockSelect.mockResolvedValueOnce([
+      row("1", "user", "Fix the login bug"),
+      row("2", "assistant", "Looking at auth.ts", "Checking the session code"),
+    ]);
+    const conversation = {
+      sessionId: "s1",
+      messages: [],
+      summary: {
+        firstPrompt: "Fix the login bug",
+        lastAssistantMessage: "Looking at auth.ts",
+        messageCount: 2,
+        costUsd: 0.42,
+      },
+    };
+    vi.mocked(invoke).mockResolvedValueOnce(conversation);
+
+    const result = await getSessionConversation("s1");
+
+    expect(result.isOk() && result.value).toEqual(conversation);
+    expect(invoke).toHaveBeenCalledWith("get_session_conversation", {
+      sessionId: "s1",
+      messages: [
+        {
+          role: "user",
+          content: "Fix the login bug",
+          thinking: null,
+          timestamp: "2026-01-01T00:00:01Z",
+        },
+        {
+          role: "assistant",
+          content: "Looking at auth.ts",
+          thinking: "Checking the session code",
+          timestamp: "2026-01-01T00:00:02Z",
+        },
+      ],
+    });
+  });
+
+  it("returns Err when the backend rejects", async () => {
+    mockSelect.mockResolvedValueOnce([row("1", "user", "hi")]);
+    vi.mocked(invoke).mockRejectedValueOnce(new Error("sidecar locked"));
+
+    const result = await getSessionConversation("s1");
+
+    expect(result.isErr() && result.error).toContain("sidecar locked");
+  });
+
+  it("returns Err when messages can't be read", async () => {
+    mockSelect.mockRejectedValueOnce(new Error("DB locked"));
+
+    const result = await getSessionConversation("s1");
+
+    expect(result.isErr()).toBe(true);
+    if (result.isErr()) {
+      expect(result.error).toContain("DB locked");
+    }
+    expect(invoke).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,56 @@
+import { invoke } from "@tauri-apps/api/core";
+import { ok, err, type Result } from "neverthrow";
+import { getMessages } from "./api";
+import type { Message } from "@/core/types";
+
+/** One turn of a stored conversation, as shown in the resume picker */
+interface ConversationMessage {
+  readonly role: Message["role"];
+  readonly content: string | null;
+  readonly thinking: string | null;
+  readonly timestamp: string;
+}
+
+/** Enough context to recognise a past session at a glance */
+interface ConversationSummary {
+  readonly firstPrompt: string | null;
+  readonly lastAssistantMessage: string | null;
+  readonly messageCount: number;
+  /** Only known for sessions that ran since the app last started */
+  readonly costUsd: number | null;
+}
+
+interface SessionConversation {
+  readonly sessionId: string;
+  readonly messages: readonly ConversationMessage[];
+  readonly summary: ConversationSummary;
+}
+
+/**
+ * A stored session's ordered messages plus a summary, for previewing before resume.
+ * Messages are read here through the SQL plugin; the backend orders them and adds the cost.
+ */
+async function getSessionConversation(
+  sessionId: string,
+): Promise<Result<SessionConversation, string>> {
+  const stored = await getMessages(sessionId);
+  if (stored.isErr()) {
+    return err(`Failed to load conversation: ${stored.error}`);
+  }
+
+  const messages: ConversationMessage[] = stored.value.map(
+    ({ role, content, thinking, timestamp }) => ({ role, content, thinking, timestamp }),
+  );
+  try {
+    const conversation = await invoke<SessionConversation>("get_session_conversation", {
+      sessionId,
+      messages,
+    });
+    return ok(conversation);
+  } catch (e) {
+    return err(`Failed to load conversation: ${String(e)}`);
+  }
+}
+
+export { getSessionConversation };
+export type { SessionConversation, ConversationMessage, ConversationSummary };
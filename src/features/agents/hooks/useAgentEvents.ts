@@ -5,10 +5,18 @@ import { useMessageStore } from "../stores/messageStore";
 import { useUIStore } from "../stores/uiStore";
 import { handleMessageEvent } from "./handlers/handleMessage";
 import { handleSessionCompleted, handleSessionFailed } from "./handlers/handleSessionLifecycle";
+import * as agentApi from "../api";
 import { debugLog } from "@/shared/debugLog";
 import { useUsageStore } from "@/features/usage/store";
 import type { AgentEventPayload, SidecarEvent, ToolApprovalRequest } from "../types";
 
+/** Keep the session resumable after a relaunch; failures are only logged */
+function persistSdkSessionId(sessionId: string, sdkSessionId: string): void {
+  void agentApi.updateSessionSdkId(sessionId, sdkSessionId).then((result) => {
+    if (result.isErr()) debugLog("REACT-EVENT", result.error);
+  });
+}
+
 async function dispatchEvent(event: SidecarEvent): Promise<void> {
   debugLog("REACT-EVENT", `Received: ${event.type} ${JSON.stringify(event).slice(0, 200)}`);
 
@@ -17,6 +25,7 @@ async function dispatchEvent(event: SidecarEvent): Promise<void> {
       useSessionStore.getState().updateSessionStatus(event.sessionId, "running");
       useSessionStore.getState().setSessionStartedAt(event.sessionId, new Date().toISOString());
       useSessionStore.getState().setSdkSessionId(event.sessionId, event.sdkSessionId);
+      persistSdkSessionId(event.sessionId, event.sdkSessionId);
       break;
     case "message":
       handleMessageEvent(event);
@@ -27,9 +36,15 @@ async function dispatchEvent(event: SidecarEvent): Promise<void> {
     case "thinking_delta":
       useMessageStore.getState().appendStreamingThinking(event.sessionId, event.delta);
       break;
+    case "thinking":
+    case "cost_update":
+    case "reprompt_status":
+    case "model_changed":
+    case "permission_mode_changed":
     case "tool_use":
     case "tool_result":
     case "tool_progress":
+    case "turn_interrupted":
       break;
     case "tool_approval_request":
       useUIStore.getState().addPendingApproval({
@@ -41,6 +56,7 @@ async function dispatchEvent(event: SidecarEvent): Promise<void> {
       });
       break;
     case "session_completed":
+      persistSdkSessionId(event.sessionId, event.sdkSessionId);
       await handleSessionCompleted(event.sessionId);
       break;
     case "session_failed":
@@ -0,0 +1,103 @@
+import { describe, it, expect, beforeEach, vi } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
+import { pruneSessions, pruneOnStartup, pruneFilter, PRUNE_POLICY_KEY } from "./prune";
+import { mockExecute, mockSelect } from "@/test-setup";
+
+describe("pruneSessions", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("does nothing without a policy", async () => {
+    const result = await pruneSessions({});
+
+    expect(result.isOk() && result.value).toBe(0);
+    expect(mockExecute).not.toHaveBeenCalled();
+  });
+
+  it("deletes the matching sessions' messages first, then forgets each session", async () => {
+    mockSelect.mockResolvedValueOnce([
+      { id: "a", projectPath: "/code/app" },
+      { id: "b", projectPath: null },
+    ]);
+    mockExecute
+      .mockResolvedValueOnce({ rowsAffected: 12, lastInsertId: 0 })
+      .mockResolvedValueOnce({ rowsAffected: 2, lastInsertId: 0 });
+
+    const result = await pruneSessions({ keepLast: 50 });
+
+    expect(result.isOk() && result.value).toBe(2);
+    expect(mockSelect).toHaveBeenCalledWith(expect.stringContaining("LIMIT $1"), [50]);
+    expect(mockExecute).toHaveBeenNthCalledWith(
+      1,
+      expect.stringContaining("DELETE FROM messages WHERE session_id IN ($1, $2)"),
+      ["a", "b"],
+    );
+    expect(mockExecute).toHaveBeenNthCalledWith(
+      2,
+      expect.stringContaining("DELETE FROM agent_sessions WHERE id IN ($1, $2)"),
+      ["a", "b"],
+    );
+    expect(invoke).toHaveBeenCalledWith("forget_agent_session", {
+      sessionId: "a",
+      projectPath: "/code/app",
+    });
+    expect(invoke).toHaveBeenCalledWith("forget_agent_session", { sessionId: "b", projectPath: null });
+  });
+
+  it("stops when no session matches", async () => {
+    const result = await pruneSessions({ olderThanMs: 1000 });
+
+    expect(result.isOk() && result.value).toBe(0);
+    expect(mockExecute).not.toHaveBeenCalled();
+  });
+
+  it("returns Err when the database fails", async () => {
+    mockSelect.mockRejectedValueOnce("database is locked");
+
+    const result = await pruneSessions({ olderThanMs: 1000 });
+
+    expect(result.isErr()).toBe(true);
+  });
+
+  it("combines both limits and never matches running sessions", () => {
+    const { sql, params } = pruneFilter({ olderThanMs: 1000, keepLast: 5 }, 10_000);
+
+    expect(sql).toContain("status != 'running'");
+    expect(sql).toContain("created_at < $1");
+    expect(sql).toContain("LIMIT $2");
+    expect(params).toEqual([new Date(9000).toISOString(), 5]);
+  });
+});
+
+describe("pruneOnStartup", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("does nothing without a saved policy", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce(null);
+
+    const result = await pruneOnStartup();
+
+    expect(result.isOk() && result.value).toBe(0);
+    expect(invoke).toHaveBeenCalledTimes(1);
+    expect(invoke).toHaveBeenCalledWith("get_setting", { key: PRUNE_POLICY_KEY });
+  });
+
+  it("prunes with the saved policy", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce(JSON.stringify({ olderThanMs: 1000, keepLast: 5 }));
+    mockSelect.mockResolvedValueOnce([
+      { id: "a", projectPath: "/code/app" },
+      { id: "b", projectPath: null },
+    ]);
+    mockExecute
+      .mockResolvedValueOnce({ rowsAffected: 4, lastInsertId: 0 })
+      .mockResolvedValueOnce({ rowsAffected: 2, lastInsertId: 0 });
+
+    const result = await pruneOnStartup();
+
+    expect(result.isOk() && result.value).toBe(2);
+    expect(mockExecute).toHaveBeenCalledTimes(2);
+  });
+});
@@ -0,0 +1,87 @@
+import Database from "@tauri-apps/plugin-sql";
+import { invoke } from "@tauri-apps/api/core";
+import { ok, err, type Result } from "neverthrow";
+import { DB_NAME } from "@/core/constants";
+import { forgetSession } from "./sessionActions";
+
+/** Setting holding the startup prune policy as JSON, e.g. {"olderThanMs": 2592000000, "keepLast": 200} */
+const PRUNE_POLICY_KEY = "session_prune_policy";
+
+interface PrunePolicy {
+  /** Only sessions created more than this long ago are removed */
+  readonly olderThanMs?: number;
+  /** The most recent sessions are always kept */
+  readonly keepLast?: number;
+}
+
+/** WHERE clause matching the sessions a policy removes. Running sessions are never matched. */
+function pruneFilter(policy: PrunePolicy, now: number): { sql: string; params: unknown[] } {
+  const conditions = ["status != 'running'"];
+  const params: unknown[] = [];
+  if (policy.olderThanMs !== undefined) {
+    params.push(new Date(now - policy.olderThanMs).toISOString());
+    conditions.push(`created_at < $${params.length}`);
+  }
+  if (policy.keepLast !== undefined) {
+    params.push(policy.keepLast);
+    conditions.push(
+      `id NOT IN (SELECT id FROM agent_sessions ORDER BY created_at DESC LIMIT $${params.length})`,
+    );
+  }
+  return { sql: conditions.join(" AND "), params };
+}
+
+/**
+ * Delete finished sessions (and their messages) that fall outside the policy.
+ * Running sessions are never removed, and with neither limit set nothing is.
+ * The backend forgets each removed session and its snapshot. Returns the number deleted.
+ */
+async function pruneSessions(policy: PrunePolicy): Promise<Result<number, string>> {
+  if (policy.olderThanMs === undefined && policy.keepLast === undefined) {
+    return ok(0);
+  }
+  try {
+    const db = Database.get(DB_NAME);
+    const { sql, params } = pruneFilter(policy, Date.now());
+    const rows = await db.select<{ id: string; projectPath: string | null }[]>(
+      `SELECT id, (SELECT path FROM projects WHERE projects.id = project_id) AS projectPath
+       FROM agent_sessions WHERE ${sql}`,
+      params,
+    );
+    if (rows.length === 0) return ok(0);
+
+    const ids = rows.map((row) => row.id);
+    const placeholders = ids.map((_, i) => `$${i + 1}`).join(", ");
+    // Messages first so a failure part-way never leaves orphaned rows
+    await db.execute(`DELETE FROM messages WHERE session_id IN (${placeholders})`, ids);
+    const result = await db.execute(`DELETE FROM agent_sessions WHERE id IN (${placeholders})`, ids);
+    await Promise.all(rows.map((row) => forgetSession(row.id, row.projectPath ?? undefined)));
+    return ok(result.rowsAffected);
+  } catch (e) {
+    return err(`Failed to prune sessions: ${String(e)}`);
+  }
+}
+
+function parsePolicy(raw: string): PrunePolicy | null {
+  try {
+    const value: unknown = JSON.parse(raw);
+    if (typeof value !== "object" || value === null) return null;
+    const { olderThanMs, keepLast } = value as Record<string, unknown>;
+    return {
+      olderThanMs: typeof olderThanMs === "number" ? olderThanMs : undefined,
+      keepLast: typeof keepLast === "number" ? keepLast : undefined,
+    };
+  } catch {
+    return null;
+  }
+}
+
+/** Apply the saved prune policy, if one is configured */
+async function pruneOnStartup(): Promise<Result<number, string>> {
+  const raw = await invoke<string | null>("get_setting", { key: PRUNE_POLICY_KEY }).catch(() => null);
+  const policy = raw ? parsePolicy(raw) : null;
+  return policy ? pruneSessions(policy) : ok(0);
+}
+
+export { pruneSessions, pruneOnStartup, pruneFilter, PRUNE_POLICY_KEY };
+export type { PrunePolicy };
@@ -0,0 +1,49 @@
+import { describe, it, expect, beforeEach, vi } from "vitest";
+import { listResumableSessions } from "./resumable";
+import { mockSelect } from "@/test-setup";
+
+const session = {
+  sessionId: "s1",
+  projectPath: "/code/app",
+  prompt: "Fix the bug",
+  model: null,
+  sdkSessionId: "sdk-1",
+  status: "interrupted",
+  createdAt: "2026-01-01T00:00:00.000Z",
+};
+
+describe("listResumableSessions", () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it("reads sessions with an SDK id from live projects", async () => {
+    mockSelect.mockResolvedValueOnce([session]);
+
+    const result = await listResumableSessions();
+
+    expect(mockSelect).toHaveBeenCalledWith(
+      expect.stringMatching(/sdk_session_id IS NOT NULL AND p\.deleted_at IS NULL/),
+    );
+    expect(result.isOk() && result.value).toEqual([session]);
+  });
+
+  it("returns Err for a status it doesn't know", async () => {
+    mockSelect.mockResolvedValueOnce([{ ...session, status: "paused" }]);
+
+    const result = await listResumableSessions();
+
+    expect(result.isErr()).toBe(true);
+    if (result.isErr()) {
+      expect(result.error).toContain("paused");
+    }
+  });
+
+  it("returns Err when the query fails", async () => {
+    mockSelect.mockRejectedValueOnce(new Error("no such table: agent_sessions"));
+
+    const result = await listResumableSessions();
+
+    expect(result.isErr()).toBe(true);
+  });
+});
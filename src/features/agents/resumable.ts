@@ -0,0 +1,73 @@
+import Database from "@tauri-apps/plugin-sql";
+import { invoke } from "@tauri-apps/api/core";
+import { ok, err, type Result } from "neverthrow";
+import { DB_NAME } from "@/core/constants";
+import type { AgentStatus } from "@/core/types";
+
+const AGENT_STATUSES: readonly string[] = [
+  "idle",
+  "running",
+  "completed",
+  "failed",
+  "aborted",
+  "interrupted",
+] satisfies readonly AgentStatus[];
+
+/** What it takes to restart a session's SDK conversation after a relaunch */
+interface ResumableSession {
+  readonly sessionId: string;
+  readonly projectPath: string;
+  readonly prompt: string | null;
+  readonly model: string | null;
+  readonly sdkSessionId: string;
+  /** "interrupted" means the app quit mid-turn */
+  readonly status: AgentStatus;
+  readonly createdAt: string;
+}
+
+/** Row shape of the resumable-session query, status not yet checked */
+type ResumableSessionDto = Omit<ResumableSession, "status"> & { readonly status: string };
+
+const RESUMABLE_SESSIONS_SQL = `
+  SELECT s.id AS sessionId, p.path AS projectPath, s.prompt, s.model,
+         s.sdk_session_id AS sdkSessionId, s.status, s.created_at AS createdAt
+  FROM agent_sessions s JOIN projects p ON p.id = s.project_id
+  WHERE s.sdk_session_id IS NOT NULL AND p.deleted_at IS NULL
+  ORDER BY s.created_at DESC`;
+
+function isAgentStatus(status: string): status is AgentStatus {
+  return AGENT_STATUSES.includes(status);
+}
+
+/**
+ * Sessions from earlier runs that reported an SDK session id, newest first.
+ * Sessions of deleted projects are left out.
+ */
+async function listResumableSessions(): Promise<Result<ResumableSession[], string>> {
+  try {
+    const sessions = await Database.get(DB_NAME).select<ResumableSessionDto[]>(RESUMABLE_SESSIONS_SQL);
+    const unknown = sessions.find((s) => !isAgentStatus(s.status));
+    if (unknown) {
+      return err(`Unknown status "${unknown.status}" for session ${unknown.sessionId}`);
+    }
+    return ok(
+      sessions.flatMap(({ status, ...rest }) => (isAgentStatus(status) ? [{ ...rest, status }] : [])),
+    );
+  } catch (e) {
+    return err(`Failed to list resumable sessions: ${String(e)}`);
+  }
+}
+
+/** Start a worker that continues a stored session's SDK conversation */
+async function resumeSession(session: ResumableSession, prompt: string): Promise<string> {
+  return invoke<string>("start_agent_session", {
+    sessionId: session.sessionId,
+    projectPath: session.projectPath,
+    prompt,
+    model: session.model,
+    resumeSessionId: session.sdkSessionId,
+  });
+}
+
+export { listResumableSessions, resumeSession };
+export type { ResumableSession };
@@ -27,6 +27,14 @@ interface SessionActions {
   readonly setError: (e: string) => void;
 }
 
+/** start_agent_session rejects with `{ code, message }`; other errors are strings or Errors */
+function describeStartError(e: unknown): string {
+  if (typeof e === "object" && e !== null && "message" in e) {
+    return String(e.message);
+  }
+  return String(e);
+}
+
 function addUserMessage(
   sessionId: string,
   content: string,
@@ -60,9 +68,9 @@ async function startNewSession(
     });
     debugLog("REACT", `start_agent_session returned: ${String(returnedId)}`);
   } catch (e) {
-    debugLog("REACT", `start_agent_session FAILED: ${String(e)}`);
+    debugLog("REACT", `start_agent_session FAILED: ${describeStartError(e)}`);
     actions.updateStatus(session.id, "failed");
-    actions.setError(`Failed to start agent: ${String(e)}`);
+    actions.setError(`Failed to start agent: ${describeStartError(e)}`);
   }
 }
 
@@ -90,7 +98,7 @@ async function sendFollowUp(
       });
     } catch (e2) {
       actions.updateStatus(sessionId, "failed");
-      actions.setError(`Failed to start agent: ${String(e2)}`);
+      actions.setError(`Failed to start agent: ${describeStartError(e2)}`);
     }
   }
 }
@@ -125,11 +133,23 @@ async function startIdleSession(
       model: null,
     });
   } catch (e) {
-    debugLog("REACT", `start_agent_session FAILED: ${String(e)}`);
+    debugLog("REACT", `start_agent_session FAILED: ${describeStartError(e)}`);
     actions.updateStatus(sessionId, "failed");
-    actions.setError(`Failed to start agent: ${String(e)}`);
+    actions.setError(`Failed to start agent: ${describeStartError(e)}`);
+  }
+}
+
+/**
+ * Drop the backend's in-memory state for a deleted session, stopping its worker if one is left.
+ * `projectPath` lets the backend find the session's snapshot ref when it no longer knows the session.
+ */
+async function forgetSession(sessionId: string, projectPath?: string): Promise<void> {
+  try {
+    await invoke("forget_agent_session", { sessionId, projectPath: projectPath ?? null });
+  } catch (e) {
+    debugLog("REACT", `forget_agent_session FAILED: ${String(e)}`);
   }
 }
 
-export { startNewSession, sendFollowUp, startIdleSession };
+export { startNewSession, sendFollowUp, startIdleSession, forgetSession };
 export type { SessionActions };
@@ -1,9 +1,11 @@
 import { describe, it, expect, beforeEach, vi } from "vitest";
+import { invoke } from "@tauri-apps/api/core";
 import { ok, err } from "neverthrow";
 import { useAgentStore } from "./store";
 import { useSessionStore } from "./stores/sessionStore";
 import { useMessageStore } from "./stores/messageStore";
 import { useUIStore } from "./stores/uiStore";
+import { useProjectStore } from "@/features/projects/store";
 import { createMockSession, createMockMessage } from "@/shared/test-helpers";
 
 vi.mock("./api", () => ({
@@ -398,11 +400,16 @@ describe("AgentStore", () => {
   });
 
   describe("deleteSession", () => {
-    it("clears session data and persists deletion to SQLite", async () => {
+    it("clears session data, persists deletion to SQLite and tells the backend", async () => {
       const api = await import("./api");
       vi.mocked(api.deleteSession).mockResolvedValue(ok(undefined));
 
       const session = createMockSession();
+      useProjectStore.setState({
+        projects: [
+          { id: session.projectId, path: "/code/app", name: "app", createdAt: "", deletedAt: null },
+        ],
+      });
       const store = useAgentStore.getState();
       store.setSession(session);
       store.addMessage(session.id, createMockMessage({ sessionId: session.id }));
@@ -413,6 +420,10 @@ describe("AgentStore", () => {
       expect(state.sessions.has(session.id)).toBe(false);
       expect(state.messagesBySession.has(session.id)).toBe(false);
       expect(vi.mocked(api.deleteSession)).toHaveBeenCalledWith(session.id);
+      expect(invoke).toHaveBeenCalledWith("forget_agent_session", {
+        sessionId: session.id,
+        projectPath: "/code/app",
+      });
     });
   });
 
@@ -1,5 +1,7 @@
 import * as agentApi from "../api";
+import { forgetSession } from "../sessionActions";
 import { debugLog } from "@/shared/debugLog";
+import { useProjectStore } from "@/features/projects/store";
 import { useSessionStore } from "./sessionStore";
 import { useMessageStore } from "./messageStore";
 import { useUIStore } from "./uiStore";
@@ -11,9 +13,12 @@ function clearSessionData(sessionId: string): void {
 }
 
 function deleteSession(sessionId: string): void {
+  const projectId = useSessionStore.getState().sessions.get(sessionId)?.projectId;
+  const projectPath = useProjectStore.getState().projects.find((p) => p.id === projectId)?.path;
   clearSessionData(sessionId);
   agentApi.deleteSession(sessionId)
     .catch((e: unknown) => { debugLog("STORE", `Persist failed [deleteSession]: ${String(e)}`); });
+  void forgetSession(sessionId, projectPath);
 }
 
 export { clearSessionData, deleteSession };
@@ -1,6 +1,7 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 import * as agentApi from "../api";
+import { pruneOnStartup } from "../prune";
 import { debugLog } from "@/shared/debugLog";
 import type { AgentSession, AgentStatus } from "../types";
 import { useUIStore } from "./uiStore";
@@ -156,6 +157,13 @@ const useSessionStore = create<SessionStore>()((set, get) => ({
       set({ error: interruptResult.error });
     }
 
+    const pruneResult = await pruneOnStartup();
+    if (pruneResult.isErr()) {
+      debugLog("REACT", pruneResult.error);
+    } else if (pruneResult.value > 0) {
+      debugLog("REACT", `Pruned ${pruneResult.value} old sessions`);
+    }
+
     const sessionsResult = await agentApi.getAllSessions();
     if (sessionsResult.isErr()) {
       set({ error: sessionsResult.error, loading: false });
@@ -2,6 +2,8 @@ import type { AgentSession, AgentStatus, Message } from "@/core/types";
 
 /** Matches SidecarEvent from the Rust backend */
 interface AgentEventPayload {
+  /** Per-session sequence number, usable with `replay_agent_events` */
+  readonly seq: number;
   readonly event: SidecarEvent;
 }
 
@@ -38,12 +40,26 @@ type SidecarEvent =
     }
   | { type: "content_delta"; sessionId: string; delta: string }
   | { type: "thinking_delta"; sessionId: string; delta: string }
+  | { type: "thinking"; sessionId: string; text: string; done: boolean }
+  | { type: "cost_update"; sessionId: string; costUsd: number }
+  | { type: "model_changed"; sessionId: string; model: string }
+  | {
+      type: "permission_mode_changed";
+      sessionId: string;
+      mode: "ask" | "auto_edit" | "plan";
+    }
+  | {
+      type: "reprompt_status";
+      sessionId: string;
+      stage: "interrupting" | "interrupted" | "sent";
+    }
   | {
       type: "tool_progress";
       sessionId: string;
       toolName: string;
       elapsedSeconds: number;
     }
+  | { type: "turn_interrupted"; sessionId: string; toolName: string }
   | {
       type: "session_completed";
       sessionId: string;
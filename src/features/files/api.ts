@@ -1,13 +1,17 @@
 import { invoke } from "@tauri-apps/api/core";
 import { ok, err, type Result } from "neverthrow";
-import type { FileTreeEntry, GitStatusInfo, FileDiff } from "./types";
+import type { FileTreeEntry, GitStatusInfo, FileDiff, DiffAlgorithm, FileHash } from "./types";
 
 async function getFileTree(
   projectPath: string,
+  showHidden = false,
+  requestId?: string,
 ): Promise<Result<readonly FileTreeEntry[], string>> {
   try {
     const tree = await invoke<FileTreeEntry[]>("get_file_tree", {
       projectPath,
+      options: { showHidden },
+      requestId: requestId ?? null,
     });
     return ok(tree);
   } catch (e) {
@@ -46,11 +50,13 @@ async function getFileContent(
 async function getDiff(
   projectPath: string,
   filePath?: string,
+  diffAlgorithm?: DiffAlgorithm,
 ): Promise<Result<readonly FileDiff[], string>> {
   try {
     const diffs = await invoke<FileDiff[]>("get_diff", {
       projectPath,
       filePath: filePath ?? null,
+      diffAlgorithm: diffAlgorithm ?? null,
     });
     return ok(diffs);
   } catch (e) {
@@ -58,16 +64,35 @@ async function getDiff(
   }
 }
 
+/** Index vs HEAD: exactly what the next commit will contain */
+async function getStagedDiff(
+  projectPath: string,
+  diffAlgorithm?: DiffAlgorithm,
+): Promise<Result<readonly FileDiff[], string>> {
+  try {
+    const diffs = await invoke<FileDiff[]>("get_staged_diff", {
+      projectPath,
+      diffAlgorithm: diffAlgorithm ?? null,
+    });
+    return ok(diffs);
+  } catch (e) {
+    return err(`Failed to get staged diff: ${String(e)}`);
+  }
+}
+
+/** Pass `expectedHash` (from `hashFile` at open) to refuse clobbering external edits */
 async function writeFile(
   projectPath: string,
   filePath: string,
   content: string,
+  expectedHash?: string,
 ): Promise<Result<undefined, string>> {
   try {
     await invoke<null>("write_file", {
       projectPath,
       filePath,
       content,
+      expectedHash: expectedHash ?? null,
     });
     return ok(undefined);
   } catch (e) {
@@ -75,4 +100,30 @@ async function writeFile(
   }
 }
 
-export { getFileTree, getGitStatus, getFileContent, getDiff, writeFile };
+async function hashFile(
+  projectPath: string,
+  filePath: string,
+): Promise<Result<FileHash, string>> {
+  try {
+    const hash = await invoke<FileHash>("hash_file", { projectPath, filePath });
+    return ok(hash);
+  } catch (e) {
+    return err(`Failed to hash file: ${String(e)}`);
+  }
+}
+
+/** Stop a tree walk or dir-size started with `requestId`; false if none is running */
+async function cancelOperation(requestId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_operation", { requestId });
+}
+
+export {
+  getFileTree,
+  getGitStatus,
+  getFileContent,
+  getDiff,
+  getStagedDiff,
+  writeFile,
+  hashFile,
+  cancelOperation,
+};
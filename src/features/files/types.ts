@@ -5,6 +5,8 @@ interface FileTreeEntry {
   readonly is_dir: boolean;
   readonly children: readonly FileTreeEntry[];
   readonly git_status: GitFileStatus | null;
+  /** Directories only: whether an unexpanded directory has anything to show */
+  readonly has_children?: boolean | null;
 }
 
 /** Git status label for a file */
@@ -51,6 +53,15 @@ interface FileDiff {
   readonly hunks: readonly DiffHunk[];
 }
 
+/** Line-matching algorithm for `get_diff` */
+type DiffAlgorithm = "myers" | "patience" | "minimal";
+
+/** Content hash and size from `hash_file` */
+interface FileHash {
+  readonly hash: string;
+  readonly size: number;
+}
+
 /** View mode for the right pane file viewer */
 type FileViewMode = "content" | "diff";
 
@@ -62,5 +73,7 @@ export type {
   DiffLine,
   DiffHunk,
   FileDiff,
+  DiffAlgorithm,
+  FileHash,
   FileViewMode,
 };
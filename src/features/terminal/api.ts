@@ -16,7 +16,22 @@ interface PtyErrorEvent {
   readonly message: string;
 }
 
-type PtyEvent = PtyOutputEvent | PtyExitEvent | PtyErrorEvent;
+interface PtyStartedEvent {
+  readonly type: "Started";
+}
+
+/** A process started from a terminal, e.g. a dev server */
+interface TerminalChildProcess {
+  readonly pid: number;
+  readonly ppid: number;
+  readonly command: string;
+}
+
+type PtyEvent =
+  | PtyOutputEvent
+  | PtyExitEvent
+  | PtyErrorEvent
+  | PtyStartedEvent;
 
 /** Start a terminal PTY session running `claude` CLI */
 async function startTerminal(
@@ -46,6 +61,15 @@ async function writeTerminalInput(
   await invoke("write_terminal_input", { sessionId, data });
 }
 
+/** Pipe a project file into a PTY session's stdin */
+async function writeTerminalFile(
+  sessionId: string,
+  projectPath: string,
+  filePath: string,
+): Promise<void> {
+  await invoke("write_terminal_file", { sessionId, projectPath, filePath });
+}
+
 /** Resize a PTY session */
 async function resizeTerminal(
   sessionId: string,
@@ -60,10 +84,51 @@ async function closeTerminal(sessionId: string): Promise<void> {
   await invoke("close_terminal", { sessionId });
 }
 
+/** Restart a PTY session in place, keeping its id and channel */
+async function restartTerminal(sessionId: string): Promise<void> {
+  await invoke("restart_terminal", { sessionId });
+}
+
+/** Recent output of a PTY session as text, e.g. to include in an agent prompt */
+async function getTerminalOutput(
+  sessionId: string,
+  lastBytes?: number,
+): Promise<string> {
+  return invoke<string>("get_terminal_output", { sessionId, lastBytes });
+}
+
+/** Resize a PTY session's scrollback; shrinking drops the oldest output */
+async function setTerminalScrollback(
+  sessionId: string,
+  bytes: number,
+): Promise<void> {
+  await invoke("set_terminal_scrollback", { sessionId, bytes });
+}
+
+/** Processes still running under a terminal */
+async function listTerminalChildren(
+  sessionId: string,
+): Promise<TerminalChildProcess[]> {
+  return invoke<TerminalChildProcess[]>("list_terminal_children", { sessionId });
+}
+
+/** SIGTERM everything a terminal started, leaving the terminal open */
+async function killTerminalChildren(
+  sessionId: string,
+): Promise<TerminalChildProcess[]> {
+  return invoke<TerminalChildProcess[]>("kill_terminal_children", { sessionId });
+}
+
 export {
   startTerminal,
   writeTerminalInput,
+  writeTerminalFile,
   resizeTerminal,
   closeTerminal,
+  restartTerminal,
+  getTerminalOutput,
+  setTerminalScrollback,
+  listTerminalChildren,
+  killTerminalChildren,
 };
-export type { PtyEvent };
+export type { PtyEvent, TerminalChildProcess };
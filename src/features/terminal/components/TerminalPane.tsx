@@ -75,6 +75,9 @@ function handlePtyEvent(entry: CachedTerminal, event: PtyEvent): void {
       );
       trackTerminalExit(entry.sessionId);
       break;
+    case "Started":
+      entry.term.clear();
+      break;
     case "Error":
       entry.term.writeln(
         `\r\n\x1b[31m[Error: ${event.message}]\x1b[0m`,
@@ -7,4 +7,17 @@ function debugLog(source: string, message: string): void {
   });
 }
 
-export { debugLog };
+/** Where the backend writes the debug log, or null if logging is off */
+async function getDebugLogPath(): Promise<string | null> {
+  return invoke<string | null>("debug_log_path");
+}
+
+type LogLevel = "error" | "warn" | "info" | "debug";
+
+/** Change backend log verbosity without a restart */
+async function setLogLevel(level: LogLevel): Promise<void> {
+  await invoke("set_log_level", { level });
+}
+
+export { debugLog, getDebugLogPath, setLogLevel };
+export type { LogLevel };
@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+type JsonValue =
+  | string
+  | number
+  | boolean
+  | null
+  | readonly JsonValue[]
+  | { readonly [key: string]: JsonValue };
+
+/** Read a setting with its JSON type intact, or null if unset */
+async function getSettingJson(key: string): Promise<JsonValue | null> {
+  return invoke<JsonValue | null>("get_setting_json", { key });
+}
+
+/** Store a number, boolean, object, … without stringifying it first */
+async function setSettingJson(key: string, value: JsonValue): Promise<void> {
+  await invoke("set_setting_json", { key, value });
+}
+
+/** Read several string settings in one round-trip; unset keys map to null */
+async function getSettings(
+  keys: readonly string[],
+): Promise<Record<string, string | null>> {
+  return invoke<Record<string, string | null>>("get_settings", { keys });
+}
+
+/** Write several string settings at once; all land or none do */
+async function setSettings(entries: Record<string, string>): Promise<void> {
+  await invoke("set_settings", { entries });
+}
+
+/** Remove a setting so its default applies again */
+async function deleteSetting(key: string): Promise<void> {
+  await invoke("delete_setting", { key });
+}
+
+/** Every key currently set, sorted */
+async function listSettings(): Promise<string[]> {
+  return invoke<string[]>("list_settings");
+}
+
+export {
+  getSettingJson,
+  setSettingJson,
+  getSettings,
+  setSettings,
+  deleteSetting,
+  listSettings,
+};
+export type { JsonValue };